@@ -1,4 +1,6 @@
 use amfiteatr_core::agent::TracingAgentGen;
-use crate::amfi::spec::ContractDP;
+use crate::amfi::spec::{BiddingDP, BoardDP, ContractDP};
 
 pub type TracingContractAgent<C, P> = TracingAgentGen<ContractDP, P, C>;
+pub type TracingBiddingAgent<C, P> = TracingAgentGen<BiddingDP, P, C>;
+pub type TracingBoardAgent<C, P> = TracingAgentGen<BoardDP, P, C>;
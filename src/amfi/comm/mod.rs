@@ -1,7 +1,11 @@
 use amfiteatr_core::comm::StdEndpoint;
 use amfiteatr_core::error::CommunicationError;
 use amfiteatr_core::domain::{AgentMessage, EnvironmentMessage};
-use crate::amfi::spec::ContractDP;
+use crate::amfi::spec::{BiddingDP, BoardDP, ContractDP};
 
 pub type ContractAgentSyncComm = StdEndpoint<AgentMessage<ContractDP>, EnvironmentMessage<ContractDP>, CommunicationError<ContractDP>>;
-pub type ContractEnvSyncComm = StdEndpoint<EnvironmentMessage<ContractDP>, AgentMessage<ContractDP>, CommunicationError<ContractDP>>;
\ No newline at end of file
+pub type ContractEnvSyncComm = StdEndpoint<EnvironmentMessage<ContractDP>, AgentMessage<ContractDP>, CommunicationError<ContractDP>>;
+pub type BiddingAgentSyncComm = StdEndpoint<AgentMessage<BiddingDP>, EnvironmentMessage<BiddingDP>, CommunicationError<BiddingDP>>;
+pub type BiddingEnvSyncComm = StdEndpoint<EnvironmentMessage<BiddingDP>, AgentMessage<BiddingDP>, CommunicationError<BiddingDP>>;
+pub type BoardAgentSyncComm = StdEndpoint<AgentMessage<BoardDP>, EnvironmentMessage<BoardDP>, CommunicationError<BoardDP>>;
+pub type BoardEnvSyncComm = StdEndpoint<EnvironmentMessage<BoardDP>, AgentMessage<BoardDP>, CommunicationError<BoardDP>>;
\ No newline at end of file
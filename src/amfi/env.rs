@@ -1,7 +1,4 @@
 use crate::player::side::{Side, SideMap, SIDES};
-use crate::amfi::state::{
-    ContractAction,
-    ContractState};
 use log::warn;
 use amfiteatr_core::{comm::BidirectionalEndpoint};
 use amfiteatr_core::env::{
@@ -13,25 +10,27 @@ use amfiteatr_core::env::{
     ScoreEnvironment,
     StatefulEnvironment};
 use amfiteatr_core::domain::{AgentMessage, DomainParameters, EnvironmentMessage, Reward};
-use crate::error::BridgeCoreError;
 use crate::amfi::spec::ContractDP;
 
-pub struct ContractEnv<S: EnvironmentStateSequential<ContractDP> + ContractState, C: BidirectionalEndpoint>{
+/// Environment of four sides communicating with agents through endpoints.
+/// By default it runs play of contract ([`ContractDP`]), with other domain (e.g. [`BiddingDP`](crate::amfi::spec::BiddingDP))
+/// it runs any game state of four sides.
+pub struct ContractEnv<S: EnvironmentStateSequential<DP>, C: BidirectionalEndpoint, DP: DomainParameters<AgentId = Side> = ContractDP>{
     state: S,
     comm: SideMap<C>,
-    penalties: SideMap<<ContractDP as DomainParameters>::UniversalReward>
+    penalties: SideMap<DP::UniversalReward>
 }
 
 impl<
-    S: EnvironmentStateSequential<ContractDP> + ContractState,
+    DP: DomainParameters<AgentId = Side>,
+    S: EnvironmentStateSequential<DP>,
     C: BidirectionalEndpoint>
-ContractEnv<S, C>{
+ContractEnv<S, C, DP>{
     pub fn new(state: S, comm: SideMap<C>) -> Self{
         Self{
             state,
             comm,
-            penalties: SideMap::new_symmetric(
-                <ContractDP as DomainParameters>::UniversalReward::neutral())
+            penalties: SideMap::new_symmetric(DP::UniversalReward::neutral())
         }
     }
     pub fn replace_state(&mut self, state: S){
@@ -44,11 +43,12 @@ ContractEnv<S, C>{
 }
 
 impl<
-    S: EnvironmentStateSequential<ContractDP> + ContractState,
+    DP: DomainParameters<AgentId = Side>,
+    S: EnvironmentStateSequential<DP>,
     C: BidirectionalEndpoint<
-        OutwardType=EnvironmentMessage<ContractDP>,
-        InwardType=AgentMessage<ContractDP>>>
-CommunicatingEndpointEnvironment<ContractDP> for ContractEnv< S, C>{
+        OutwardType=EnvironmentMessage<DP>,
+        InwardType=AgentMessage<DP>>>
+CommunicatingEndpointEnvironment<DP> for ContractEnv<S, C, DP>{
 
     type CommunicationError = C::Error;
     //type AgentId = Side;
@@ -56,29 +56,30 @@ CommunicatingEndpointEnvironment<ContractDP> for ContractEnv< S, C>{
     fn send_to(
         &mut self,
         agent_id: &Side,
-        message: EnvironmentMessage<ContractDP>)
+        message: EnvironmentMessage<DP>)
         -> Result<(), Self::CommunicationError> {
 
         self.comm[agent_id].send(message)
     }
 
-    fn blocking_receive_from(&mut self, agent_id: &Side) -> Result<AgentMessage<ContractDP>, Self::CommunicationError> {
+    fn blocking_receive_from(&mut self, agent_id: &Side) -> Result<AgentMessage<DP>, Self::CommunicationError> {
         self.comm[agent_id].receive_blocking()
     }
 
-    fn nonblocking_receive_from(&mut self, agent_id: &Side) -> Result<Option<AgentMessage<ContractDP>>, Self::CommunicationError> {
+    fn nonblocking_receive_from(&mut self, agent_id: &Side) -> Result<Option<AgentMessage<DP>>, Self::CommunicationError> {
         self.comm[agent_id].receive_non_blocking()
     }
 }
 
-impl<S: EnvironmentStateSequential<ContractDP> + ContractState,
+impl<DP: DomainParameters<AgentId = Side>,
+    S: EnvironmentStateSequential<DP>,
     C: BidirectionalEndpoint<
-        OutwardType=EnvironmentMessage<ContractDP>,
-        InwardType=AgentMessage<ContractDP>>>
-BroadcastingEndpointEnvironment<ContractDP> for ContractEnv<S, C>
+        OutwardType=EnvironmentMessage<DP>,
+        InwardType=AgentMessage<DP>>>
+BroadcastingEndpointEnvironment<DP> for ContractEnv<S, C, DP>
 where <C as BidirectionalEndpoint>::OutwardType: Clone{
 
-    fn send_to_all(&mut self, message: EnvironmentMessage<ContractDP>) -> Result<(), Self::CommunicationError> {
+    fn send_to_all(&mut self, message: EnvironmentMessage<DP>) -> Result<(), Self::CommunicationError> {
         for s in SIDES{
             match self.comm[&s].send(message.clone()){
                 Ok(_) => {},
@@ -90,9 +91,10 @@ where <C as BidirectionalEndpoint>::OutwardType: Clone{
 }
 
 impl<
-    S: EnvironmentStateSequential<ContractDP> + ContractState,
+    DP: DomainParameters<AgentId = Side>,
+    S: EnvironmentStateSequential<DP>,
     C: BidirectionalEndpoint>
-EnvironmentWithAgents<ContractDP> for ContractEnv<S, C>{
+EnvironmentWithAgents<DP> for ContractEnv<S, C, DP>{
 
     type PlayerIterator = [Side; 4];
 
@@ -102,10 +104,10 @@ EnvironmentWithAgents<ContractDP> for ContractEnv<S, C>{
 }
 
 impl<
-    S: EnvironmentStateSequential<ContractDP> + ContractState + ContractState,
+    DP: DomainParameters<AgentId = Side>,
+    S: EnvironmentStateSequential<DP>,
     C: BidirectionalEndpoint>
-StatefulEnvironment<ContractDP> for ContractEnv<S, C>
-where S: EnvironmentStateSequential<ContractDP> {
+StatefulEnvironment<DP> for ContractEnv<S, C, DP>{
     type State = S;
     //type Updates = <[(Side, ContractStateUpdate);4] as IntoIterator>::IntoIter;
 
@@ -113,28 +115,27 @@ where S: EnvironmentStateSequential<ContractDP> {
         &self.state
     }
 
-    fn process_action(&mut self, agent: &Side, action: &ContractAction)
-        -> Result<<Self::State as EnvironmentStateSequential<ContractDP>>::Updates, BridgeCoreError> {
+    fn process_action(&mut self, agent: &Side, action: &DP::ActionType)
+        -> Result<<Self::State as EnvironmentStateSequential<DP>>::Updates, DP::GameErrorType> {
 
-        self.state.forward(*agent, *action)
+        self.state.forward(*agent, action.clone())
     }
 }
 
 
 impl<
-    S: EnvironmentStateSequential<ContractDP>
-        + ContractState + EnvironmentStateUniScore<ContractDP> ,
+    DP: DomainParameters<AgentId = Side>,
+    S: EnvironmentStateSequential<DP> + EnvironmentStateUniScore<DP>,
     C: BidirectionalEndpoint>
-ScoreEnvironment<ContractDP> for ContractEnv<S, C>
-where S: EnvironmentStateSequential<ContractDP> {
+ScoreEnvironment<DP> for ContractEnv<S, C, DP>{
     fn process_action_penalise_illegal(
         &mut self,
-        agent: &<ContractDP as DomainParameters>::AgentId,
-        action: &<ContractDP as DomainParameters>::ActionType,
-        penalty_reward: <ContractDP as DomainParameters>::UniversalReward)
+        agent: &Side,
+        action: &DP::ActionType,
+        penalty_reward: DP::UniversalReward)
 
         -> Result<
-            <<Self as StatefulEnvironment<ContractDP>>::State as EnvironmentStateSequential<ContractDP>>::Updates, <ContractDP as DomainParameters>::GameErrorType> {
+            <<Self as StatefulEnvironment<DP>>::State as EnvironmentStateSequential<DP>>::Updates, DP::GameErrorType> {
 
         /*
         let state_update =
@@ -157,23 +158,22 @@ where S: EnvironmentStateSequential<ContractDP> {
         }
         */
 
-        self.state.forward(*agent, *action).map_err(|e|{
+        self.state.forward(*agent, action.clone()).inspect_err(|_|{
             self.penalties[agent] += &penalty_reward;
-            e
         })
 
 
     }
 
-    fn actual_state_score_of_player(&self, agent: &<ContractDP as DomainParameters>::AgentId) -> <ContractDP as DomainParameters>::UniversalReward {
+    fn actual_state_score_of_player(&self, agent: &Side) -> DP::UniversalReward {
         self.state.state_score_of_player(agent)
     }
 
-    fn actual_penalty_score_of_player(&self, agent: &<ContractDP as DomainParameters>::AgentId) -> <ContractDP as DomainParameters>::UniversalReward {
-        self.penalties[agent]
+    fn actual_penalty_score_of_player(&self, agent: &Side) -> DP::UniversalReward {
+        self.penalties[agent].clone()
     }
 
-    fn actual_score_of_player(&self, agent: &Side) -> <ContractDP as DomainParameters>::UniversalReward {
+    fn actual_score_of_player(&self, agent: &Side) -> DP::UniversalReward {
         self.state.state_score_of_player(agent)
     }
 
@@ -1,6 +1,10 @@
+mod rule_based;
+
 use amfiteatr_core::agent::Policy;
 use crate::amfi::spec::ContractDP;
 
+pub use rule_based::*;
+
 pub trait ContractPolicy: Policy<ContractDP>{}
 
-impl<P: Policy<ContractDP>> ContractPolicy for P{}
\ No newline at end of file
+impl<P: Policy<ContractDP>> ContractPolicy for P{}
@@ -0,0 +1,268 @@
+use std::marker::PhantomData;
+use karty::cards::{Card, Card2SymTrait, STANDARD_DECK};
+use karty::figures::{Jack, F10};
+use karty::hand::HandTrait;
+use karty::register::Register;
+use karty::suits::{Suit, SUITS};
+use amfiteatr_core::agent::{Policy, PresentPossibleActions};
+use crate::cards::trump::TrumpGen;
+use crate::contract::{Contract, ContractMechanics};
+use crate::player::side::Side;
+use crate::amfi::spec::ContractDP;
+use crate::amfi::state::{ContractAction, ContractInfoSet};
+
+/// Heuristic of card play used by [`RuleBasedPolicy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayRule{
+    /// Lead top card of sequence headed by at least ten (king from K-Q-x, jack from J-10-x)
+    TopOfSequenceLead,
+    /// Without sequence lead fourth highest card of longest suit
+    FourthBestLead,
+    /// Play lowest card in second seat
+    SecondHandLow,
+    /// Play highest card in third seat, unless partner already wins the trick
+    ThirdHandHigh,
+    /// Cover honour (jack or higher) of opponent with the cheapest higher card
+    CoverHonour,
+    /// Ruff when void in suit led and partner does not win the trick
+    RuffWhenProfitable,
+    /// Declarer leads trumps as long as opponents may hold them
+    DrawTrumps
+}
+
+pub const PLAY_RULES: [PlayRule; 7] = [PlayRule::TopOfSequenceLead, PlayRule::FourthBestLead, PlayRule::SecondHandLow,
+    PlayRule::ThirdHandHigh, PlayRule::CoverHonour, PlayRule::RuffWhenProfitable, PlayRule::DrawTrumps];
+
+/// Set of enabled play rules, by default all rules are enabled
+/// ```
+/// use brydz_core::amfi::{PlayRule, PlayRules};
+/// let rules = PlayRules::all().with(PlayRule::FourthBestLead, false);
+/// assert!(rules.is_enabled(PlayRule::TopOfSequenceLead));
+/// assert!(!rules.is_enabled(PlayRule::FourthBestLead));
+/// assert_eq!(PlayRules::none().with(PlayRule::FourthBestLead, true).enabled().collect::<Vec<_>>(), vec![PlayRule::FourthBestLead]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayRules{
+    enabled: [bool; PLAY_RULES.len()]
+}
+
+impl PlayRules{
+    pub fn all() -> Self{
+        Self{enabled: [true; PLAY_RULES.len()]}
+    }
+    pub fn none() -> Self{
+        Self{enabled: [false; PLAY_RULES.len()]}
+    }
+    pub fn with(mut self, rule: PlayRule, enabled: bool) -> Self{
+        self.enabled[rule as usize] = enabled;
+        self
+    }
+    pub fn is_enabled(&self, rule: PlayRule) -> bool{
+        self.enabled[rule as usize]
+    }
+    pub fn enabled(&self) -> impl Iterator<Item = PlayRule> + '_{
+        PLAY_RULES.into_iter().filter(|rule| self.is_enabled(*rule))
+    }
+}
+
+impl Default for PlayRules{
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Baseline policy of card play following classic rules of thumb (see [`PlayRule`]).
+/// When no enabled rule applies, side leads lowest card of its longest suit, wins trick as cheaply as possible
+/// unless partner is winning it, and discards lowest card outside trumps.
+/// Works for every contract information set presenting possible actions, declarer chooses cards of dummy too.
+/// ```
+/// use amfiteatr_core::agent::Policy;
+/// use brydz_core::amfi::RuleBasedPolicy;
+/// use brydz_core::amfi::state::{ContractAction, ContractAgentInfoSetSimple};
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::{Contract, ContractParametersGen};
+/// use brydz_core::player::side::Side::{East, North};
+/// use karty::card_set;
+/// use karty::cards::*;
+/// let contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+/// let hand = card_set![KING_SPADES, QUEEN_SPADES, JACK_SPADES, FOUR_SPADES, ACE_HEARTS, EIGHT_HEARTS, SEVEN_HEARTS,
+///     SIX_HEARTS, TWO_HEARTS, NINE_DIAMONDS, FOUR_DIAMONDS, THREE_CLUBS, TWO_CLUBS];
+/// let info_set = ContractAgentInfoSetSimple::new(East, hand, contract, None);
+/// let policy = RuleBasedPolicy::<ContractAgentInfoSetSimple>::default();
+/// assert_eq!(policy.select_action(&info_set), Some(ContractAction::PlaceCard(KING_SPADES)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuleBasedPolicy<IS>{
+    rules: PlayRules,
+    _info_set: PhantomData<IS>
+}
+
+impl<IS> RuleBasedPolicy<IS>{
+    pub fn new(rules: PlayRules) -> Self{
+        Self{rules, _info_set: PhantomData}
+    }
+    pub fn rules(&self) -> &PlayRules{
+        &self.rules
+    }
+}
+
+impl<IS> Default for RuleBasedPolicy<IS>{
+    fn default() -> Self {
+        Self::new(PlayRules::default())
+    }
+}
+
+fn trump_suit(contract: &Contract) -> Option<Suit>{
+    match contract.trump(){
+        TrumpGen::Colored(suit) => Some(*suit),
+        TrumpGen::NoTrump => None
+    }
+}
+
+fn lowest<'a>(cards: impl Iterator<Item = &'a Card>) -> Option<Card>{
+    cards.min_by_key(|c| c.figure()).copied()
+}
+
+/// Cards of suit, from the highest
+fn suit_cards(cards: &[Card], suit: Suit) -> Vec<Card>{
+    let mut in_suit: Vec<Card> = cards.iter().filter(|c| c.suit() == suit).copied().collect();
+    in_suit.sort_by_key(|c| std::cmp::Reverse(c.figure()));
+    in_suit
+}
+
+fn beats(card: &Card, winner: &Card, trump: Option<Suit>) -> bool{
+    match card.suit() == winner.suit(){
+        true => card.figure() > winner.figure(),
+        false => Some(card.suit()) == trump
+    }
+}
+
+fn trick_winner(contract: &Contract) -> Option<(Side, Card)>{
+    let trick = contract.current_trick();
+    let trump = trump_suit(contract);
+    let mut side = trick.first_player_side();
+    let mut winner = (side, trick[side]?);
+    for _ in 1..trick.count_cards(){
+        side = side.next();
+        if let Some(card) = trick[side]{
+            if beats(&card, &winner.1, trump){
+                winner = (side, card);
+            }
+        }
+    }
+    Some(winner)
+}
+
+/// Cards of suit that are neither played nor seen by player (in own hand or dummy)
+fn hidden_cards<IS: ContractInfoSet>(state: &IS, suit: Suit) -> Vec<Card>{
+    STANDARD_DECK.iter().filter(|c| c.suit() == suit
+        && !state.contract_data().used_cards().is_registered(c)
+        && !state.hand().contains(c)
+        && !state.dummy_hand().is_some_and(|dummy| dummy.contains(c)))
+        .copied().collect()
+}
+
+impl<IS: ContractInfoSet> RuleBasedPolicy<IS>{
+    fn lead(&self, state: &IS, legal: &[Card]) -> Card{
+        let contract = state.contract_data();
+        let trump = trump_suit(contract);
+        if let Some(trump) = trump{
+            let trumps = suit_cards(legal, trump);
+            let hidden = hidden_cards(state, trump);
+            if self.rules.is_enabled(PlayRule::DrawTrumps) && state.side() == contract.declarer()
+                && !trumps.is_empty() && !hidden.is_empty(){
+                return match hidden.iter().all(|c| c.figure() < trumps[0].figure()){
+                    true => trumps[0],
+                    false => trumps[trumps.len() - 1]
+                }
+            }
+        }
+        let side_suits: Vec<Vec<Card>> = SUITS.iter().filter(|s| Some(**s) != trump)
+            .map(|s| suit_cards(legal, *s)).filter(|cards| !cards.is_empty()).collect();
+        if self.rules.is_enabled(PlayRule::TopOfSequenceLead){
+            let sequence = side_suits.iter().filter(|cards| cards.len() >= 2 && cards[0].figure() >= F10
+                && STANDARD_DECK.iter().filter(|c| c.suit() == cards[0].suit()
+                    && c.figure() < cards[0].figure() && c.figure() > cards[1].figure())
+                    .all(|c| contract.used_cards().is_registered(c)))
+                .max_by_key(|cards| cards.len());
+            if let Some(cards) = sequence{
+                return cards[0]
+            }
+        }
+        let longest = side_suits.iter().max_by_key(|cards| cards.len());
+        if self.rules.is_enabled(PlayRule::FourthBestLead){
+            if let Some(cards) = longest.filter(|cards| cards.len() >= 4){
+                return cards[3]
+            }
+        }
+        match longest{
+            Some(cards) => cards[cards.len() - 1],
+            None => lowest(legal.iter()).expect("Lead is chosen from non empty hand")
+        }
+    }
+
+    fn follow(&self, state: &IS, legal: &[Card]) -> Card{
+        let contract = state.contract_data();
+        let trump = trump_suit(contract);
+        let position = contract.current_trick().count_cards();
+        let (winner_side, winner) = trick_winner(contract).expect("Following card is chosen in started trick");
+        let partner_winning = winner_side == contract.current_side().partner();
+        let cheapest_winner = lowest(legal.iter().filter(|c| beats(c, &winner, trump)));
+        let lowest_card = lowest(legal.iter()).expect("Card is chosen from non empty hand");
+
+        if !legal.iter().any(|c| Some(c.suit()) == contract.current_trick().called_suit()){
+            return self.discard(legal, trump, partner_winning, cheapest_winner)
+        }
+        if self.rules.is_enabled(PlayRule::CoverHonour) && position < 3 && !partner_winning && winner.figure() >= Jack{
+            if let Some(card) = cheapest_winner{
+                return card
+            }
+        }
+        match position{
+            1 if self.rules.is_enabled(PlayRule::SecondHandLow) => lowest_card,
+            2 if self.rules.is_enabled(PlayRule::ThirdHandHigh) => {
+                let highest = legal.iter().max_by_key(|c| c.figure()).copied().unwrap_or(lowest_card);
+                match !partner_winning && beats(&highest, &winner, trump){
+                    true => highest,
+                    false => lowest_card
+                }
+            },
+            _ => match partner_winning{
+                true => lowest_card,
+                false => cheapest_winner.unwrap_or(lowest_card)
+            }
+        }
+    }
+
+    fn discard(&self, legal: &[Card], trump: Option<Suit>, partner_winning: bool, cheapest_winner: Option<Card>) -> Card{
+        if self.rules.is_enabled(PlayRule::RuffWhenProfitable) && !partner_winning{
+            if let Some(ruff) = cheapest_winner.filter(|c| Some(c.suit()) == trump){
+                return ruff
+            }
+        }
+        lowest(legal.iter().filter(|c| Some(c.suit()) != trump))
+            .or_else(|| lowest(legal.iter()))
+            .expect("Card is chosen from non empty hand")
+    }
+}
+
+impl<IS: ContractInfoSet + PresentPossibleActions<ContractDP>> Policy<ContractDP> for RuleBasedPolicy<IS>{
+    type InfoSetType = IS;
+
+    fn select_action(&self, state: &Self::InfoSetType) -> Option<ContractAction> {
+        let legal: Vec<Card> = state.available_actions().into_iter().filter_map(|action| match action{
+            ContractAction::PlaceCard(card) => Some(card),
+            _ => None
+        }).collect();
+        if legal.is_empty(){
+            return None
+        }
+        Some(ContractAction::PlaceCard(match state.contract_data().current_trick().is_empty(){
+            true => self.lead(state, &legal),
+            false => self.follow(state, &legal)
+        }))
+    }
+}
@@ -1,7 +1,7 @@
 use amfiteatr_core::domain::DomainParameters;
 use crate::error::BridgeCoreError;
 use crate::player::side::Side;
-use crate::amfi::state::{ContractAction, ContractStateUpdate};
+use crate::amfi::state::{BiddingAction, BiddingStateUpdate, BoardAction, BoardStateUpdate, ContractAction, ContractStateUpdate};
 
 #[derive(Clone, Copy, Debug)]
 pub struct ContractDP {
@@ -14,4 +14,32 @@ impl DomainParameters for ContractDP {
     type UpdateType = ContractStateUpdate;
     type AgentId = Side;
     type UniversalReward = i32;
-}
\ No newline at end of file
+}
+
+/// Domain of auction, agents make calls
+#[derive(Clone, Copy, Debug)]
+pub struct BiddingDP {
+
+}
+
+impl DomainParameters for BiddingDP {
+    type ActionType = BiddingAction;
+    type GameErrorType = BridgeCoreError;
+    type UpdateType = BiddingStateUpdate;
+    type AgentId = Side;
+    type UniversalReward = i32;
+}
+
+/// Domain of whole board: auction followed by play
+#[derive(Clone, Copy, Debug)]
+pub struct BoardDP {
+
+}
+
+impl DomainParameters for BoardDP {
+    type ActionType = BoardAction;
+    type GameErrorType = BridgeCoreError;
+    type UpdateType = BoardStateUpdate;
+    type AgentId = Side;
+    type UniversalReward = i32;
+}
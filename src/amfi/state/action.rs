@@ -1,48 +1,191 @@
 use std::fmt::{Display, Formatter};
-use karty::cards::Card;
+use karty::cards::{Card, Card2SymTrait, DECK_SIZE};
+use karty::figures::Figure;
 use karty::hand::CardSet;
+use karty::suits::Suit;
+use karty::symbol::CardSymbol;
+use crate::bidding::Call;
+use crate::meta::QUARTER_SIZE;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
 pub enum ContractAction{
     ShowHand(CardSet),
-    PlaceCard(Card)
+    PlaceCard(Card),
+    /// Claim of number of remaining tricks for axis of acting side, the rest is conceded
+    Claim(u8)
 }
 impl Display for ContractAction{
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match f.alternate(){
             true => match self{
                 ContractAction::ShowHand(h) => write!(f, "Hand|{h:#}|"),
-                ContractAction::PlaceCard(c) => write!(f, "{c:#}")
+                ContractAction::PlaceCard(c) => write!(f, "{c:#}"),
+                ContractAction::Claim(t) => write!(f, "Claim|{t}|")
             }
             false => match self {
                 ContractAction::ShowHand(h) => write!(f, "Hand|{h:}|"),
-                ContractAction::PlaceCard(c) => write!(f, "{c:}")
+                ContractAction::PlaceCard(c) => write!(f, "{c:}"),
+                ContractAction::Claim(t) => write!(f, "Claim|{t}|")
             }
         }
     }
 }
 
+impl ContractAction{
+    /// Index of action used by tensor representations: index of card,
+    /// or `DECK_SIZE + n` for claim of `n` tricks (as if claims were fifth suit after cards).
+    /// Showing hand (exclusive move of dummy) has no index.
+    /// ```
+    /// use brydz_core::amfi::state::ContractAction;
+    /// use karty::cards::{ACE_SPADES, DECK_SIZE};
+    /// assert_eq!(ContractAction::PlaceCard(ACE_SPADES).index(), Some(DECK_SIZE - 1));
+    /// assert_eq!(ContractAction::Claim(3).index(), Some(DECK_SIZE + 3));
+    /// assert_eq!(ContractAction::from_index(DECK_SIZE + 3), Some(ContractAction::Claim(3)));
+    /// ```
+    pub fn index(&self) -> Option<usize>{
+        match self{
+            ContractAction::ShowHand(_) => None,
+            ContractAction::PlaceCard(c) => Some(c.usize_index()),
+            ContractAction::Claim(tricks) => Some(DECK_SIZE + *tricks as usize)
+        }
+    }
+
+    pub fn from_index(index: usize) -> Option<Self>{
+        match index.checked_sub(DECK_SIZE){
+            None => Card::from_usize_index(index).ok().map(Self::PlaceCard),
+            Some(tricks) if tricks <= QUARTER_SIZE => Some(Self::Claim(tricks as u8)),
+            Some(_) => None
+        }
+    }
+
+    /// Two value code of action used in histories of actions, the same scheme as [`index`](ContractAction::index):
+    /// card is `[suit + 1, figure + 1]`, claim of `n` tricks is `[5, n + 1]` (fifth suit)
+    /// and showing hand is `[0, 0]`.
+    /// ```
+    /// use brydz_core::amfi::state::ContractAction;
+    /// use karty::cards::TWO_CLUBS;
+    /// use karty::hand::{CardSet, HandTrait};
+    /// assert_eq!(ContractAction::PlaceCard(TWO_CLUBS).code(), [1, 1]);
+    /// assert_eq!(ContractAction::Claim(0).code(), [5, 1]);
+    /// assert_eq!(ContractAction::ShowHand(CardSet::empty()).code(), [0, 0]);
+    /// ```
+    pub fn code(&self) -> [u8; 2]{
+        match self{
+            ContractAction::ShowHand(_) => [0, 0],
+            ContractAction::PlaceCard(c) => [c.suit().usize_index() as u8 + 1, c.figure().usize_index() as u8 + 1],
+            ContractAction::Claim(tricks) => [Suit::SYMBOL_SPACE as u8 + 1, tricks + 1]
+        }
+    }
+
+    /// Decodes action from [`code`](ContractAction::code), shown hand can not be restored from it.
+    pub fn from_code(code: [u8; 2]) -> Option<Self>{
+        match code{
+            [0, _] | [_, 0] => None,
+            [suit, tricks] if suit as usize == Suit::SYMBOL_SPACE + 1 => match tricks as usize - 1 <= QUARTER_SIZE{
+                true => Some(Self::Claim(tricks - 1)),
+                false => None
+            },
+            [suit, figure] => Some(Self::PlaceCard(Card::new(
+                Figure::from_usize_index(figure as usize - 1).ok()?,
+                Suit::from_usize_index(suit as usize - 1).ok()?)))
+        }
+    }
+}
+
 impl Action for ContractAction{}
 
+/// Call made by agent in auction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct BiddingAction(pub Call<Suit>);
+
+impl BiddingAction{
+    pub fn call(&self) -> &Call<Suit>{
+        &self.0
+    }
+}
+
+impl Display for BiddingAction{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match f.alternate(){
+            true => write!(f, "{:#}", self.0),
+            false => write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl From<Call<Suit>> for BiddingAction{
+    fn from(call: Call<Suit>) -> Self {
+        Self(call)
+    }
+}
+
+impl Action for BiddingAction{}
+
+/// Action of agent in whole board: call in auction or action in play
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub enum BoardAction{
+    Call(Call<Suit>),
+    ShowHand(CardSet),
+    PlaceCard(Card),
+    Claim(u8)
+}
+
+impl Display for BoardAction{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match f.alternate(){
+            true => match self{
+                BoardAction::Call(c) => write!(f, "{c:#}"),
+                BoardAction::ShowHand(h) => write!(f, "Hand|{h:#}|"),
+                BoardAction::PlaceCard(c) => write!(f, "{c:#}"),
+                BoardAction::Claim(t) => write!(f, "Claim|{t}|")
+            }
+            false => match self{
+                BoardAction::Call(c) => write!(f, "{c}"),
+                BoardAction::ShowHand(h) => write!(f, "Hand|{h}|"),
+                BoardAction::PlaceCard(c) => write!(f, "{c}"),
+                BoardAction::Claim(t) => write!(f, "Claim|{t}|")
+            }
+        }
+    }
+}
+
+impl From<BiddingAction> for BoardAction{
+    fn from(action: BiddingAction) -> Self {
+        Self::Call(action.0)
+    }
+}
+
+impl From<ContractAction> for BoardAction{
+    fn from(action: ContractAction) -> Self {
+        match action{
+            ContractAction::ShowHand(h) => Self::ShowHand(h),
+            ContractAction::PlaceCard(c) => Self::PlaceCard(c),
+            ContractAction::Claim(t) => Self::Claim(t)
+        }
+    }
+}
+
+impl Action for BoardAction{}
+
 
 #[cfg(feature = "torch")]
 mod neuro_impls{
     use amfiteatr_rl::tch::Tensor;
-    use karty::cards::{Card};
-    use karty::symbol::CardSymbol;
     use amfiteatr_core::error::ConvertError;
     use amfiteatr_rl::tensor_data::ActionTensor;
     use crate::amfi::state::ContractAction;
 
 
 
-
+    /// Actions are encoded with [`ContractAction::index`]
     impl ActionTensor for ContractAction{
         fn to_tensor(&self) -> Tensor {
-            match self{
-                ContractAction::ShowHand(_) => panic!("Show hand is not expected to be converted to tensor - this is exclusive move of dummy"),
-                ContractAction::PlaceCard(c) => Tensor::from_slice(&[c.usize_index() as f32;1])
+            match self.index(){
+                None => panic!("Show hand is not expected to be converted to tensor - this is exclusive move of dummy"),
+                Some(index) => Tensor::from_slice(&[index as f32;1])
             }
         }
 
@@ -53,11 +196,9 @@ mod neuro_impls{
                     return Err(ConvertError::ActionDeserialize(format!("{}: {e:}", t)))
                 }
             };
-            let action_index = v[0];
-            Card::from_usize_index(action_index as usize)
-                .map_err(|e| ConvertError::ActionDeserialize(format!("Bad index of card: {e:}")))
-                .map(|ok| Self::PlaceCard(ok))
-
+            let action_index = v[0] as usize;
+            Self::from_index(action_index)
+                .ok_or_else(|| ConvertError::ActionDeserialize(format!("Bad index of action: {action_index}")))
         }
     }
 }
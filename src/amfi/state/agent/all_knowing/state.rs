@@ -6,7 +6,7 @@ use karty::register::Register;
 use amfiteatr_core::agent::{InformationSet, PresentPossibleActions, EvaluatedInformationSet};
 use amfiteatr_core::domain::{DomainParameters};
 
-use crate::contract::{Contract, ContractMechanics, ContractParameters};
+use crate::contract::{Claim, Contract, ContractMechanics, ContractParameters};
 use crate::deal::{DescriptionDeckDeal};
 use crate::error::{BridgeCoreError, BridgeCoreErrorGen};
 use crate::error::ContractErrorGen::CardNotInHand;
@@ -55,6 +55,7 @@ impl InformationSet<ContractDP> for ContractAgentInfoSetAllKnowing{
 
     fn is_action_valid(&self, action: &ContractAction) -> bool {
         match action{
+            ContractAction::Claim(tricks) => self.contract.dummy() != self.side && *tricks <= self.contract.remaining_tricks(),
             ContractAction::ShowHand(_h) => {
                 self.contract.dummy() == self.side
             }
@@ -77,6 +78,7 @@ impl InformationSet<ContractDP> for ContractAgentInfoSetAllKnowing{
     fn update(&mut self, update: ContractStateUpdate) -> Result<(), BridgeCoreError> {
         let (side, action) = update.into_tuple();
         match action{
+            ContractAction::Claim(tricks) => Ok(self.contract.accept_claim(Claim::new(side, tricks))?),
             ContractAction::ShowHand(dhand) => {
                 let local_dhand = self.dummy_hand().unwrap();
                 if local_dhand != &dhand{
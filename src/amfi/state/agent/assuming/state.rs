@@ -1,16 +1,16 @@
 use std::ops::{Deref};
-use log::debug;
+use log::{debug, warn};
+use rand::Rng;
 use smallvec::SmallVec;
 use karty::cards::{Card, Card2SymTrait};
 use karty::hand::{CardSet, HandSuitedTrait, HandTrait};
-use karty::register::Register;
 use amfiteatr_core::agent::{InformationSet, PresentPossibleActions, EvaluatedInformationSet};
 use amfiteatr_core::domain::{DomainParameters};
-use crate::contract::{Contract, ContractMechanics, ContractParameters};
+use crate::contract::{Claim, Contract, ContractMechanics, ContractParameters};
 use crate::deal::{BiasedHandDistribution, DealDistribution, DescriptionDeckDeal};
-use crate::error::BridgeCoreError;
+use crate::error::{BridgeCoreError, DistributionError};
 use crate::meta::HAND_SIZE;
-use crate::player::side::Side;
+use crate::player::side::{Side, SideMap};
 use crate::amfi::spec::ContractDP;
 use crate::amfi::state::{ContractAction, ContractInfoSet, ContractStateUpdate, CreatedContractInfoSet, RenewableContractInfoSet, StateWithSide};
 
@@ -26,11 +26,36 @@ pub struct ContractAgentInfoSetAssuming {
 impl ContractAgentInfoSetAssuming{
     #[allow(dead_code)]
     pub fn new(side: Side, hand: CardSet, contract: Contract, dummy_hand: Option<CardSet>, card_distribution: BiasedHandDistribution) -> Self{
-        Self{side, hand, dummy_hand, contract, card_distribution}
+        let mut info_set = Self{side, hand, dummy_hand, contract, card_distribution};
+        if let Err(e) = info_set.update_distribution(){
+            warn!("Agent {}: distribution assumption is inconsistent with game: {e:?}", info_set.side);
+        }
+        info_set
     }
     #[allow(dead_code)]
     pub fn new_fair(side: Side, hand: CardSet, contract: Contract, dummy_hand: Option<CardSet>) -> Self{
-        Self{side, hand, dummy_hand, contract, card_distribution: Default::default()}
+        Self::new(side, hand, contract, dummy_hand, Default::default())
+    }
+    /// Creates info set with beliefs estimated from distribution of deal in `descript`.
+    /// Unlike conversion with [`From`], which assumes fair deal for constrained distribution,
+    /// this samples deals of constrained distribution using `rng`.
+    pub fn from_description<R: Rng + ?Sized>(side: Side, params: &ContractParameters, descript: &DescriptionDeckDeal, rng: &mut R) -> Self{
+        let hand = descript.cards[&side];
+        let distr = match &descript.probabilities{
+            DealDistribution::Constrained(generator) => {
+                let known = SideMap::new_with_fn(|s| match s == side{
+                    true => hand,
+                    false => CardSet::empty()
+                });
+                BiasedHandDistribution::from_generator(generator, &known, CONSTRAINED_PRIOR_SAMPLES, rng)
+                    .unwrap_or_else(|e|{
+                        warn!("Agent {side}: failed to estimate distribution from deal generator, assuming fair deal: {e:?}");
+                        Default::default()
+                    })
+            },
+            probabilities => cheap_prior(probabilities)
+        };
+        Self::new(side, hand, Contract::new(params.clone()), None, distr)
     }
 
     pub fn side(&self) -> &Side{
@@ -50,12 +75,15 @@ impl ContractAgentInfoSetAssuming{
     }
 
     pub fn possibly_has_card(&self, side: Side, card: &Card) -> bool{
+        self.card_possible_in_game(side, card) && !self.card_distribution[side][card].is_zero()
+    }
+
+    /// Checks if side can hold card considering only what is known for sure (own hand, dummy, play history),
+    /// ignoring distribution assumption.
+    fn card_possible_in_game(&self, side: Side, card: &Card) -> bool{
         if !self.contract.side_possibly_has_card(side, card){
             return false;
         }
-        if self.card_distribution[side][card].is_zero(){
-            return false;
-        }
         if side == self.side && !self.hand.contains(card){
             return false;
         }
@@ -86,7 +114,23 @@ impl ContractAgentInfoSetAssuming{
         false
     }
 
-
+    /// Renormalises distribution assumption to current state of game: played cards are removed,
+    /// suits that side showed out of are zeroed for it, own and dummy's cards become certain
+    /// and remaining probability is rescaled to number of cards each side holds.
+    /// When assumption contradicts game (e.g. it excluded card that was later shown),
+    /// uniform distribution over possible cards is used instead.
+    fn update_distribution(&mut self) -> Result<(), DistributionError>{
+        let cards_left = SideMap::new_with_fn(|side| self.contract.cards_left(side) as u8);
+        let mut distribution = self.card_distribution.clone();
+        if let Err(e) = distribution.renormalise(|side, card| self.card_possible_in_game(side, card), &cards_left){
+            debug!("Agent {}: renormalising distribution assumption failed ({e:?}), falling back to uniform", self.side);
+            distribution = BiasedHandDistribution::default();
+            distribution.renormalise(|side, card| self.card_possible_in_game(side, card), &cards_left)
+                .map_err(|_| DistributionError::NoConsistentLayout)?;
+        }
+        self.card_distribution = distribution;
+        Ok(())
+    }
 
 }
 
@@ -100,6 +144,7 @@ impl InformationSet<ContractDP> for ContractAgentInfoSetAssuming {
 
     fn is_action_valid(&self, action: &ContractAction) -> bool {
         match action{
+            ContractAction::Claim(tricks) => self.contract.dummy() != self.side && *tricks <= self.contract.remaining_tricks(),
             ContractAction::ShowHand(_h) => {
                 self.contract.dummy() == self.side
             }
@@ -123,12 +168,13 @@ impl InformationSet<ContractDP> for ContractAgentInfoSetAssuming {
         //debug!("Agent {} received state update: {:?}", self.side, &update);
         let (side, action) = update.into_tuple();
         match action{
+            ContractAction::Claim(tricks) => Ok(self.contract.accept_claim(Claim::new(side, tricks))?),
             ContractAction::ShowHand(dhand) => match side{
                 s if s == self.contract.dummy() => match self.dummy_hand{
                     Some(_) => panic!("Behavior when dummy shows hand second time"),
                     None => {
                         self.dummy_hand = Some(dhand);
-                        Ok(())
+                        Ok(self.update_distribution()?)
                     }
 
                 }
@@ -153,7 +199,7 @@ impl InformationSet<ContractDP> for ContractAgentInfoSetAssuming {
                         dh.remove_card(&card)?
                     }
                 }
-                Ok(())
+                Ok(self.update_distribution()?)
 
             }
         }
@@ -221,18 +267,15 @@ impl RenewableContractInfoSet for ContractAgentInfoSetAssuming{
         self.hand = hand;
         self.contract = contract;
         self.dummy_hand = dummy_hand;
+        if let Err(e) = self.update_distribution(){
+            warn!("Agent {}: distribution assumption is inconsistent with game: {e:?}", self.side);
+        }
     }
 }
 
 impl CreatedContractInfoSet for ContractAgentInfoSetAssuming{
     fn create_new(side: Side, hand: CardSet, contract: Contract, dummy_hand: Option<CardSet>, distribution: BiasedHandDistribution) -> Self {
-        Self{
-            side,
-            hand,
-            dummy_hand,
-            contract,
-            card_distribution: distribution
-        }
+        Self::new(side, hand, contract, dummy_hand, distribution)
     }
 }
 
@@ -259,64 +302,29 @@ impl ContractInfoSet for ContractAgentInfoSetAssuming{
         &self.hand
     }
 
+    /// Posterior probability of card in side's hand, renormalised after every update
     fn hint_card_probability_for_player(&self, side: Side, card: &Card) -> f32 {
-        if self.contract.card_used().is_registered(card){
-            return 0.0;
-        }
-        if self.contract.suits_exhausted().is_registered(&(side, card.suit())){
-            return 0.0;
-        }
-        if self.side == side{
-            return match self.hand.contains(card){
-                true => 1.0,
-                false => 0.0
-            };
-        } else {
-            if self.hand.contains(card){
-                return 0.0; //this player has, other cant
-            }
-            if let Some(d) = self.dummy_hand{
-                //dummy shown
-                if side == self.contract.dummy(){
-                    //check dummys card
-                    return match d.contains(card){
-                        true => 1.0,
-                        false => 0.0
-                    }
-                }
-                if d.contains(card){
-                    return 0.0;
-                }
-
-                //neither self nor dummy, card not marked as used
-                let initial_proba_this: f32 = self.card_distribution[self.side][card].into();
-                let initial_proba_dummy: f32 = self.card_distribution[self.contract.dummy()][card].into();
-                let remaining_proba = 1.0 - initial_proba_dummy - initial_proba_this;
-                assert!(remaining_proba >= 0.0);
-                let c_proba: f32 = self.card_distribution[side][card].into();
-                c_proba / remaining_proba
-
-
-            } else {
-                //dummy not shown, anyone except this can have
-                let initial_proba_this: f32 = self.card_distribution[self.side][card].into();
-                let remaining_proba = 1.0 -  initial_proba_this;
-                let c_proba: f32 = self.card_distribution[side][card].into();
-                return c_proba / remaining_proba;
-            }
-        }
+        self.card_distribution[side][card].into()
     }
 }
+/// Number of deals sampled from generator of constrained distribution to estimate beliefs of agent
+const CONSTRAINED_PRIOR_SAMPLES: usize = 500;
+
+/// Beliefs of agent before play that do not require sampling, constrained distribution is treated as fair
+fn cheap_prior(probabilities: &DealDistribution) -> BiasedHandDistribution{
+    match probabilities{
+        DealDistribution::Biased(biased) => biased.deref().clone(),
+        DealDistribution::Fair | DealDistribution::Constrained(_) => Default::default()
+    }
+}
+
+/// Does not sample constrained distribution, see [`ContractAgentInfoSetAssuming::from_description`]
 impl From<(Side, ContractParameters, DescriptionDeckDeal,)> for ContractAgentInfoSetAssuming{
 
     fn from(base: (Side, ContractParameters, DescriptionDeckDeal,)) -> Self {
         let (side, params, descript) = base;
 
-         let distr = match descript.probabilities{
-            DealDistribution::Fair => Default::default(),
-            DealDistribution::Biased(biased) => biased.deref().clone()
-        };
-
+        let distr = cheap_prior(&descript.probabilities);
         let contract = Contract::new(params);
         Self::new(side, descript.cards[&side] , contract, None, distr)
     }
@@ -325,11 +333,7 @@ impl From<(&Side, &ContractParameters, &DescriptionDeckDeal,)> for ContractAgent
     fn from(base: (&Side, &ContractParameters, &DescriptionDeckDeal,)) -> Self {
         let (side, params, descript) = base;
 
-        let distr = match &descript.probabilities{
-            DealDistribution::Fair => Default::default(),
-            DealDistribution::Biased(biased) => biased.deref().clone()
-        };
-
+        let distr = cheap_prior(&descript.probabilities);
         let contract = Contract::new(params.clone());
         Self::new(*side, descript.cards[&side], contract, None, distr)
     }
@@ -0,0 +1,104 @@
+use karty::hand::CardSet;
+use karty::suits::Suit;
+use log::debug;
+use amfiteatr_core::agent::{EvaluatedInformationSet, InformationSet, PresentPossibleActions};
+use crate::bidding::{AuctionStack, Call, GeneralDeclarationStorage};
+use crate::error::BridgeCoreError;
+use crate::player::side::Side;
+use crate::amfi::spec::BiddingDP;
+use crate::amfi::state::{BiddingAction, BiddingStateUpdate, StateWithSide};
+
+/// Information set of side in auction: own hand and public history of calls.
+/// ```
+/// use amfiteatr_core::agent::{InformationSet, PresentPossibleActions};
+/// use brydz_core::amfi::state::{BiddingAction, BiddingAgentInfoSet, BiddingStateUpdate};
+/// use brydz_core::bidding::{Bid, Call};
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::player::side::Side::*;
+/// use karty::hand::{CardSet, HandTrait};
+/// use karty::suits::Suit::Spades;
+/// let mut info_set = BiddingAgentInfoSet::new(East, CardSet::empty(), North);
+/// assert!(info_set.available_actions().is_empty());
+/// let seven_spades = Call::NewBid(Bid::init(TrumpGen::Colored(Spades), 7).unwrap());
+/// info_set.update(BiddingStateUpdate::new(North, BiddingAction(seven_spades))).unwrap();
+/// // East can pass, double, or bid 7NT
+/// assert_eq!(info_set.available_actions().len(), 3);
+/// assert!(info_set.is_action_valid(&BiddingAction(Call::Double)));
+/// assert!(!info_set.is_action_valid(&BiddingAction(Call::Redouble)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BiddingAgentInfoSet{
+    side: Side,
+    hand: CardSet,
+    dealer: Side,
+    auction: AuctionStack<Suit, GeneralDeclarationStorage<Suit>>,
+}
+
+impl BiddingAgentInfoSet{
+    pub fn new(side: Side, hand: CardSet, dealer: Side) -> Self{
+        Self{side, hand, dealer, auction: AuctionStack::new_with_dealer(dealer)}
+    }
+    pub fn hand(&self) -> &CardSet{
+        &self.hand
+    }
+    pub fn dealer(&self) -> Side{
+        self.dealer
+    }
+    pub fn auction(&self) -> &AuctionStack<Suit, GeneralDeclarationStorage<Suit>>{
+        &self.auction
+    }
+    /// Side to make next call, `None` when auction is finished.
+    pub fn current_side(&self) -> Option<Side>{
+        self.auction.current_side()
+    }
+
+    fn is_call_legal(&self, call: Call<Suit>) -> bool{
+        self.auction.legal_calls().any(|legal| legal == call)
+    }
+}
+
+impl InformationSet<BiddingDP> for BiddingAgentInfoSet{
+    fn agent_id(&self) -> &Side {
+        &self.side
+    }
+
+    fn is_action_valid(&self, action: &BiddingAction) -> bool {
+        self.current_side() == Some(self.side) && self.is_call_legal(action.0)
+    }
+
+    fn update(&mut self, update: BiddingStateUpdate) -> Result<(), BridgeCoreError> {
+        debug!("Agent {} received bidding update: {:#}", self.side, &update);
+        let (side, action) = update.into_tuple();
+        self.auction.add_contract_bid(side, action.0)?;
+        Ok(())
+    }
+}
+
+impl PresentPossibleActions<BiddingDP> for BiddingAgentInfoSet{
+    type ActionIteratorType = Vec<BiddingAction>;
+
+    fn available_actions(&self) -> Self::ActionIteratorType {
+        if self.current_side() != Some(self.side){
+            return Vec::new()
+        }
+        self.auction.legal_calls().map(BiddingAction).collect()
+    }
+}
+
+impl EvaluatedInformationSet<BiddingDP> for BiddingAgentInfoSet{
+    type RewardType = i32;
+
+    fn current_subjective_score(&self) -> Self::RewardType {
+        0
+    }
+
+    fn penalty_for_illegal(&self) -> Self::RewardType {
+        -100
+    }
+}
+
+impl StateWithSide for BiddingAgentInfoSet{
+    fn id(&self) -> Side {
+        self.side
+    }
+}
@@ -0,0 +1,159 @@
+use karty::hand::CardSet;
+use amfiteatr_core::agent::{EvaluatedInformationSet, InformationSet, PresentPossibleActions};
+use crate::contract::{Contract, ContractMechanics};
+use crate::deal::Vulnerability;
+use crate::error::{BiddingErrorGen, BridgeCoreError, ContractErrorGen};
+use crate::player::side::Side;
+use crate::score::sport::ScoreTableSport;
+use crate::amfi::spec::BoardDP;
+use crate::amfi::state::{BiddingAction, BiddingAgentInfoSet, BiddingStateUpdate, BoardAction, BoardStateUpdate, ContractAction, ContractAgentInfoSetSimple, ContractDummyState, ContractInfoSet, ContractStateUpdate, StateWithSide};
+
+/// Information set used in play phase, dummy only shows hand.
+#[derive(Debug, Clone)]
+enum PlayInfoSet{
+    Player(ContractAgentInfoSetSimple),
+    Dummy(ContractDummyState)
+}
+
+impl PlayInfoSet{
+    fn contract(&self) -> &Contract{
+        match self{
+            PlayInfoSet::Player(p) => p.contract_data(),
+            PlayInfoSet::Dummy(d) => d.contract_data()
+        }
+    }
+}
+
+/// Information set of side in whole board: auction history and then information set of play.
+/// ```
+/// use amfiteatr_core::agent::{InformationSet, PresentPossibleActions};
+/// use brydz_core::amfi::state::{BoardAction, BoardAgentInfoSet, BoardStateUpdate};
+/// use brydz_core::bidding::{Bid, Call};
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::deal::{fair_bridge_deal, Vulnerability};
+/// use brydz_core::player::side::Side::*;
+/// use karty::hand::CardSet;
+/// let hands = fair_bridge_deal::<CardSet>();
+/// let mut info_set = BoardAgentInfoSet::new(East, hands[&East], North, Vulnerability::None);
+/// let one_no_trump = Call::NewBid(Bid::init(TrumpGen::NoTrump, 1).unwrap());
+/// for (side, call) in [(North, one_no_trump), (East, Call::Pass), (South, Call::Pass), (West, Call::Pass)]{
+///     info_set.update(BoardStateUpdate::new(side, BoardAction::Call(call))).unwrap();
+/// }
+/// assert!(info_set.contract().is_some());
+/// // East leads
+/// assert_eq!(info_set.available_actions().len(), 13);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardAgentInfoSet{
+    side: Side,
+    hand: CardSet,
+    bidding: BiddingAgentInfoSet,
+    play: Option<PlayInfoSet>,
+    score_table: ScoreTableSport,
+}
+
+impl BoardAgentInfoSet{
+    pub fn new(side: Side, hand: CardSet, dealer: Side, vulnerability: Vulnerability) -> Self{
+        Self{
+            side,
+            hand,
+            bidding: BiddingAgentInfoSet::new(side, hand, dealer),
+            play: None,
+            score_table: ScoreTableSport::with_vulnerability(vulnerability)
+        }
+    }
+    /// Initial hand
+    pub fn hand(&self) -> &CardSet{
+        &self.hand
+    }
+    pub fn bidding(&self) -> &BiddingAgentInfoSet{
+        &self.bidding
+    }
+    /// Contract in play, `None` during auction and when board was passed out
+    pub fn contract(&self) -> Option<&Contract>{
+        self.play.as_ref().map(|p| p.contract())
+    }
+}
+
+impl InformationSet<BoardDP> for BoardAgentInfoSet{
+    fn agent_id(&self) -> &Side {
+        &self.side
+    }
+
+    fn is_action_valid(&self, action: &BoardAction) -> bool {
+        match (action, &self.play){
+            (BoardAction::Call(call), None) => self.bidding.is_action_valid(&BiddingAction(*call)),
+            (BoardAction::ShowHand(hand), Some(PlayInfoSet::Dummy(d))) => d.is_action_valid(&ContractAction::ShowHand(*hand)),
+            (BoardAction::PlaceCard(card), Some(PlayInfoSet::Player(p))) => p.is_action_valid(&ContractAction::PlaceCard(*card)),
+            (BoardAction::Claim(tricks), Some(PlayInfoSet::Player(p))) => p.is_action_valid(&ContractAction::Claim(*tricks)),
+            _ => false
+        }
+    }
+
+    fn update(&mut self, update: BoardStateUpdate) -> Result<(), BridgeCoreError> {
+        let (side, action) = update.into_tuple();
+        match (action, &mut self.play){
+            (BoardAction::Call(call), None) => {
+                self.bidding.update(BiddingStateUpdate::new(side, BiddingAction(call)))?;
+                if let (true, Some(parameters)) = (self.bidding.auction().is_finished(), self.bidding.auction().current_contract()){
+                    let contract = Contract::new(parameters.clone());
+                    self.play = Some(match contract.dummy() == self.side{
+                        true => PlayInfoSet::Dummy(ContractDummyState::new(self.side, self.hand, contract)),
+                        false => PlayInfoSet::Player(ContractAgentInfoSetSimple::new(self.side, self.hand, contract, None))
+                    });
+                }
+                Ok(())
+            },
+            (BoardAction::Call(_), Some(_)) => Err(BiddingErrorGen::AuctionFinished.into()),
+            (_, None) => Err(ContractErrorGen::AuctionNotFinished.into()),
+            (BoardAction::ShowHand(hand), Some(play)) => match play{
+                PlayInfoSet::Player(p) => p.update(ContractStateUpdate::new(side, ContractAction::ShowHand(hand))),
+                PlayInfoSet::Dummy(d) => d.update(ContractStateUpdate::new(side, ContractAction::ShowHand(hand)))
+            },
+            (BoardAction::PlaceCard(card), Some(play)) => match play{
+                PlayInfoSet::Player(p) => p.update(ContractStateUpdate::new(side, ContractAction::PlaceCard(card))),
+                PlayInfoSet::Dummy(d) => d.update(ContractStateUpdate::new(side, ContractAction::PlaceCard(card)))
+            },
+            (BoardAction::Claim(tricks), Some(play)) => match play{
+                PlayInfoSet::Player(p) => p.update(ContractStateUpdate::new(side, ContractAction::Claim(tricks))),
+                PlayInfoSet::Dummy(d) => d.update(ContractStateUpdate::new(side, ContractAction::Claim(tricks)))
+            }
+        }
+    }
+}
+
+impl PresentPossibleActions<BoardDP> for BoardAgentInfoSet{
+    type ActionIteratorType = Vec<BoardAction>;
+
+    fn available_actions(&self) -> Self::ActionIteratorType {
+        match &self.play{
+            None => self.bidding.available_actions().into_iter().map(BoardAction::from).collect(),
+            Some(PlayInfoSet::Player(p)) => p.available_actions().into_iter().map(BoardAction::from).collect(),
+            Some(PlayInfoSet::Dummy(d)) => d.available_actions().into_iter().map(BoardAction::from).collect()
+        }
+    }
+}
+
+impl EvaluatedInformationSet<BoardDP> for BoardAgentInfoSet{
+    type RewardType = i32;
+
+    /// Score of own axis when board is completed, 0 before
+    fn current_subjective_score(&self) -> Self::RewardType {
+        match &self.play{
+            Some(play) if play.contract().is_completed() => self.score_table
+                .completed_contract_points(play.contract(), self.side.axis())
+                .unwrap_or(0),
+            _ => 0
+        }
+    }
+
+    fn penalty_for_illegal(&self) -> Self::RewardType {
+        -100
+    }
+}
+
+impl StateWithSide for BoardAgentInfoSet{
+    fn id(&self) -> Side {
+        self.side
+    }
+}
@@ -1,6 +1,6 @@
 use smallvec::{SmallVec, smallvec};
 use karty::hand::{HandTrait, CardSet};
-use crate::contract::{Contract, ContractMechanics, ContractParameters};
+use crate::contract::{Claim, Contract, ContractMechanics, ContractParameters};
 use crate::error::BridgeCoreError;
 use crate::player::side::Side;
 use crate::amfi::state::{ContractAction, ContractStateUpdate, StateWithSide};
@@ -23,6 +23,9 @@ impl ContractDummyState {
     pub fn new(side: Side, hand: CardSet, contract: Contract) -> Self{
         Self{side, hand, contract}
     }
+    pub fn contract_data(&self) -> &Contract{
+        &self.contract
+    }
 }
 
 
@@ -33,6 +36,7 @@ impl InformationSet<ContractDP> for ContractDummyState {
 
     fn is_action_valid(&self, action: &ContractAction) -> bool {
         match action{
+            ContractAction::Claim(_) => false,
             ContractAction::ShowHand(_) => true,
             ContractAction::PlaceCard(_) => false
         }
@@ -42,6 +46,7 @@ impl InformationSet<ContractDP> for ContractDummyState {
         let (side, action) = update.into_tuple();
 
         match action{
+            ContractAction::Claim(tricks) => Ok(self.contract.accept_claim(Claim::new(side, tricks))?),
             ContractAction::ShowHand(h) =>{
                 debug!("Dummy ({}) got state update of shown hand {:#}", side, h);
                 Ok(())
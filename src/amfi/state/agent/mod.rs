@@ -8,6 +8,8 @@ mod assuming;
 
 mod state_id;
 mod all_knowing;
+mod bidding;
+mod board;
 
 #[cfg(feature = "torch")]
 mod tensor_convert;
@@ -21,6 +23,8 @@ pub use traits::*;
 pub use state_id::*;
 pub use all_knowing::*;
 pub use assuming::*;
+pub use bidding::*;
+pub use board::*;
 
 #[cfg(feature = "torch")]
 pub use tensor_convert::*;
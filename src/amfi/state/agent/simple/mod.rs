@@ -1,6 +1,6 @@
 use smallvec::SmallVec;
 use karty::hand::{HandSuitedTrait, HandTrait, CardSet};
-use crate::contract::{Contract, ContractMechanics, ContractParameters};
+use crate::contract::{Claim, Contract, ContractMechanics, ContractParameters};
 use crate::error::BridgeCoreError;
 use crate::meta::HAND_SIZE;
 use crate::player::side::Side;
@@ -107,6 +107,7 @@ impl InformationSet<ContractDP> for ContractAgentInfoSetSimple {
 
     fn is_action_valid(&self, action: &ContractAction) -> bool {
         match action{
+            ContractAction::Claim(tricks) => self.contract.dummy() != self.side && *tricks <= self.contract.remaining_tricks(),
             ContractAction::ShowHand(_h) => {
                 self.contract.dummy() == self.side
             }
@@ -130,6 +131,7 @@ impl InformationSet<ContractDP> for ContractAgentInfoSetSimple {
         //debug!("Agent {} received state update: {:?}", self.side, &update);
         let (side, action) = update.into_tuple();
         match action{
+            ContractAction::Claim(tricks) => Ok(self.contract.accept_claim(Claim::new(side, tricks))?),
             ContractAction::ShowHand(dhand) => match side{
                 s if s == self.contract.dummy() => match self.dummy_hand{
                     Some(_) => panic!("Behavior when dummy shows hand second time"),
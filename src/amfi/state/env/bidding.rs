@@ -0,0 +1,98 @@
+use karty::hand::CardSet;
+use karty::suits::Suit;
+use log::debug;
+use amfiteatr_core::env::{EnvironmentStateSequential, EnvironmentStateUniScore};
+use crate::bidding::{AuctionStack, GeneralDeclarationStorage};
+use crate::contract::ContractParameters;
+use crate::deal::DescriptionDeckDeal;
+use crate::error::BridgeCoreError;
+use crate::player::side::{Side, SideMap};
+use crate::player::side::Side::*;
+use crate::amfi::spec::BiddingDP;
+use crate::amfi::state::{BiddingAction, BiddingStateUpdate};
+
+/// Environment state of auction. Calls are checked by [`AuctionStack`] and published to all sides.
+/// Auction itself is not scored, every side gets neutral score.
+/// ```
+/// use amfiteatr_core::env::EnvironmentStateSequential;
+/// use brydz_core::amfi::state::{BiddingAction, BiddingEnvState};
+/// use brydz_core::bidding::{Bid, Call};
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::deal::fair_bridge_deal;
+/// use brydz_core::player::side::Side::*;
+/// use karty::hand::CardSet;
+/// use karty::suits::Suit::Hearts;
+/// let mut state = BiddingEnvState::new(fair_bridge_deal::<CardSet>(), East);
+/// assert_eq!(state.current_player(), Some(East));
+/// state.forward(East, BiddingAction(Call::NewBid(Bid::init(TrumpGen::Colored(Hearts), 1).unwrap()))).unwrap();
+/// assert!(state.forward(North, BiddingAction(Call::Pass)).is_err());
+/// for side in [South, West, North]{
+///     state.forward(side, BiddingAction(Call::Pass)).unwrap();
+/// }
+/// assert!(state.is_finished());
+/// assert_eq!(state.contract_parameters().unwrap().declarer(), East);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BiddingEnvState{
+    hands: SideMap<CardSet>,
+    dealer: Side,
+    auction: AuctionStack<Suit, GeneralDeclarationStorage<Suit>>,
+}
+
+impl BiddingEnvState{
+    pub fn new(hands: SideMap<CardSet>, dealer: Side) -> Self{
+        Self{hands, dealer, auction: AuctionStack::new_with_dealer(dealer)}
+    }
+    pub fn hands(&self) -> &SideMap<CardSet>{
+        &self.hands
+    }
+    pub fn dealer(&self) -> Side{
+        self.dealer
+    }
+    pub fn auction(&self) -> &AuctionStack<Suit, GeneralDeclarationStorage<Suit>>{
+        &self.auction
+    }
+    /// Contract resulting from auction, `None` when auction is not finished or deal was passed out.
+    pub fn contract_parameters(&self) -> Option<&ContractParameters>{
+        match self.auction.is_finished(){
+            true => self.auction.current_contract(),
+            false => None
+        }
+    }
+}
+
+impl EnvironmentStateSequential<BiddingDP> for BiddingEnvState{
+    type Updates = [(Side, BiddingStateUpdate);4];
+
+    fn current_player(&self) -> Option<Side> {
+        self.auction.current_side()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.auction.is_finished()
+    }
+
+    fn forward(&mut self, side: Side, action: BiddingAction) -> Result<Self::Updates, BridgeCoreError> {
+        debug!("Translating bidding state by agent {:} using call {:#}", &side, &action);
+        self.auction.add_contract_bid(side, action.0)?;
+        let update = BiddingStateUpdate::new(side, action);
+        Ok([
+            (North, update),
+            (East, update),
+            (South, update),
+            (West, update)])
+    }
+}
+
+impl EnvironmentStateUniScore<BiddingDP> for BiddingEnvState{
+    fn state_score_of_player(&self, _agent: &Side) -> i32 {
+        0
+    }
+}
+
+impl From<(Side, &DescriptionDeckDeal)> for BiddingEnvState{
+    fn from(base: (Side, &DescriptionDeckDeal)) -> Self {
+        let (dealer, description) = base;
+        Self::new(description.cards, dealer)
+    }
+}
@@ -0,0 +1,151 @@
+use karty::hand::CardSet;
+use karty::suits::Suit;
+use log::debug;
+use rand::Rng;
+use amfiteatr_core::env::{EnvironmentStateSequential, EnvironmentStateUniScore};
+use crate::bidding::{AuctionStack, GeneralDeclarationStorage};
+use crate::contract::Contract;
+use crate::deal::{Board, DealDistribution, Vulnerability};
+use crate::error::{BiddingErrorGen, BridgeCoreError, ContractErrorGen, DistributionError};
+use crate::player::side::{Side, SideMap};
+use crate::score::sport::ScoreTableSport;
+use crate::amfi::spec::BoardDP;
+use crate::amfi::state::{BiddingAction, BiddingEnvState, BoardAction, BoardStateUpdate, ContractAction, ContractEnvStateComplete, ContractState};
+
+/// Environment state of whole board: auction and then play of resulting contract.
+/// Completed board is scored with [`ScoreTableSport`], passed out board scores 0.
+/// ```
+/// use amfiteatr_core::env::{EnvironmentStateSequential, EnvironmentStateUniScore};
+/// use brydz_core::amfi::state::{BoardAction, BoardEnvState};
+/// use brydz_core::bidding::Call;
+/// use brydz_core::deal::{fair_bridge_deal, Vulnerability};
+/// use brydz_core::player::side::Side::*;
+/// use karty::hand::CardSet;
+/// let mut state = BoardEnvState::new(fair_bridge_deal::<CardSet>(), South, Vulnerability::EastWest);
+/// for side in [South, West, North, East]{
+///     state.forward(side, BoardAction::Call(Call::Pass)).unwrap();
+/// }
+/// assert!(state.is_finished());
+/// assert!(state.contract().is_none());
+/// assert_eq!(state.state_score_of_player(&West), 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoardEnvState{
+    bidding: BiddingEnvState,
+    play: Option<ContractEnvStateComplete>,
+    score_table: ScoreTableSport,
+}
+
+impl BoardEnvState{
+    pub fn new(hands: SideMap<CardSet>, dealer: Side, vulnerability: Vulnerability) -> Self{
+        Self{
+            bidding: BiddingEnvState::new(hands, dealer),
+            play: None,
+            score_table: ScoreTableSport::with_vulnerability(vulnerability)
+        }
+    }
+
+    /// Creates state with deal, dealer and vulnerability of board
+    pub fn from_board(board: &Board) -> Self{
+        Self::new(*board.deal(), board.dealer(), board.vulnerability())
+    }
+
+    /// Creates board with cards dealt from distribution
+    /// ```
+    /// use brydz_core::amfi::state::BoardEnvState;
+    /// use brydz_core::deal::{board_rng, DealDistribution, Vulnerability};
+    /// use brydz_core::player::side::Side::North;
+    /// let first = BoardEnvState::new_dealt(&DealDistribution::Fair, North, Vulnerability::None, &mut board_rng(3, 1)).unwrap();
+    /// let second = BoardEnvState::new_dealt(&DealDistribution::Fair, North, Vulnerability::None, &mut board_rng(3, 1)).unwrap();
+    /// assert_eq!(first.hands(), second.hands());
+    /// ```
+    pub fn new_dealt<R: Rng + ?Sized>(distribution: &DealDistribution, dealer: Side, vulnerability: Vulnerability, rng: &mut R) -> Result<Self, DistributionError>{
+        distribution.generate(rng).map(|hands| Self::new(hands, dealer, vulnerability))
+    }
+
+    /// Initial hands of players
+    pub fn hands(&self) -> &SideMap<CardSet>{
+        self.bidding.hands()
+    }
+    pub fn dealer(&self) -> Side{
+        self.bidding.dealer()
+    }
+    pub fn auction(&self) -> &AuctionStack<Suit, GeneralDeclarationStorage<Suit>>{
+        self.bidding.auction()
+    }
+    /// Contract in play, `None` during auction and when board was passed out
+    pub fn contract(&self) -> Option<&Contract>{
+        self.play.as_ref().map(|p| p.contract_data())
+    }
+    pub fn score_table(&self) -> &ScoreTableSport{
+        &self.score_table
+    }
+
+    fn start_play(&mut self){
+        if let Some(parameters) = self.bidding.contract_parameters(){
+            let declarer = parameters.declarer();
+            let hands = self.bidding.hands();
+            debug!("Auction finished, starting play of contract {:?}", parameters);
+            self.play = Some(ContractEnvStateComplete::new(Contract::new(parameters.clone()),
+                hands[&declarer], hands[&declarer.next_i(1)],
+                hands[&declarer.next_i(2)], hands[&declarer.next_i(3)]));
+        }
+    }
+}
+
+impl EnvironmentStateSequential<BoardDP> for BoardEnvState{
+    type Updates = [(Side, BoardStateUpdate);4];
+
+    fn current_player(&self) -> Option<Side> {
+        match &self.play{
+            None => self.bidding.current_player(),
+            Some(play) => play.current_player()
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        match &self.play{
+            None => self.bidding.is_finished(),
+            Some(play) => play.is_finished()
+        }
+    }
+
+    fn forward(&mut self, side: Side, action: BoardAction) -> Result<Self::Updates, BridgeCoreError> {
+        match (action, &mut self.play){
+            (BoardAction::Call(call), None) => {
+                let updates = self.bidding.forward(side, BiddingAction(call))?;
+                if self.bidding.is_finished(){
+                    self.start_play();
+                }
+                Ok(updates.map(|(s, u)| (s, u.into())))
+            },
+            (BoardAction::Call(_), Some(_)) => Err(BiddingErrorGen::AuctionFinished.into()),
+            (_, None) => Err(ContractErrorGen::AuctionNotFinished.into()),
+            (action, Some(play)) => {
+                match play.current_player(){
+                    Some(expected) if expected == side => {},
+                    Some(expected) => return Err(ContractErrorGen::CurrentSidePresume(expected, side).into()),
+                    None => return Err(ContractErrorGen::ContractFull.into())
+                }
+                let action = match action{
+                    BoardAction::ShowHand(hand) if side == play.dummy_side() => ContractAction::ShowHand(hand),
+                    BoardAction::PlaceCard(card) => ContractAction::PlaceCard(card),
+                    BoardAction::Claim(tricks) => ContractAction::Claim(tricks),
+                    _ => return Err(ContractErrorGen::DummyCardSetMissmatch.into())
+                };
+                Ok(play.forward(side, action)?.map(|(s, u)| (s, u.into())))
+            }
+        }
+    }
+}
+
+impl EnvironmentStateUniScore<BoardDP> for BoardEnvState{
+    fn state_score_of_player(&self, agent: &Side) -> i32 {
+        match &self.play{
+            Some(play) if play.is_finished() => self.score_table
+                .completed_contract_points(play.contract_data(), agent.axis())
+                .unwrap_or(0),
+            _ => 0
+        }
+    }
+}
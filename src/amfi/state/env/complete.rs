@@ -5,10 +5,10 @@ use karty::error::{CardSetErrorGen};
 use karty::hand::{CardSet, HandSuitedTrait, HandTrait};
 use amfiteatr_core::env::{EnvironmentStateSequential, EnvironmentStateUniScore};
 use amfiteatr_core::domain::{DomainParameters};
-use crate::contract::{Contract, ContractMechanics, ContractParameters};
+use crate::contract::{Claim, ClaimCheck, Contract, ContractMechanics, ContractParameters};
 use crate::deal::DescriptionDeckDeal;
 use crate::error::{BridgeCoreError, ContractErrorGen};
-use crate::player::side::Side;
+use crate::player::side::{Side, SideMap};
 use crate::player::side::Side::*;
 use crate::amfi::spec::ContractDP;
 use crate::amfi::state::{ContractAction, ContractState, ContractStateUpdate};
@@ -203,6 +203,17 @@ impl EnvironmentStateSequential<ContractDP> for ContractEnvStateComplete{
                             (West, update)])
 
             }
+            ContractAction::Claim(tricks) => {
+                self.contract.check_claimer(side)?;
+                let hands = SideMap::new(self[North], self[East], self[South], self[West]);
+                self.contract.claim(Claim::new(side, tricks), &hands, ClaimCheck::DoubleDummy)?;
+                let update = ContractStateUpdate::new(side, ContractAction::Claim(tricks));
+                Ok([
+                            (North, update),
+                            (East, update),
+                            (South, update),
+                            (West, update)])
+            }
         }
 
 
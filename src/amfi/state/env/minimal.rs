@@ -1,6 +1,6 @@
 use karty::hand::{HandTrait, CardSet};
 use crate::contract::{Contract, ContractMechanics, ContractParameters};
-use crate::error::BridgeCoreError;
+use crate::error::{BridgeCoreError, ContractErrorGen};
 use crate::amfi::state::{ContractAction, ContractState, ContractStateUpdate};
 use log::{debug};
 use amfiteatr_core::env::{EnvironmentStateSequential, EnvironmentStateUniScore};
@@ -150,6 +150,11 @@ impl EnvironmentStateSequential<ContractDP> for ContractEnvStateMin{
                             (West, update)])
 
             }
+            // hands are unknown here, so claim can not be verified
+            ContractAction::Claim(tricks) => {
+                self.contract.check_claimer(side)?;
+                Err(ContractErrorGen::ClaimRejected(side, tricks).into())
+            }
         }
 
 
@@ -1,5 +1,9 @@
 mod minimal;
 mod complete;
+mod bidding;
+mod board;
 
 pub use minimal::*;
-pub use complete::*;
\ No newline at end of file
+pub use complete::*;
+pub use bidding::*;
+pub use board::*;
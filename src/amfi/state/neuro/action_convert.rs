@@ -1,7 +1,5 @@
 use amfiteatr_rl::tch::Tensor;
 use amfiteatr_rl::error::TensorRepresentationError;
-use karty::cards::Card2SymTrait;
-use karty::symbol::CardSymbol;
 use amfiteatr_rl::tensor_data::{ConvertToTensor, ConversionToTensor};
 use crate::amfi::state::ContractAction;
 
@@ -17,13 +15,13 @@ impl ConversionToTensor for ContractActionWayToTensor{
 }
 
 impl ConvertToTensor<ContractActionWayToTensor> for ContractAction{
-    fn try_to_tensor(&self, _way: &ContractActionWayToTensor) -> Result<Tensor, TensorRepresentationError> {
+    fn try_to_tensor(&self, way: &ContractActionWayToTensor) -> Result<Tensor, TensorRepresentationError> {
         match self{
-            ContractAction::ShowHand(_) => {panic!("Not prepared to convert show hand to tensor")}
-            ContractAction::PlaceCard(c) => {
-                let v = vec![c.suit().usize_index() as f32, c.figure().usize_index() as f32];
-                Ok(Tensor::from_slice(&v[..]))
-            }
+            ContractAction::ShowHand(_) => Err(TensorRepresentationError::InfoSetNotFit{
+                info_set: format!("{self:?}"),
+                shape: way.desired_shape().to_vec()
+            }),
+            action => Ok(Tensor::from_slice(&action.code().map(|v| v as f32)))
         }
     }
 }
\ No newline at end of file
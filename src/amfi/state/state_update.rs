@@ -1,7 +1,7 @@
 use std::fmt::{Debug, Display, Formatter};
 //use sztorm::state::StateUpdate;
 use crate::player::side::Side;
-use crate::amfi::state::ContractAction;
+use crate::amfi::state::{BiddingAction, BoardAction, ContractAction};
 
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,6 +41,84 @@ impl Display for ContractStateUpdate{
 }
 
 
+/// Call made by side, published to every agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct BiddingStateUpdate {
+    agent: Side,
+    action: BiddingAction
+}
+
+impl BiddingStateUpdate{
+    pub fn new(side: Side, action: BiddingAction) -> Self{
+        Self{agent: side, action}
+    }
+
+    pub fn side(&self) -> &Side{
+        &self.agent
+    }
+    pub fn action(&self) -> &BiddingAction{
+        &self.action
+    }
+    pub fn into_tuple(self) -> (Side, BiddingAction){
+        (self.agent, self.action)
+    }
+}
+
+impl Display for BiddingStateUpdate{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match f.alternate(){
+            true => write!(f, "agent {:#}; call: {:#}", &self.agent, &self.action),
+            false => write!(f, "agent {}; call: {}", &self.agent, &self.action)
+        }
+    }
+}
+
+/// Action of side in board, published to every agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+pub struct BoardStateUpdate {
+    agent: Side,
+    action: BoardAction
+}
+
+impl BoardStateUpdate{
+    pub fn new(side: Side, action: BoardAction) -> Self{
+        Self{agent: side, action}
+    }
+
+    pub fn side(&self) -> &Side{
+        &self.agent
+    }
+    pub fn action(&self) -> &BoardAction{
+        &self.action
+    }
+    pub fn into_tuple(self) -> (Side, BoardAction){
+        (self.agent, self.action)
+    }
+}
+
+impl Display for BoardStateUpdate{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match f.alternate(){
+            true => write!(f, "agent {:#}; action: {:#}", &self.agent, &self.action),
+            false => write!(f, "agent {}; action: {}", &self.agent, &self.action)
+        }
+    }
+}
+
+impl From<BiddingStateUpdate> for BoardStateUpdate{
+    fn from(update: BiddingStateUpdate) -> Self {
+        Self{agent: update.agent, action: update.action.into()}
+    }
+}
+
+impl From<ContractStateUpdate> for BoardStateUpdate{
+    fn from(update: ContractStateUpdate) -> Self {
+        Self{agent: update.agent, action: update.action.into()}
+    }
+}
+
 /*
 impl StateUpdate for ContractStateUpdate{
 
@@ -50,25 +128,20 @@ impl StateUpdate for ContractStateUpdate{
 
 #[cfg(feature = "torch")]
 mod tensor{
-    use karty::cards::{Card2SymTrait, DECK_SIZE};
+    use karty::cards::DECK_SIZE;
     use karty::symbol::CardSymbol;
     use crate::amfi::state::ContractAction;
     const MIN_ACTION_SIZE:usize = 2;
 
+    /// See [`ContractAction::code`]
     impl From<&ContractAction> for [u8;MIN_ACTION_SIZE]{
         fn from(value: &ContractAction) -> Self {
-            match value{
-                ContractAction::ShowHand(_) => [0,0],
-                ContractAction::PlaceCard(c) => [c.suit().usize_index() as u8 +1, c.figure().usize_index() as u8 + 1]
-            }
+            value.code()
         }
     }
     impl From<&ContractAction> for [f32;MIN_ACTION_SIZE]{
         fn from(value: &ContractAction) -> Self {
-            match value{
-                ContractAction::ShowHand(_) => [0.0,0.0],
-                ContractAction::PlaceCard(c) => [c.suit().usize_index() as f32 +1.0, c.figure().usize_index() as f32 + 1.0]
-            }
+            value.code().map(|v| v as f32)
         }
     }
 
@@ -94,6 +167,7 @@ mod tensor{
                     crd[c.usize_index()] = 1.0;
                     crd[DECK_SIZE] = 1.0;
                 }
+                ContractAction::Claim(_) => {}
             }
 
             crd
@@ -2,18 +2,18 @@ use std::thread;
 use karty::hand::CardSet;
 use karty::suits::Suit::Spades;
 use amfiteatr_core::agent::{AutomaticAgentRewarded, RandomPolicy, RewardedAgent, StatefulAgent, EvaluatedInformationSet};
-use amfiteatr_core::env::RoundRobinUniversalEnvironment;
+use amfiteatr_core::env::{RoundRobinUniversalEnvironment, StatefulEnvironment};
 use crate::bidding::Bid;
 use crate::cards::trump::TrumpGen;
-use crate::contract::{Contract, ContractParametersGen};
-use crate::deal::fair_bridge_deal;
+use crate::contract::{Contract, ContractMechanics, ContractParametersGen};
+use crate::deal::{fair_bridge_deal, Board};
 use crate::player::side::{Side, SideMap};
 use crate::player::side::Side::*;
-use crate::amfi::agent::TracingContractAgent;
-use crate::amfi::comm::ContractEnvSyncComm;
+use crate::amfi::agent::{TracingBiddingAgent, TracingBoardAgent, TracingContractAgent};
+use crate::amfi::comm::{BiddingEnvSyncComm, BoardEnvSyncComm, ContractEnvSyncComm};
 use crate::amfi::env::ContractEnv;
-use crate::amfi::spec::ContractDP;
-use crate::amfi::state::{ContractAgentInfoSetSimple, ContractDummyState, ContractEnvStateMin};
+use crate::amfi::spec::{BiddingDP, BoardDP, ContractDP};
+use crate::amfi::state::{BiddingAgentInfoSet, BiddingEnvState, BoardAgentInfoSet, BoardEnvState, ContractAgentInfoSetSimple, ContractDummyState, ContractEnvStateMin};
 
 mod env_agent;
 
@@ -71,4 +71,402 @@ fn random_agents_sync_comm(){
 
     assert_eq!(agent_east.info_set().current_subjective_score() + agent_north.info_set().current_subjective_score(), 13);
     assert_eq!(agent_east.current_universal_score() + agent_north.current_universal_score(), 13);
-}
\ No newline at end of file
+}
+
+#[test]
+fn random_bidding_agents_sync_comm(){
+    let (comm_env_north, comm_north) = BiddingEnvSyncComm::new_pair();
+    let (comm_env_east, comm_east) = BiddingEnvSyncComm::new_pair();
+    let (comm_env_west, comm_west) = BiddingEnvSyncComm::new_pair();
+    let (comm_env_south, comm_south) = BiddingEnvSyncComm::new_pair();
+
+    let comm_association = SideMap::new(comm_env_north, comm_env_east, comm_env_south, comm_env_west);
+    let card_deal = fair_bridge_deal::<CardSet>();
+    let mut env = ContractEnv::<_, _, BiddingDP>::new(BiddingEnvState::new(card_deal, West), comm_association);
+
+    let random_policy = RandomPolicy::<BiddingDP, BiddingAgentInfoSet>::new();
+    let mut agent_north = TracingBiddingAgent::new(BiddingAgentInfoSet::new(North, card_deal[&North], West), comm_north, random_policy.clone());
+    let mut agent_east = TracingBiddingAgent::new(BiddingAgentInfoSet::new(East, card_deal[&East], West), comm_east, random_policy.clone());
+    let mut agent_south = TracingBiddingAgent::new(BiddingAgentInfoSet::new(South, card_deal[&South], West), comm_south, random_policy.clone());
+    let mut agent_west = TracingBiddingAgent::new(BiddingAgentInfoSet::new(West, card_deal[&West], West), comm_west, random_policy);
+
+    thread::scope(|s|{
+        s.spawn(||{
+            env.run_round_robin_with_rewards().unwrap();
+        });
+        s.spawn(||{
+            agent_north.run_rewarded().unwrap();
+        });
+        s.spawn(||{
+            agent_east.run_rewarded().unwrap();
+        });
+        s.spawn(||{
+            agent_south.run_rewarded().unwrap();
+        });
+        s.spawn(||{
+            agent_west.run_rewarded().unwrap();
+        });
+    });
+
+    assert!(env.state().auction().is_finished());
+    assert_eq!(agent_east.info_set().auction(), env.state().auction());
+    assert_eq!(env.state().auction().calls_entries()[0].player_side(), West);
+}
+
+#[test]
+fn random_board_agents_sync_comm(){
+    let (comm_env_north, comm_north) = BoardEnvSyncComm::new_pair();
+    let (comm_env_east, comm_east) = BoardEnvSyncComm::new_pair();
+    let (comm_env_west, comm_west) = BoardEnvSyncComm::new_pair();
+    let (comm_env_south, comm_south) = BoardEnvSyncComm::new_pair();
+
+    let comm_association = SideMap::new(comm_env_north, comm_env_east, comm_env_south, comm_env_west);
+    let card_deal = fair_bridge_deal::<CardSet>();
+    let board = Board::new(2, card_deal);
+    let card_deal = board.deal();
+    let mut env = ContractEnv::<_, _, BoardDP>::new(BoardEnvState::from_board(&board), comm_association);
+
+    let random_policy = RandomPolicy::<BoardDP, BoardAgentInfoSet>::new();
+    let mut agent_north = TracingBoardAgent::new(BoardAgentInfoSet::new(North, card_deal[&North], board.dealer(), board.vulnerability()), comm_north, random_policy.clone());
+    let mut agent_east = TracingBoardAgent::new(BoardAgentInfoSet::new(East, card_deal[&East], board.dealer(), board.vulnerability()), comm_east, random_policy.clone());
+    let mut agent_south = TracingBoardAgent::new(BoardAgentInfoSet::new(South, card_deal[&South], board.dealer(), board.vulnerability()), comm_south, random_policy.clone());
+    let mut agent_west = TracingBoardAgent::new(BoardAgentInfoSet::new(West, card_deal[&West], board.dealer(), board.vulnerability()), comm_west, random_policy);
+
+    thread::scope(|s|{
+        s.spawn(||{
+            env.run_round_robin_with_rewards().unwrap();
+        });
+        s.spawn(||{
+            agent_north.run_rewarded().unwrap();
+        });
+        s.spawn(||{
+            agent_east.run_rewarded().unwrap();
+        });
+        s.spawn(||{
+            agent_south.run_rewarded().unwrap();
+        });
+        s.spawn(||{
+            agent_west.run_rewarded().unwrap();
+        });
+    });
+
+    assert!(env.state().auction().is_finished());
+    if let Some(contract) = env.state().contract(){
+        assert!(contract.is_completed());
+    }
+    assert_eq!(agent_north.current_universal_score(), -agent_east.current_universal_score());
+    assert_eq!(agent_south.info_set().current_subjective_score(), agent_north.current_universal_score());
+}
+
+#[test]
+fn assuming_info_set_renormalises_distribution(){
+    use karty::cards::{Card2SymTrait, STANDARD_DECK};
+    use karty::hand::HandTrait;
+    use karty::suits::Suit::{Clubs, Diamonds, Hearts};
+    use karty::symbol::CardSymbol;
+    use amfiteatr_core::agent::InformationSet;
+    use crate::amfi::state::{ContractAction, ContractAgentInfoSetAssuming, ContractInfoSet, ContractStateUpdate};
+
+    let mut hands = SideMap::new_symmetric(CardSet::empty());
+    for card in STANDARD_DECK{
+        let side = match (card.suit(), card.figure().usize_index()){
+            (Spades, _) => North,
+            (Diamonds, _) => South,
+            (_, i) if i < 6 => East,
+            (Hearts, 6) => East,
+            _ => West
+        };
+        hands[&side].insert_card(card).unwrap();
+    }
+    let contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 1).unwrap()));
+    let mut info_set = ContractAgentInfoSetAssuming::new_fair(East, hands[&East], contract, None);
+    for card in STANDARD_DECK{
+        assert_eq!(info_set.hint_card_probability_for_player(East, &card), if hands[&East].contains(&card){1.0} else {0.0});
+    }
+    for side in [North, South, West]{
+        assert!((info_set.distribution_assumption()[side].sum_probabilities() - 13.0).abs() < 0.01);
+    }
+
+    let lead = hands[&East].into_iter().find(|c| c.suit() == Clubs).unwrap();
+    let dummy_card = hands[&South].into_iter().next().unwrap();
+    let declarer_card = hands[&North].into_iter().next().unwrap();
+    let west_card = hands[&West].into_iter().find(|c| c.suit() == Clubs).unwrap();
+    info_set.update(ContractStateUpdate::new(East, ContractAction::PlaceCard(lead))).unwrap();
+    info_set.update(ContractStateUpdate::new(South, ContractAction::ShowHand(hands[&South]))).unwrap();
+    info_set.update(ContractStateUpdate::new(South, ContractAction::PlaceCard(dummy_card))).unwrap();
+    info_set.update(ContractStateUpdate::new(West, ContractAction::PlaceCard(west_card))).unwrap();
+    info_set.update(ContractStateUpdate::new(North, ContractAction::PlaceCard(declarer_card))).unwrap();
+
+    for card in [lead, dummy_card, declarer_card, west_card]{
+        for side in [North, East, South, West]{
+            assert_eq!(info_set.hint_card_probability_for_player(side, &card), 0.0);
+        }
+    }
+    for card in hands[&South].into_iter().filter(|c| c != &dummy_card){
+        assert_eq!(info_set.hint_card_probability_for_player(South, &card), 1.0);
+    }
+    // North showed out of clubs, remaining clubs are surely with West
+    for card in hands[&West].into_iter().filter(|c| c.suit() == Clubs && c != &west_card){
+        assert_eq!(info_set.hint_card_probability_for_player(North, &card), 0.0);
+        assert_eq!(info_set.hint_card_probability_for_player(West, &card), 1.0);
+    }
+    for side in [North, South, West]{
+        assert!((info_set.distribution_assumption()[side].sum_probabilities() - 12.0).abs() < 0.01);
+    }
+    let hidden_spade = hands[&North].into_iter().find(|c| c != &declarer_card).unwrap();
+    let north = info_set.hint_card_probability_for_player(North, &hidden_spade);
+    let west = info_set.hint_card_probability_for_player(West, &hidden_spade);
+    assert!(north > 0.0 && west > 0.0);
+    assert!((north + west - 1.0).abs() < 0.001);
+}
+
+#[test]
+fn claim_in_complete_environment(){
+    use amfiteatr_core::agent::InformationSet;
+    use amfiteatr_core::env::{EnvironmentStateSequential, EnvironmentStateUniScore};
+    use crate::amfi::state::{ContractAction, ContractEnvStateComplete, ContractInfoSet, ContractStateUpdate};
+    use crate::deal::deal_at_index;
+    use crate::player::axis::Axis::NorthSouth;
+    // North holds all spades, so every lead of East is ruffed
+    let deal = deal_at_index(0).unwrap();
+    let contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 4).unwrap()));
+    let mut state = ContractEnvStateComplete::new(contract.clone(), deal[&North], deal[&East], deal[&South], deal[&West]);
+    assert!(state.forward(East, ContractAction::Claim(1)).is_err());
+    assert!(!state.is_finished());
+    let updates = state.forward(East, ContractAction::Claim(0)).unwrap();
+    assert!(state.is_finished());
+    assert_eq!(state.state_score_of_player(&North), 13);
+    assert_eq!(state.state_score_of_player(&West), 0);
+
+    let mut info_set = ContractAgentInfoSetSimple::new(North, deal[&North], contract, None);
+    assert!(info_set.is_action_valid(&ContractAction::Claim(13)));
+    info_set.update(updates[2].1).unwrap();
+    assert!(info_set.contract_data().is_completed());
+    assert_eq!(info_set.contract_data().total_tricks_taken_axis(NorthSouth), 13);
+    assert!(info_set.update(ContractStateUpdate::new(North, ContractAction::Claim(13))).is_err());
+}
+
+#[test]
+fn claims_of_dummy_and_out_of_turn_are_rejected(){
+    use amfiteatr_core::env::EnvironmentStateSequential;
+    use crate::amfi::state::{ContractAction, ContractEnvStateComplete};
+    use crate::deal::deal_at_index;
+    use crate::error::{BridgeCoreErrorGen, ContractErrorGen};
+    let deal = deal_at_index(0).unwrap();
+    let contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 4).unwrap()));
+    let lead = deal[&East].into_iter().next().unwrap();
+    let mut complete = ContractEnvStateComplete::new(contract.clone(), deal[&North], deal[&East], deal[&South], deal[&West]);
+    let mut minimal = ContractEnvStateMin::new(contract, Some(deal[&South]));
+
+    assert_eq!(complete.forward(South, ContractAction::Claim(13)).unwrap_err(),
+        BridgeCoreErrorGen::Contract(ContractErrorGen::ClaimByDummy(South)));
+    assert_eq!(minimal.forward(South, ContractAction::Claim(13)).unwrap_err(),
+        BridgeCoreErrorGen::Contract(ContractErrorGen::ClaimByDummy(South)));
+    assert_eq!(complete.forward(North, ContractAction::Claim(13)).unwrap_err(),
+        BridgeCoreErrorGen::Contract(ContractErrorGen::CurrentSidePresume(East, North)));
+    assert_eq!(minimal.forward(West, ContractAction::Claim(0)).unwrap_err(),
+        BridgeCoreErrorGen::Contract(ContractErrorGen::CurrentSidePresume(East, West)));
+
+    complete.forward(East, ContractAction::PlaceCard(lead)).unwrap();
+    minimal.forward(East, ContractAction::PlaceCard(lead)).unwrap();
+    // dummy is to play, so declarer claims
+    assert!(complete.forward(South, ContractAction::Claim(13)).is_err());
+    complete.forward(North, ContractAction::Claim(13)).unwrap();
+    assert!(complete.is_finished());
+    // minimal environment does not know hands to verify claim
+    assert_eq!(minimal.forward(North, ContractAction::Claim(13)).unwrap_err(),
+        BridgeCoreErrorGen::Contract(ContractErrorGen::ClaimRejected(North, 13)));
+    assert!(!minimal.is_finished());
+}
+
+#[test]
+fn assuming_info_set_from_auction(){
+    use karty::card_set;
+    use karty::cards::*;
+    use karty::hand::HandTrait;
+    use crate::amfi::state::{ContractAgentInfoSetAssuming, ContractInfoSet, CreatedContractInfoSet};
+    use crate::bidding::Call;
+    use crate::bidding::consts::{BID_NT1, BID_NT3};
+    use crate::bidding::parser::parse_auction;
+    use crate::bidding::system::{BiddingSystem, CallMeaning, SystemNode};
+    use crate::deal::{BiasedHandDistribution, HandCondition};
+    use crate::hand_eval::high_card_points;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    let system = BiddingSystem::new("Test")
+        .opening(SystemNode::new(Call::NewBid(BID_NT1), CallMeaning::new("Strong notrump")
+                .condition(HandCondition::Hcp(15..=17)).condition(HandCondition::Balanced))
+            .continuation(SystemNode::new(Call::NewBid(BID_NT3), CallMeaning::new("To play")
+                .condition(HandCondition::Hcp(10..=15)))));
+    let auction = parse_auction("1NT P 3NT P P P", North).unwrap();
+    let east = card_set![ACE_SPADES, TWO_SPADES, THREE_SPADES, FOUR_SPADES, TWO_HEARTS, THREE_HEARTS, FOUR_HEARTS,
+        TWO_DIAMONDS, THREE_DIAMONDS, FOUR_DIAMONDS, TWO_CLUBS, THREE_CLUBS, FOUR_CLUBS];
+    let known = SideMap::new(CardSet::empty(), east, CardSet::empty(), CardSet::empty());
+    let distribution = BiasedHandDistribution::from_auction(&system, &auction, &known, 300, &mut StdRng::seed_from_u64(24)).unwrap();
+    let contract = Contract::new(auction.current_contract().unwrap().clone());
+
+    let info_set = ContractAgentInfoSetAssuming::create_new(East, east, contract.clone(), None, distribution.clone());
+    let expected_hcp = |side: Side| -> f32 {
+        STANDARD_DECK.iter().map(|c| info_set.hint_card_probability_for_player(side, c) * high_card_points(&card_set![*c]) as f32).sum()
+    };
+    assert!((15.0..=17.0).contains(&expected_hcp(North)));
+    assert!((10.0..=15.0).contains(&expected_hcp(South)));
+    // West gets what is left of 36 points
+    assert!((36.0 - expected_hcp(North) - expected_hcp(South) - expected_hcp(West)).abs() < 0.01);
+    for card in east{
+        assert_eq!(info_set.hint_card_probability_for_player(East, &card), 1.0);
+    }
+    for side in [North, South, West]{
+        assert!((info_set.distribution_assumption()[side].sum_probabilities() - 13.0).abs() < 0.01);
+    }
+    let direct = ContractAgentInfoSetAssuming::new(East, east, contract, None, distribution);
+    assert_eq!(direct.hint_card_probability_for_player(North, &KING_SPADES), info_set.hint_card_probability_for_player(North, &KING_SPADES));
+}
+
+#[test]
+fn rule_based_policy_follows_rules(){
+    use amfiteatr_core::agent::Policy;
+    use karty::card_set;
+    use karty::cards::*;
+    use karty::suits::Suit::Hearts;
+    use crate::amfi::{PlayRule, PlayRules, RuleBasedPolicy};
+    use crate::amfi::state::ContractAction::PlaceCard;
+    let policy = RuleBasedPolicy::<ContractAgentInfoSetSimple>::default();
+    let without = |rule| RuleBasedPolicy::<ContractAgentInfoSetSimple>::new(PlayRules::all().with(rule, false));
+    let no_trump = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+    let hearts = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Hearts), 4).unwrap()));
+
+    // fourth best without sequence
+    let west = card_set![ACE_HEARTS, NINE_HEARTS, SEVEN_HEARTS, FIVE_HEARTS, TWO_HEARTS, KING_SPADES, FOUR_SPADES, THREE_SPADES,
+        JACK_DIAMONDS, EIGHT_DIAMONDS, TWO_DIAMONDS, SIX_CLUBS, TWO_CLUBS];
+    let info_set = ContractAgentInfoSetSimple::new(West, west, no_trump.clone(), None);
+    assert_eq!(policy.select_action(&info_set), Some(PlaceCard(FIVE_HEARTS)));
+    assert_eq!(without(PlayRule::FourthBestLead).select_action(&info_set), Some(PlaceCard(TWO_HEARTS)));
+
+    // declarer plays from dummy in second seat
+    let north = card_set![KING_SPADES, SEVEN_SPADES, TWO_SPADES, ACE_HEARTS];
+    let south = card_set![ACE_SPADES, THREE_HEARTS, FOUR_HEARTS, FIVE_HEARTS];
+    let mut contract = no_trump.clone();
+    contract.insert_card(West, FOUR_SPADES).unwrap();
+    let info_set = ContractAgentInfoSetSimple::new(South, south, contract, Some(north));
+    assert_eq!(policy.select_action(&info_set), Some(PlaceCard(TWO_SPADES)));
+    assert_eq!(without(PlayRule::SecondHandLow).select_action(&info_set), Some(PlaceCard(SEVEN_SPADES)));
+    let mut contract = no_trump.clone();
+    contract.insert_card(West, QUEEN_SPADES).unwrap();
+    let info_set = ContractAgentInfoSetSimple::new(South, south, contract, Some(north));
+    assert_eq!(policy.select_action(&info_set), Some(PlaceCard(KING_SPADES)));
+    assert_eq!(without(PlayRule::CoverHonour).select_action(&info_set), Some(PlaceCard(TWO_SPADES)));
+
+    // third hand high
+    let mut contract = no_trump;
+    contract.insert_card(West, TWO_HEARTS).unwrap();
+    contract.insert_card(North, THREE_HEARTS).unwrap();
+    let east = card_set![KING_HEARTS, EIGHT_HEARTS, FOUR_HEARTS, TWO_CLUBS];
+    let info_set = ContractAgentInfoSetSimple::new(East, east, contract, None);
+    assert_eq!(policy.select_action(&info_set), Some(PlaceCard(KING_HEARTS)));
+    assert_eq!(without(PlayRule::ThirdHandHigh).select_action(&info_set), Some(PlaceCard(FOUR_HEARTS)));
+
+    // ruff only when partner does not win the trick
+    let east = card_set![NINE_HEARTS, FIVE_HEARTS, SEVEN_DIAMONDS, THREE_DIAMONDS];
+    let mut contract = hearts.clone();
+    contract.insert_card(West, TWO_CLUBS).unwrap();
+    contract.insert_card(North, KING_CLUBS).unwrap();
+    let info_set = ContractAgentInfoSetSimple::new(East, east, contract, None);
+    assert_eq!(policy.select_action(&info_set), Some(PlaceCard(FIVE_HEARTS)));
+    assert_eq!(without(PlayRule::RuffWhenProfitable).select_action(&info_set), Some(PlaceCard(THREE_DIAMONDS)));
+    let mut contract = hearts.clone();
+    contract.insert_card(West, ACE_CLUBS).unwrap();
+    contract.insert_card(North, THREE_CLUBS).unwrap();
+    let info_set = ContractAgentInfoSetSimple::new(East, east, contract, None);
+    assert_eq!(policy.select_action(&info_set), Some(PlaceCard(THREE_DIAMONDS)));
+
+    // declarer draws trumps
+    let mut contract = hearts;
+    for (side, card) in [(West, TWO_CLUBS), (North, THREE_CLUBS), (East, FOUR_CLUBS), (South, ACE_CLUBS)]{
+        contract.insert_card(side, card).unwrap();
+    }
+    let south = card_set![ACE_HEARTS, SIX_HEARTS, KING_SPADES, QUEEN_SPADES, JACK_SPADES];
+    let north = card_set![TWO_HEARTS, FIVE_SPADES, FIVE_DIAMONDS, SIX_DIAMONDS, SEVEN_DIAMONDS];
+    let info_set = ContractAgentInfoSetSimple::new(South, south, contract, Some(north));
+    assert_eq!(policy.select_action(&info_set), Some(PlaceCard(ACE_HEARTS)));
+    assert_eq!(without(PlayRule::DrawTrumps).select_action(&info_set), Some(PlaceCard(KING_SPADES)));
+}
+
+#[test]
+fn rule_based_agents_play_contract(){
+    use crate::amfi::RuleBasedPolicy;
+    use crate::amfi::state::{ContractAgentInfoSetAssuming, ContractState};
+    use crate::deal::fair_bridge_deal_with_rng;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    let contract = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 4).unwrap());
+    let (comm_env_north, comm_north) = ContractEnvSyncComm::new_pair();
+    let (comm_env_east, comm_east) = ContractEnvSyncComm::new_pair();
+    let (comm_env_west, comm_west) = ContractEnvSyncComm::new_pair();
+    let (comm_env_south, comm_south) = ContractEnvSyncComm::new_pair();
+    let comm_association = SideMap::new(comm_env_north, comm_env_east, comm_env_south, comm_env_west);
+    let initial_contract = Contract::new(contract);
+    let mut env = ContractEnv::new(ContractEnvStateMin::new(initial_contract.clone(), None), comm_association);
+
+    let (hand_north, hand_east, hand_south, hand_west) = fair_bridge_deal_with_rng::<CardSet, _>(&mut StdRng::seed_from_u64(25)).destruct();
+    let mut agent_north = TracingContractAgent::new(ContractAgentInfoSetSimple::new(North, hand_north, initial_contract.clone(), None),
+        comm_north, RuleBasedPolicy::<ContractAgentInfoSetSimple>::default());
+    let mut agent_east = TracingContractAgent::new(ContractAgentInfoSetAssuming::new_fair(East, hand_east, initial_contract.clone(), None),
+        comm_east, RuleBasedPolicy::<ContractAgentInfoSetAssuming>::default());
+    let mut agent_south = TracingContractAgent::new(ContractDummyState::new(South, hand_south, initial_contract.clone()),
+        comm_south, RandomPolicy::<ContractDP, ContractDummyState>::new());
+    let mut agent_west = TracingContractAgent::new(ContractAgentInfoSetSimple::new(West, hand_west, initial_contract, None),
+        comm_west, RuleBasedPolicy::<ContractAgentInfoSetSimple>::default());
+
+    thread::scope(|s|{
+        s.spawn(|| env.run_round_robin_with_rewards().unwrap());
+        s.spawn(|| agent_north.run_rewarded().unwrap());
+        s.spawn(|| agent_east.run_rewarded().unwrap());
+        s.spawn(|| agent_south.run_rewarded().unwrap());
+        s.spawn(|| agent_west.run_rewarded().unwrap());
+    });
+    assert!(env.state().contract_data().is_completed());
+    assert_eq!(agent_north.info_set().current_subjective_score() + agent_east.info_set().current_subjective_score(), 13);
+}
+
+#[test]
+fn assuming_info_set_from_constrained_deal(){
+    use karty::cards::{Card2SymTrait, STANDARD_DECK};
+    use karty::suits::Suit::Clubs;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::amfi::state::{ContractAgentInfoSetAssuming, ContractInfoSet};
+    use crate::deal::{DealDistribution, DealFilter, DescriptionDeckDeal};
+    let filter: DealFilter = "North 7+ clubs".parse().unwrap();
+    let probabilities = DealDistribution::constrained(filter.generator()).unwrap();
+    let mut rng = StdRng::seed_from_u64(14);
+    let cards = probabilities.sample(&mut rng);
+    let description = DescriptionDeckDeal{probabilities, cards};
+    let contract = ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 3).unwrap());
+    let info_set = ContractAgentInfoSetAssuming::from_description(East, &contract, &description, &mut rng);
+    // long clubs of North are taken into account
+    let expected_clubs = |side| STANDARD_DECK.iter().filter(|c| c.suit() == Clubs)
+        .map(|c| info_set.hint_card_probability_for_player(side, c)).sum::<f32>();
+    assert!(expected_clubs(North) >= 6.0);
+    assert!(expected_clubs(North) > 2.0 * expected_clubs(West));
+}
+
+#[test]
+fn contract_action_codes_round_trip(){
+    use karty::cards::{Card, DECK_SIZE};
+    use karty::hand::HandTrait;
+    use karty::symbol::CardSymbol;
+    use crate::amfi::state::ContractAction;
+    let actions = Card::iterator().map(ContractAction::PlaceCard)
+        .chain((0..=13).map(ContractAction::Claim));
+    for action in actions{
+        assert_eq!(ContractAction::from_index(action.index().unwrap()), Some(action));
+        assert_eq!(ContractAction::from_code(action.code()), Some(action));
+    }
+    let show_hand = ContractAction::ShowHand(CardSet::empty());
+    assert_eq!(show_hand.index(), None);
+    assert_ne!(ContractAction::Claim(0).code(), show_hand.code());
+    assert_eq!(ContractAction::from_code(show_hand.code()), None);
+    assert_eq!(ContractAction::from_index(DECK_SIZE + 14), None);
+}
@@ -1,12 +1,15 @@
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use karty::suits::SuitTrait;
-use crate::error::BiddingErrorGen::{BidTooLow, DoubleAfterDouble, DoubleAfterReDouble, DoubleOnSameAxis, DoubleOnVoidCall, ReDoubleAfterReDouble, ReDoubleOnSameAxis, ReDoubleOnVoidCall, ReDoubleWithoutDouble, ViolatedOrder};
+use crate::error::BiddingErrorGen::{AuctionFinished, BidTooLow, DoubleAfterDouble, DoubleAfterReDouble, DoubleOnSameAxis, DoubleOnVoidCall, ReDoubleAfterReDouble, ReDoubleOnSameAxis, ReDoubleOnVoidCall, ReDoubleWithoutDouble, ViolatedOrder};
 use crate::bidding::call::{Call, CallEntry, Doubling};
 
 use crate::bidding::bid::{Bid};
 use crate::bidding::declaration_storage::DeclarationStorage;
+use crate::cards::trump::TrumpGen;
 use crate::contract::ContractParametersGen;
 use crate::error::{BiddingErrorGen, Mismatch};
+use crate::meta::{MAX_BID_NUMBER, MIN_BID_NUMBER};
 
 use crate::player::side::Side;
 
@@ -26,16 +29,48 @@ pub struct AuctionStack<S: SuitTrait, DS: DeclarationStorage<S>>{
     calls_entries: Vec<CallEntry<S>>,
     current_contract: Option<ContractParametersGen<S>>,
     declaration_storage: DS,
+    dealer: Option<Side>,
 
 }
 
 impl<S: SuitTrait, DS: DeclarationStorage<S>> AuctionStack<S, DS>{
     pub fn new() -> Self{
         Self{ calls_entries: Vec::new(), current_contract: None,
-            declaration_storage: DS::default()}
+            declaration_storage: DS::default(), dealer: None}
 
     }
 
+    /// Creates auction in which every call (including passes before first bid) must be made in order starting from dealer.
+    /// ```
+    /// use brydz_core::bidding::{AuctionStack, Call, GeneralDeclarationStorage};
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::suits::Suit;
+    /// let mut auction = AuctionStack::<Suit, GeneralDeclarationStorage<Suit>>::new_with_dealer(West);
+    /// assert_eq!(auction.current_side(), Some(West));
+    /// assert!(auction.add_contract_bid(North, Call::Pass).is_err());
+    /// auction.add_contract_bid(West, Call::Pass).unwrap();
+    /// assert_eq!(auction.current_side(), Some(North));
+    /// ```
+    pub fn new_with_dealer(dealer: Side) -> Self{
+        Self{dealer: Some(dealer), ..Self::new()}
+    }
+
+    pub fn dealer(&self) -> Option<Side>{
+        self.dealer
+    }
+
+    /// Side expected to make next call, `None` when auction is finished
+    /// or when it has no dealer and no call was made yet.
+    pub fn current_side(&self) -> Option<Side>{
+        if self.is_finished(){
+            return None
+        }
+        match (self.calls_entries.last(), self.dealer){
+            (Some(last), _) => Some(last.player_side().next()),
+            (None, dealer) => dealer
+        }
+    }
+
     pub fn current_contract(&self) -> Option<&ContractParametersGen<S>>{
         match &self.current_contract{
             Some(x) => Some(x),
@@ -44,6 +79,97 @@ impl<S: SuitTrait, DS: DeclarationStorage<S>> AuctionStack<S, DS>{
 
     }
 
+    /// Calls made so far, in order.
+    pub fn calls_entries(&self) -> &[CallEntry<S>]{
+        &self.calls_entries
+    }
+
+    /// Iterator over calls made so far, from the first one.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &CallEntry<S>> + ExactSizeIterator{
+        self.calls_entries.iter()
+    }
+
+    /// Iterator over calls made by side, in order.
+    pub fn calls_of_side(&self, side: Side) -> impl DoubleEndedIterator<Item = &CallEntry<S>>{
+        self.calls_entries.iter().filter(move |entry| entry.player_side() == side)
+    }
+
+    /// Iterator over bids (calls other than pass, double and redouble), in order.
+    pub fn bids_history(&self) -> impl DoubleEndedIterator<Item = &CallEntry<S>>{
+        self.calls_entries.iter().filter(|entry| matches!(entry.call(), Call::NewBid(_)))
+    }
+
+    pub fn last_call(&self) -> Option<&CallEntry<S>>{
+        self.calls_entries.last()
+    }
+
+    /// Removes last call and restores state of auction from before it.
+    /// Declarations are rebuilt by replaying remaining calls.
+    /// ```
+    /// use brydz_core::bidding::{AuctionStack, Call, GeneralDeclarationStorage};
+    /// use brydz_core::bidding::consts::{BID_C1, BID_H1, BID_H2};
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::suits::Suit;
+    /// let mut auction = AuctionStack::<Suit, GeneralDeclarationStorage<Suit>>::new_with_dealer(North);
+    /// auction.add_contract_bid(North, Call::NewBid(BID_C1)).unwrap();
+    /// auction.add_contract_bid(East, Call::Pass).unwrap();
+    /// auction.add_contract_bid(South, Call::NewBid(BID_H1)).unwrap();
+    /// assert_eq!(auction.undo_last_call().unwrap().player_side(), South);
+    /// assert_eq!(auction.current_bid(), Some(&BID_C1));
+    /// assert_eq!(auction.current_side(), Some(South));
+    /// // North becomes declarer in hearts when South does not bid them first
+    /// auction.add_contract_bid(South, Call::Pass).unwrap();
+    /// auction.add_contract_bid(West, Call::Double).unwrap();
+    /// auction.add_contract_bid(North, Call::NewBid(BID_H2)).unwrap();
+    /// assert_eq!(auction.current_contract().unwrap().declarer(), North);
+    /// ```
+    pub fn undo_last_call(&mut self) -> Option<CallEntry<S>>{
+        let mut entries = std::mem::take(&mut self.calls_entries);
+        let last = entries.pop();
+        *self = Self{dealer: self.dealer, ..Self::new()};
+        for entry in entries{
+            self.add_contract_bid(entry.player_side(), entry.call().to_owned())
+                .expect("Calls already accepted in auction must be legal when replayed");
+        }
+        last
+    }
+
+    /// Iterator over calls that side to act may make: pass, double and redouble when allowed and
+    /// bids higher than current one in ascending order. Empty when auction is finished.
+    /// ```
+    /// use brydz_core::bidding::{AuctionStack, Call, GeneralDeclarationStorage};
+    /// use brydz_core::bidding::consts::{BID_NT7, BID_S7};
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::suits::Suit;
+    /// let mut auction = AuctionStack::<Suit, GeneralDeclarationStorage<Suit>>::new_with_dealer(West);
+    /// assert_eq!(auction.legal_calls().count(), 36);
+    /// auction.add_contract_bid(West, Call::NewBid(BID_S7)).unwrap();
+    /// assert_eq!(auction.legal_calls().collect::<Vec<_>>(), vec![Call::Pass, Call::Double, Call::NewBid(BID_NT7)]);
+    /// auction.add_contract_bid(North, Call::Double).unwrap();
+    /// assert_eq!(auction.legal_calls().collect::<Vec<_>>(), vec![Call::Pass, Call::Redouble, Call::NewBid(BID_NT7)]);
+    /// ```
+    pub fn legal_calls(&self) -> impl Iterator<Item = Call<S>> + '_{
+        let side = self.current_side();
+        let contract = self.current_contract.as_ref().filter(|_| !self.is_finished());
+        let opponents_call = |contract: &ContractParametersGen<S>|
+            side.is_some_and(|s| s.axis() != contract.declarer().axis());
+        let double = contract.filter(|c| c.doubling() == Doubling::None && opponents_call(c))
+            .map(|_| Call::Double);
+        let redouble = contract.filter(|c| c.doubling() == Doubling::Double && !opponents_call(c))
+            .map(|_| Call::Redouble);
+        let mut bids: Vec<Bid<S>> = (MIN_BID_NUMBER..=MAX_BID_NUMBER).flat_map(|number|{
+            (0..S::SYMBOL_SPACE).filter_map(|index| S::from_usize_index(index).ok()).map(TrumpGen::Colored)
+                .chain(std::iter::once(TrumpGen::NoTrump))
+                .filter_map(move |trump| Bid::init(trump, number).ok())
+        }).collect();
+        bids.sort();
+        let pass = (!self.is_finished()).then_some(Call::Pass);
+        pass.into_iter().chain(double).chain(redouble)
+            .chain(bids.into_iter()
+                .filter(move |bid| !self.is_finished() && self.current_bid().is_none_or(|current| bid > current))
+                .map(Call::NewBid))
+    }
+
     pub fn last_passes(&self) -> u8{
         let mut counter = 0u8;
         for it in self.calls_entries.iter().rev(){
@@ -61,7 +187,36 @@ impl<S: SuitTrait, DS: DeclarationStorage<S>> AuctionStack<S, DS>{
         self.current_contract.as_ref().map(|c| c.bid())
     }
 
+    /// Auction is finished after three passes following a bid or after four passes when nothing was bid.
+    /// ```
+    /// use brydz_core::bidding::{AuctionStack, Call, GeneralDeclarationStorage};
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::suits::Suit;
+    /// let mut auction = AuctionStack::<Suit, GeneralDeclarationStorage<Suit>>::new();
+    /// for side in [North, East, South]{
+    ///     auction.add_contract_bid(side, Call::Pass).unwrap();
+    /// }
+    /// assert!(!auction.is_finished());
+    /// auction.add_contract_bid(West, Call::Pass).unwrap();
+    /// assert!(auction.is_finished());
+    /// assert!(auction.add_contract_bid(North, Call::Pass).is_err());
+    /// ```
+    pub fn is_finished(&self) -> bool{
+        match self.current_contract{
+            Some(_) => self.last_passes() >= 3,
+            None => self.last_passes() >= 4
+        }
+    }
+
     pub fn add_contract_bid(&mut self, player_side: Side, call: Call<S>) -> Result<AuctionStatus, BiddingErrorGen<S>>{
+        if self.is_finished(){
+            return Err(AuctionFinished)
+        }
+        if let Some(expected) = self.current_side(){
+            if expected != player_side{
+                return Err(ViolatedOrder(Mismatch{expected, found: player_side}))
+            }
+        }
         match self.current_contract{
             None => {
                 // First bid, must not be double or redouble
@@ -69,7 +224,10 @@ impl<S: SuitTrait, DS: DeclarationStorage<S>> AuctionStack<S, DS>{
                     Call::Pass=> {
                         self.calls_entries.push(CallEntry::new(player_side, call));
                         //self.last_player = Some(player_side);
-                        Ok(AuctionStatus::Running(player_side.next()))
+                        match self.is_finished(){
+                            true => Ok(AuctionStatus::Finished),
+                            false => Ok(AuctionStatus::Running(player_side.next()))
+                        }
                     },
                     Call::NewBid(ref bid) => {
                         self.calls_entries.push(CallEntry::new(player_side, call.to_owned()));
@@ -168,6 +326,34 @@ impl<S: SuitTrait, DS: DeclarationStorage<S>> AuctionStack<S, DS>{
         }
     }
 }
+/// Auction is written as table with columns of West, North, East and South. First call is placed
+/// in column of dealer (or of side that made it when auction has no dealer).
+/// ```
+/// use brydz_core::bidding::parser::parse_auction;
+/// use brydz_core::player::side::Side::North;
+/// let auction = parse_auction("1C P 1H X XX 2NT P P P", North).unwrap();
+/// assert_eq!(auction.to_string(), "\
+/// West   North  East   South
+///        1♣     Pass   1♥
+/// X      XX     2NT    Pass
+/// Pass   Pass");
+/// ```
+impl<S: SuitTrait + Display, DS: DeclarationStorage<S>> Display for AuctionStack<S, DS>{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const COLUMNS: [Side; 4] = [Side::West, Side::North, Side::East, Side::South];
+        let first = self.calls_entries.first().map(|entry| entry.player_side()).or(self.dealer);
+        let skipped = first.map_or(0, |side| COLUMNS.iter().position(|s| *s == side).unwrap_or(0));
+        let cells: Vec<String> = COLUMNS.iter().map(|side| side.to_string())
+            .chain(std::iter::repeat_n(String::new(), skipped))
+            .chain(self.calls_entries.iter().map(|entry| format!("{:#}", entry.call())))
+            .collect();
+        let rows: Vec<String> = cells.chunks(COLUMNS.len()).map(|row|{
+            row.iter().map(|cell| format!("{cell:<7}")).collect::<String>().trim_end().to_owned()
+        }).collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
 impl<S: SuitTrait, DS: DeclarationStorage<S>> Default for AuctionStack<S, DS> {
      fn default() -> Self {
          Self::new()
@@ -184,7 +370,7 @@ mod tests{
     use crate::bidding::auction_field::{AuctionStack};
     use crate::bidding::Bid;
     use crate::player::side::Side::{East, North, South, West};
-    use crate::bidding::call::{Call, Doubling};
+    use crate::bidding::call::{Call, CallEntry, Doubling};
     use crate::bidding::bid::consts::{ BID_C1, BID_C2, BID_C3, BID_S2};
     use crate::bidding::declaration_storage::GeneralDeclarationStorage;
     use crate::contract::ContractParametersGen;
@@ -342,9 +528,34 @@ mod tests{
 
     }
 
-
-
-
-
-
-}
\ No newline at end of file
+    #[test]
+    fn legal_calls_and_undo_on_random_auctions(){
+        use rand::rngs::StdRng;
+        use rand::seq::IteratorRandom;
+        use rand::SeedableRng;
+        use crate::cards::trump::TRUMPS;
+        let mut rng = StdRng::seed_from_u64(21);
+        let all_calls: Vec<Call<Suit>> = [Call::Pass, Call::Double, Call::Redouble].into_iter()
+            .chain((1..=7).flat_map(|n| TRUMPS.into_iter().map(move |t| Call::NewBid(Bid::init(t, n).unwrap()))))
+            .collect();
+        for _ in 0..20{
+            let mut auction_stack = AuctionStack::<Suit, GeneralDeclarationStorage<Suit>>::new_with_dealer(South);
+            while let Some(side) = auction_stack.current_side(){
+                let legal: Vec<_> = auction_stack.legal_calls().collect();
+                for call in all_calls.iter(){
+                    assert_eq!(legal.contains(call), auction_stack.clone().add_contract_bid(side, *call).is_ok());
+                }
+                let before = auction_stack.clone();
+                let call = *legal.iter().choose(&mut rng).unwrap();
+                auction_stack.add_contract_bid(side, call).unwrap();
+                let mut undone = auction_stack.clone();
+                assert_eq!(undone.undo_last_call(), Some(CallEntry::new(side, call)));
+                assert_eq!(undone, before);
+            }
+            assert_eq!(auction_stack.legal_calls().count(), 0);
+            assert_eq!(auction_stack.history().count(), auction_stack.calls_of_side(South).count()
+                + auction_stack.calls_of_side(West).count() + auction_stack.calls_of_side(North).count()
+                + auction_stack.calls_of_side(East).count());
+        }
+    }
+}
@@ -82,12 +82,23 @@ impl<S: SuitTrait> PartialOrd for Bid<S> {
     }
 }
 
+/// Bid is written as number and trump, alternate form uses suit symbols.
+/// ```
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use karty::suits::Suit::{self, Spades};
+/// assert_eq!(format!("{}", Bid::init(TrumpGen::Colored(Spades), 3).unwrap()), "3 Spades");
+/// assert_eq!(format!("{:#}", Bid::init(TrumpGen::Colored(Spades), 3).unwrap()), "3♠");
+/// assert_eq!(format!("{:#}", Bid::init(TrumpGen::<Suit>::NoTrump, 1).unwrap()), "1NT");
+/// ```
 impl<S: SuitTrait + Display> Display for Bid<S>{
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if f.alternate(){
-
+        match (f.alternate(), self.trump()){
+            (true, TrumpGen::Colored(s)) => write!(f, "{}{s:#}", self.number()),
+            (false, TrumpGen::Colored(s)) => write!(f, "{} {s}", self.number()),
+            (true, TrumpGen::NoTrump) => write!(f, "{}NT", self.number()),
+            (false, TrumpGen::NoTrump) => write!(f, "{} NoTrump", self.number()),
         }
-        todo!();
     }
 }
 
@@ -248,7 +259,7 @@ pub mod consts {
     pub const BID_NT7: Bid<karty::suits::Suit> = Bid { trump: TrumpGen::NoTrump, number: 7 };
 }
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "serde_dedicate", feature = "serde_derive")))]
 mod tests{
     use karty::suits::Suit;
     use karty::suits::Suit::Hearts;
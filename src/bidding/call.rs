@@ -28,22 +28,31 @@ pub enum Call<S: SuitTrait> {
 
 pub type CallStd = Call<Suit>;
 
+/// Call is written as bid or name of call, alternate form is short.
+/// ```
+/// use brydz_core::bidding::{Bid, Call};
+/// use brydz_core::cards::trump::TrumpGen;
+/// use karty::suits::Suit;
+/// assert_eq!(format!("{:#}", Call::NewBid(Bid::init(TrumpGen::<Suit>::NoTrump, 2).unwrap())), "2NT");
+/// assert_eq!(format!("{}", Call::<Suit>::Double), "Double");
+/// assert_eq!(format!("{:#}", Call::<Suit>::Redouble), "XX");
+/// ```
 impl<S: SuitTrait + Display> Display for Call<S>{
-    fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!();
-        /*
-        if f.alternate(){
-            match self{
-                Call::Bid(bid) => write!(f, "Call::Bid{{ {:#} }}", bid),
-
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match f.alternate(){
+            true => match self{
+                Call::NewBid(bid) => write!(f, "{bid:#}"),
+                Call::Double => write!(f, "X"),
+                Call::Redouble => write!(f, "XX"),
+                Call::Pass => write!(f, "Pass")
+            },
+            false => match self{
+                Call::NewBid(bid) => write!(f, "{bid}"),
+                Call::Double => write!(f, "Double"),
+                Call::Redouble => write!(f, "Redouble"),
+                Call::Pass => write!(f, "Pass")
             }
-
         }
-        else{
-            match self{
-                Call::Bid(bid) => write!(f, "Call::Bid{{ {} }}", bid),
-            }
-        }*/
     }
 }
 
@@ -10,6 +10,7 @@ pub trait DeclarationStorage<S: SuitTrait>: Default{
     fn set_declarer(&mut self, side: Side, trump: TrumpGen<S>);
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GeneralDeclarationStorage<S: SuitTrait + Hash>{
     east_west_declarations: HashMap<TrumpGen<S>, Side>,
     north_south_declarations: HashMap<TrumpGen<S>, Side>,
@@ -5,6 +5,7 @@ pub use call::*;
 mod auction_field;
 pub use auction_field::*;
 pub mod parser;
+pub mod system;
 mod bid;
 pub use bid::*;
 
@@ -1,10 +1,14 @@
 use nom::branch::alt;
 use nom::bytes::complete::{tag_no_case};
 use nom::character::complete::{digit1, space0};
+use nom::combinator::map;
 use nom::IResult;
 use nom::sequence::{delimited, separated_pair};
 use crate::bidding::bid::Bid;
+use crate::bidding::{AuctionStack, Call, GeneralDeclarationStorage};
 use crate::cards::trump::TrumpGen;
+use crate::error::{BiddingErrorGen, BridgeCoreError, FormatError};
+use crate::player::side::Side;
 use nom::error::ErrorKind;
 use karty::suits::parse::parse_suit;
 use karty::suits::Suit;
@@ -84,3 +88,70 @@ pub fn parse_bid(s: &str) -> IResult<&str, Bid<Suit>>{
     }
         //.map(|(i, (digs, trump))| )
 }
+
+/// Parses call: pass, double, redouble or bid
+/// ```
+/// use brydz_core::bidding::parser::parse_call;
+/// use brydz_core::bidding::{Bid, Call};
+/// use brydz_core::cards::trump::TrumpGen;
+/// use karty::suits::Suit::Hearts;
+/// assert_eq!(parse_call("pass"), Ok(("", Call::Pass)));
+/// assert_eq!(parse_call("P 1H"), Ok((" 1H", Call::Pass)));
+/// assert_eq!(parse_call("XX"), Ok(("", Call::Redouble)));
+/// assert_eq!(parse_call("x"), Ok(("", Call::Double)));
+/// assert_eq!(parse_call("rdbl"), Ok(("", Call::Redouble)));
+/// assert_eq!(parse_call("2h"), Ok(("", Call::NewBid(Bid::init(TrumpGen::Colored(Hearts), 2).unwrap()))));
+/// ```
+pub fn parse_call(s: &str) -> IResult<&str, Call<Suit>>{
+    alt((
+        map(alt((tag_no_case("pass"), tag_no_case("p"))), |_| Call::Pass),
+        map(alt((tag_no_case("xx"), tag_no_case("rdbl"), tag_no_case("redouble"))), |_| Call::Redouble),
+        map(alt((tag_no_case("x"), tag_no_case("dbl"), tag_no_case("double"))), |_| Call::Double),
+        map(parse_bid, Call::NewBid)
+    ))(s)
+}
+
+/// Parses whitespace separated calls and replays them in auction starting from dealer.
+/// Calls are read with [`parse_call`] (so suit symbols and `dbl`, `rdbl` are accepted),
+/// `AP` stands for passes closing auction. Error is reported with index of token that caused it.
+/// ```
+/// use brydz_core::bidding::parser::parse_auction;
+/// use brydz_core::bidding::{Bid, Doubling};
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::error::{BiddingErrorGen, BridgeCoreErrorGen, FormatError, Mismatch};
+/// use brydz_core::player::side::Side::*;
+/// use karty::suits::Suit::{Hearts, Spades};
+/// let auction = parse_auction("1C P 1H X XX 2NT P P P", North).unwrap();
+/// assert!(auction.is_finished());
+/// let contract = auction.current_contract().unwrap();
+/// assert_eq!(contract.declarer(), East);
+/// assert_eq!(contract.bid(), &Bid::init(TrumpGen::NoTrump, 2).unwrap());
+/// assert_eq!(contract.doubling(), Doubling::None);
+/// let auction = parse_auction("pass 1♠ dbl rdbl AP", West).unwrap();
+/// assert!(auction.is_finished());
+/// assert_eq!(auction.current_contract().unwrap().doubling(), Doubling::Redouble);
+/// assert_eq!(parse_auction("1C 1X", East), Err(BridgeCoreErrorGen::from(FormatError::DeserializeError).at_token(1)));
+/// assert_eq!(parse_auction("1S 1H", East),
+///     Err(BridgeCoreErrorGen::Bidding(BiddingErrorGen::BidTooLow(Mismatch{
+///         expected: Bid::init(TrumpGen::Colored(Spades), 1).unwrap(),
+///         found: Bid::init(TrumpGen::Colored(Hearts), 1).unwrap()})).at_token(1)));
+/// ```
+pub fn parse_auction(s: &str, dealer: Side) -> Result<AuctionStack<Suit, GeneralDeclarationStorage<Suit>>, BridgeCoreError>{
+    let mut auction = AuctionStack::new_with_dealer(dealer);
+    for (index, token) in s.split_whitespace().enumerate(){
+        if token.eq_ignore_ascii_case("AP"){
+            while let Some(side) = auction.current_side(){
+                auction.add_contract_bid(side, Call::Pass).map_err(|e| BridgeCoreError::from(e).at_token(index))?;
+            }
+            continue;
+        }
+        let call = match parse_call(token){
+            Ok(("", call)) => call,
+            _ => return Err(BridgeCoreError::from(FormatError::DeserializeError).at_token(index))
+        };
+        let side = auction.current_side()
+            .ok_or_else(|| BridgeCoreError::from(BiddingErrorGen::AuctionFinished).at_token(index))?;
+        auction.add_contract_bid(side, call).map_err(|e| BridgeCoreError::from(e).at_token(index))?;
+    }
+    Ok(auction)
+}
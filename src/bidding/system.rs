@@ -0,0 +1,344 @@
+//! Bidding system described as data: tree of calls of partnership with their meanings.
+//!
+//! System covers uncontested auctions. Its roots are openings (made after any number of passes),
+//! every node lists continuations of the opening partnership after opponents passed.
+//! Meaning of call is given as conditions on hand of caller (in notation of [`DealFilter`](crate::deal::DealFilter))
+//! and forcing status. With feature `serde_ron` system can be read from RON, e.g. `data/sayc.ron`:
+//! ```text
+//! (
+//!     name: "Example",
+//!     openings: [
+//!         (call: "1NT", meaning: (description: "Strong notrump", hand: "15-17 hcp balanced"), continuations: [
+//!             (call: "2C", meaning: (description: "Stayman", hand: "8+ hcp", forcing: OneRound)),
+//!         ]),
+//!     ],
+//! )
+//! ```
+use karty::hand::CardSet;
+use karty::suits::Suit;
+use crate::bidding::{AuctionStack, Call, DeclarationStorage};
+use crate::deal::{DealFilter, HandCondition};
+
+/// Obligation of partner to continue auction after call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Forcing{
+    #[default]
+    NonForcing,
+    /// Partner is invited to bid game with maximum of shown values
+    Invitational,
+    /// Partner must not pass in next round
+    OneRound,
+    /// Partnership must not stop below game
+    Game
+}
+
+impl Forcing{
+    pub fn is_forcing(&self) -> bool{
+        matches!(self, Forcing::OneRound | Forcing::Game)
+    }
+}
+
+/// Meaning of call: conditions hand of caller satisfies and forcing status
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallMeaning{
+    #[cfg_attr(feature = "serde", serde(default))]
+    description: String,
+    #[cfg_attr(feature = "serde", serde(default, with = "notation::conditions"))]
+    hand: Vec<HandCondition>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    forcing: Forcing
+}
+
+impl CallMeaning{
+    pub fn new(description: &str) -> Self{
+        Self{description: description.to_owned(), ..Default::default()}
+    }
+    pub fn condition(mut self, condition: HandCondition) -> Self{
+        self.hand.push(condition);
+        self
+    }
+    pub fn forcing(mut self, forcing: Forcing) -> Self{
+        self.forcing = forcing;
+        self
+    }
+    pub fn description(&self) -> &str{
+        &self.description
+    }
+    pub fn conditions(&self) -> &[HandCondition]{
+        &self.hand
+    }
+    pub fn forcing_status(&self) -> Forcing{
+        self.forcing
+    }
+    pub fn is_satisfied_by(&self, hand: &CardSet) -> bool{
+        self.hand.iter().all(|condition| condition.matches(hand))
+    }
+}
+
+/// Call of system with its meaning and continuations of partnership after it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemNode{
+    #[cfg_attr(feature = "serde", serde(with = "notation::call"))]
+    call: Call<Suit>,
+    meaning: CallMeaning,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    continuations: Vec<SystemNode>
+}
+
+impl SystemNode{
+    pub fn new(call: Call<Suit>, meaning: CallMeaning) -> Self{
+        Self{call, meaning, continuations: Vec::new()}
+    }
+    pub fn continuation(mut self, node: SystemNode) -> Self{
+        self.continuations.push(node);
+        self
+    }
+    pub fn call(&self) -> &Call<Suit>{
+        &self.call
+    }
+    pub fn meaning(&self) -> &CallMeaning{
+        &self.meaning
+    }
+    pub fn continuations(&self) -> &[SystemNode]{
+        &self.continuations
+    }
+}
+
+/// Bidding system: tree of openings and their continuations.
+/// ```
+/// use brydz_core::bidding::Call;
+/// use brydz_core::bidding::consts::{BID_C2, BID_NT1};
+/// use brydz_core::bidding::parser::parse_auction;
+/// use brydz_core::bidding::system::{BiddingSystem, CallMeaning, Forcing, SystemNode};
+/// use brydz_core::deal::{deal_at_index, HandCondition};
+/// use brydz_core::player::side::Side::{North, West};
+/// let system = BiddingSystem::new("Example")
+///     .opening(SystemNode::new(Call::NewBid(BID_NT1), CallMeaning::new("Strong notrump")
+///             .condition(HandCondition::Hcp(15..=17)).condition(HandCondition::Balanced))
+///         .continuation(SystemNode::new(Call::NewBid(BID_C2), CallMeaning::new("Stayman")
+///             .condition(HandCondition::Hcp(8..=40)).forcing(Forcing::OneRound))));
+/// let auction = parse_auction("P 1NT P", North).unwrap();
+/// let stayman = system.meaning(&auction, &Call::NewBid(BID_C2)).unwrap();
+/// assert_eq!(stayman.description(), "Stayman");
+/// assert!(stayman.forcing_status().is_forcing());
+/// // opponents bid, auction is no longer covered
+/// assert!(system.meaning(&parse_auction("P 1NT 2C", North).unwrap(), &Call::Pass).is_none());
+/// // West holds all clubs (10 hcp)
+/// let deal = deal_at_index(0).unwrap();
+/// assert_eq!(system.calls_for_hand(&auction, &deal[&West]), vec![(Call::NewBid(BID_C2), stayman)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BiddingSystem{
+    name: String,
+    openings: Vec<SystemNode>
+}
+
+impl BiddingSystem{
+    pub fn new(name: &str) -> Self{
+        Self{name: name.to_owned(), openings: Vec::new()}
+    }
+    pub fn opening(mut self, node: SystemNode) -> Self{
+        self.openings.push(node);
+        self
+    }
+    pub fn name(&self) -> &str{
+        &self.name
+    }
+    pub fn openings(&self) -> &[SystemNode]{
+        &self.openings
+    }
+
+    /// Calls defined by system for side to act. Empty when auction leaves the system:
+    /// a call of partnership is not described, opponents made call other than pass
+    /// or opponent of opener is to act.
+    pub fn next_calls<DS: DeclarationStorage<Suit>>(&self, auction: &AuctionStack<Suit, DS>) -> &[SystemNode]{
+        if auction.is_finished(){
+            return &[]
+        }
+        let mut entries = auction.history().skip_while(|entry| entry.call() == &Call::Pass).peekable();
+        let opener = match entries.peek(){
+            None => return &self.openings,
+            Some(opening) => opening.player_side().axis()
+        };
+        let mut nodes = &self.openings[..];
+        for entry in entries{
+            if entry.player_side().axis() == opener{
+                match nodes.iter().find(|node| &node.call == entry.call()){
+                    Some(node) => nodes = &node.continuations,
+                    None => return &[]
+                }
+            } else if entry.call() != &Call::Pass{
+                return &[]
+            }
+        }
+        match auction.current_side(){
+            Some(side) if side.axis() == opener => nodes,
+            _ => &[]
+        }
+    }
+
+    /// Meaning of call made by side to act
+    pub fn meaning<DS: DeclarationStorage<Suit>>(&self, auction: &AuctionStack<Suit, DS>, call: &Call<Suit>) -> Option<&CallMeaning>{
+        self.next_calls(auction).iter().find(|node| &node.call == call).map(|node| &node.meaning)
+    }
+
+    /// Legal calls of system that hand of side to act satisfies
+    pub fn calls_for_hand<DS: DeclarationStorage<Suit>>(&self, auction: &AuctionStack<Suit, DS>, hand: &CardSet) -> Vec<(Call<Suit>, &CallMeaning)>{
+        let legal: Vec<Call<Suit>> = auction.legal_calls().collect();
+        self.next_calls(auction).iter()
+            .filter(|node| legal.contains(&node.call) && node.meaning.is_satisfied_by(hand))
+            .map(|node| (node.call, &node.meaning))
+            .collect()
+    }
+
+    /// Conditions on hands shown by calls of auction, every call is read with meaning system gives it
+    /// at the moment it was made. Calls made after auction left the system show nothing.
+    /// ```
+    /// use brydz_core::bidding::Call;
+    /// use brydz_core::bidding::consts::{BID_C2, BID_NT1};
+    /// use brydz_core::bidding::parser::parse_auction;
+    /// use brydz_core::bidding::system::{BiddingSystem, CallMeaning, SystemNode};
+    /// use brydz_core::deal::{DealFilter, HandCondition};
+    /// use brydz_core::player::side::Side::{North, South};
+    /// let system = BiddingSystem::new("Example")
+    ///     .opening(SystemNode::new(Call::NewBid(BID_NT1), CallMeaning::new("Strong notrump")
+    ///             .condition(HandCondition::Hcp(15..=17)).condition(HandCondition::Balanced))
+    ///         .continuation(SystemNode::new(Call::NewBid(BID_C2), CallMeaning::new("Stayman")
+    ///             .condition(HandCondition::Hcp(8..=40)))));
+    /// let auction = parse_auction("1NT P 2C X 2S P", North).unwrap();
+    /// assert_eq!(system.auction_filter(&auction), DealFilter::new().hcp(North, 15..=17).balanced(North).hcp(South, 8..=40));
+    /// ```
+    pub fn auction_filter<DS: DeclarationStorage<Suit>>(&self, auction: &AuctionStack<Suit, DS>) -> DealFilter{
+        let mut filter = DealFilter::new();
+        let mut replay = match auction.dealer(){
+            Some(dealer) => AuctionStack::<Suit, DS>::new_with_dealer(dealer),
+            None => AuctionStack::<Suit, DS>::new()
+        };
+        for entry in auction.history(){
+            if let Some(meaning) = self.meaning(&replay, entry.call()){
+                for condition in meaning.conditions(){
+                    filter = filter.condition(entry.player_side(), condition.clone());
+                }
+            }
+            replay.add_contract_bid(entry.player_side(), entry.call().to_owned())
+                .expect("Calls already accepted in auction must be legal when replayed");
+        }
+        filter
+    }
+
+    #[cfg(feature = "serde_ron")]
+    pub fn from_ron(s: &str) -> Result<Self, ron::error::SpannedError>{
+        ron::from_str(s)
+    }
+    #[cfg(feature = "serde_ron")]
+    pub fn to_ron(&self) -> Result<String, ron::Error>{
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+    #[cfg(feature = "serde")]
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error>{
+        serde_json::from_str(s)
+    }
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error>{
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Text notation of calls and hand conditions in serialized system
+#[cfg(feature = "serde")]
+mod notation{
+    pub mod call{
+        use karty::suits::Suit;
+        use serde::{Deserialize, Deserializer, Serializer};
+        use serde::de::Error;
+        use crate::bidding::Call;
+        use crate::format::{call_symbol, parse_call_symbol};
+
+        pub fn serialize<S: Serializer>(call: &Call<Suit>, serializer: S) -> Result<S::Ok, S::Error>{
+            serializer.serialize_str(&call_symbol(call))
+        }
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Call<Suit>, D::Error>{
+            let s = String::deserialize(deserializer)?;
+            parse_call_symbol(&s).map_err(|_| D::Error::custom(format!("invalid call: {s}")))
+        }
+    }
+
+    pub mod conditions{
+        use nom::combinator::all_consuming;
+        use serde::{Deserialize, Deserializer, Serializer};
+        use serde::de::Error;
+        use crate::deal::{parse_hand_conditions, HandCondition};
+
+        pub fn serialize<S: Serializer>(conditions: &[HandCondition], serializer: S) -> Result<S::Ok, S::Error>{
+            serializer.serialize_str(&conditions.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "))
+        }
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<HandCondition>, D::Error>{
+            let s = String::deserialize(deserializer)?;
+            let parsed = all_consuming(parse_hand_conditions)(&s).map(|(_, conditions)| conditions).ok();
+            parsed.ok_or_else(|| D::Error::custom(format!("invalid hand conditions: {s}")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::card_set;
+    use karty::cards::*;
+    use crate::bidding::Call;
+    use crate::bidding::consts::{BID_C1, BID_H1, BID_H2, BID_NT1, BID_S1};
+    use crate::bidding::parser::parse_auction;
+    use crate::bidding::system::{BiddingSystem, CallMeaning, SystemNode};
+    use crate::deal::HandCondition;
+    use crate::player::side::Side::{East, West};
+    use karty::suits::Suit::{Clubs, Hearts, Spades};
+
+    fn system() -> BiddingSystem{
+        let one_heart = SystemNode::new(Call::NewBid(BID_H1), CallMeaning::new("Four hearts")
+            .condition(HandCondition::Hcp(6..=40)).condition(HandCondition::SuitLength(Hearts, 4..=13)));
+        BiddingSystem::new("Test")
+            .opening(SystemNode::new(Call::NewBid(BID_C1), CallMeaning::new("Clubs")
+                    .condition(HandCondition::Hcp(12..=21)).condition(HandCondition::SuitLength(Clubs, 3..=13)))
+                .continuation(one_heart)
+                .continuation(SystemNode::new(Call::NewBid(BID_S1), CallMeaning::new("Four spades")
+                    .condition(HandCondition::Hcp(6..=40)).condition(HandCondition::SuitLength(Spades, 4..=13)))))
+            .opening(SystemNode::new(Call::NewBid(BID_NT1), CallMeaning::new("Strong notrump")
+                .condition(HandCondition::Hcp(15..=17)).condition(HandCondition::Balanced)))
+            .opening(SystemNode::new(Call::Pass, CallMeaning::new("No opening").condition(HandCondition::Hcp(0..=11))))
+    }
+
+    #[test]
+    fn calls_follow_auction(){
+        let system = system();
+        // 16 hcp, 4-4-3-2
+        let strong = card_set![ACE_SPADES, KING_SPADES, FOUR_SPADES, THREE_SPADES, ACE_HEARTS, QUEEN_HEARTS, FIVE_HEARTS, TWO_HEARTS,
+            KING_DIAMONDS, FOUR_DIAMONDS, THREE_DIAMONDS, JACK_CLUBS, TWO_CLUBS];
+        let auction = parse_auction("P", West).unwrap();
+        let calls: Vec<Call<_>> = system.calls_for_hand(&auction, &strong).into_iter().map(|(call, _)| call).collect();
+        assert_eq!(calls, vec![Call::NewBid(BID_NT1)]);
+
+        let auction = parse_auction("P 1C P", West).unwrap();
+        let calls: Vec<Call<_>> = system.calls_for_hand(&auction, &strong).into_iter().map(|(call, _)| call).collect();
+        assert_eq!(calls, vec![Call::NewBid(BID_H1), Call::NewBid(BID_S1)]);
+        assert_eq!(system.meaning(&auction, &Call::NewBid(BID_S1)).unwrap().description(), "Four spades");
+        assert!(system.meaning(&auction, &Call::NewBid(BID_H2)).is_none());
+        // opponent of opener to act
+        assert!(system.next_calls(&parse_auction("1C", East).unwrap()).is_empty());
+        assert!(system.next_calls(&parse_auction("1C X", East).unwrap()).is_empty());
+    }
+
+    #[cfg(feature = "serde_ron")]
+    #[test]
+    fn sayc_loads_from_ron(){
+        use crate::bidding::consts::BID_C2;
+        let sayc = BiddingSystem::from_ron(include_str!("../../data/sayc.ron")).unwrap();
+        assert_eq!(sayc.name(), "SAYC");
+        let auction = parse_auction("1NT P", West).unwrap();
+        assert!(sayc.meaning(&auction, &Call::NewBid(BID_C2)).unwrap().forcing_status().is_forcing());
+        assert_eq!(BiddingSystem::from_ron(&sayc.to_ron().unwrap()).unwrap(), sayc);
+        assert_eq!(BiddingSystem::from_json(&sayc.to_json().unwrap()).unwrap(), sayc);
+    }
+}
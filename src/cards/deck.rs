@@ -1,6 +1,6 @@
 
 use arrayvec::ArrayVec;
-use rand::{thread_rng};
+use rand::{Rng, thread_rng};
 use itertools::Itertools;
 use std::ops::Index;
 use rand::seq::SliceRandom;
@@ -47,8 +47,10 @@ impl Deck{
     }
 
     pub fn shuffle(&mut self){
-        let mut rng = thread_rng();
-        self.cards.shuffle(&mut rng);
+        self.shuffle_with_rng(&mut thread_rng());
+    }
+    pub fn shuffle_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R){
+        self.cards.shuffle(rng);
     }
     pub fn cards(&self) -> &ArrayVec<Card2SGen<Figure, Suit>, DECK_SIZE>{
         &self.cards
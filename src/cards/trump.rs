@@ -189,10 +189,10 @@ pub const TRUMP_HEARTS: Trump = Trump::Colored(Hearts);
 pub const TRUMP_SPADES: Trump = Trump::Colored(Spades);
 pub const NO_TRUMP: Trump = Trump::NoTrump;
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "serde_dedicate", feature = "serde_derive")))]
 mod tests{
     use karty::suits::Suit;
-    use karty::suits::Suit::{Diamonds, Hearts};
+    use karty::suits::Suit::Hearts;
     use crate::cards::trump::TrumpGen;
 
     #[test]
@@ -209,6 +209,7 @@ mod tests{
     #[cfg(feature = "serde_dedicate")]
     fn deserialize_trump(){
         use ron;
+        use karty::suits::Suit::Diamonds;
         assert_eq!(ron::from_str::<TrumpGen<Suit>>("NoTrump").unwrap(), TrumpGen::NoTrump);
         assert_eq!(ron::from_str::<TrumpGen<Suit>>("Diamonds").unwrap(), TrumpGen::Colored(Diamonds));
     }
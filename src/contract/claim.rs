@@ -0,0 +1,197 @@
+use karty::cards::Card2SymTrait;
+use karty::hand::{CardSet, HandSuitedTrait};
+use karty::suits::SUITS;
+use crate::cards::trump::TrumpGen;
+use crate::contract::{Contract, ContractMechanics};
+use crate::contract::double_dummy::DoubleDummySolver;
+use crate::error::{BridgeCoreError, ContractError, ContractErrorGen};
+use crate::player::side::{Side, SideMap};
+
+/// Claim of side: number of remaining tricks (including current one) its axis takes,
+/// the other remaining tricks are conceded to opponents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "speedy", derive(speedy::Writable, speedy::Readable))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Claim{
+    side: Side,
+    tricks: u8
+}
+
+impl Claim{
+    pub fn new(side: Side, tricks: u8) -> Self{
+        Self{side, tricks}
+    }
+    pub fn side(&self) -> Side{
+        self.side
+    }
+    pub fn tricks(&self) -> u8{
+        self.tricks
+    }
+}
+
+/// Method of checking claim against hands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ClaimCheck{
+    /// Side on lead (claimer or partner, with empty current trick) holds enough cards that win
+    /// when cashed one by one: higher than every card of opponents in suit, and in side suits
+    /// only as long as opponents holding trumps follow suit
+    TopWinners,
+    /// Axis of claimer takes at least claimed tricks with perfect play of every side
+    #[default]
+    DoubleDummy
+}
+
+/// Number of tricks side on lead surely takes by cashing its top cards
+fn top_winners(hands: &SideMap<CardSet>, leader: Side, trump: &TrumpGen<karty::suits::Suit>) -> u8{
+    let opponents = [leader.next(), leader.prev()];
+    let opponents_with_trumps: Vec<Side> = match trump{
+        TrumpGen::Colored(t) => opponents.iter().filter(|s| hands[s].contains_in_suit(t)).copied().collect(),
+        TrumpGen::NoTrump => Vec::new()
+    };
+    SUITS.iter().map(|suit|{
+        let highest_opponent = opponents.iter()
+            .flat_map(|s| hands[s].suit_iterator(suit)).map(|c| c.figure()).max();
+        let winners = hands[&leader].suit_iterator(suit)
+            .filter(|c| highest_opponent.is_none_or(|h| c.figure() > h)).count();
+        let ruffed_after = match trump{
+            TrumpGen::Colored(t) if t != suit => opponents_with_trumps.iter()
+                .map(|s| hands[s].suit_iterator(suit).count()).min(),
+            _ => None
+        };
+        ruffed_after.map_or(winners, |n| winners.min(n)) as u8
+    }).sum()
+}
+
+impl Contract{
+    /// Checks that `side` may claim now: it is the player to act (declarer acts when dummy is to play).
+    /// Dummy never claims.
+    /// ```
+    /// use brydz_core::bidding::Bid;
+    /// use brydz_core::cards::trump::TrumpGen;
+    /// use brydz_core::contract::{Contract, ContractMechanics, ContractParametersGen};
+    /// use brydz_core::error::ContractErrorGen;
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::cards::ACE_HEARTS;
+    /// let mut contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+    /// assert_eq!(contract.check_claimer(South), Err(ContractErrorGen::ClaimByDummy(South)));
+    /// assert_eq!(contract.check_claimer(West), Err(ContractErrorGen::CurrentSidePresume(East, West)));
+    /// assert!(contract.check_claimer(East).is_ok());
+    /// contract.insert_card(East, ACE_HEARTS).unwrap();
+    /// // dummy is to play, declarer may claim
+    /// assert!(contract.check_claimer(North).is_ok());
+    /// ```
+    pub fn check_claimer(&self, side: Side) -> Result<(), ContractError>{
+        if side == self.dummy(){
+            return Err(ContractErrorGen::ClaimByDummy(side))
+        }
+        if self.is_completed(){
+            return Err(match self.accepted_claim(){
+                Some(_) => ContractErrorGen::ContractClaimed,
+                None => ContractErrorGen::ContractFull
+            })
+        }
+        let on_turn = match self.current_side(){
+            s if s == self.dummy() => self.declarer(),
+            s => s
+        };
+        match side == on_turn{
+            true => Ok(()),
+            false => Err(ContractErrorGen::CurrentSidePresume(on_turn, side))
+        }
+    }
+
+    /// Checks claim against `hands` — cards still held by sides (cards of current trick are not there).
+    /// Claim of more tricks than remain is never valid.
+    pub fn is_claim_valid(&self, claim: &Claim, hands: &SideMap<CardSet>, check: ClaimCheck) -> Result<bool, BridgeCoreError>{
+        if self.is_completed() || claim.tricks > self.remaining_tricks(){
+            return Ok(false)
+        }
+        if claim.tricks == 0{
+            return Ok(true)
+        }
+        match check{
+            ClaimCheck::TopWinners => {
+                let leader = self.current_side();
+                Ok(self.current_trick().is_empty() && leader.axis() == claim.side.axis()
+                    && top_winners(hands, leader, self.trump()) >= claim.tricks)
+            },
+            ClaimCheck::DoubleDummy => {
+                let mut solver = DoubleDummySolver::new(*self.trump());
+                Ok(solver.solve(hands, self.current_trick(), claim.side.axis())? >= claim.tricks)
+            }
+        }
+    }
+
+    /// Validates claim and closes contract with it.
+    /// # Example:
+    /// ```
+    /// use brydz_core::bidding::Bid;
+    /// use brydz_core::cards::trump::TrumpGen;
+    /// use brydz_core::contract::{Claim, ClaimCheck, Contract, ContractMechanics, ContractParametersGen};
+    /// use brydz_core::error::{BridgeCoreErrorGen, ContractErrorGen};
+    /// use brydz_core::player::axis::Axis::{EastWest, NorthSouth};
+    /// use brydz_core::player::side::Side::*;
+    /// use brydz_core::player::side::SideMap;
+    /// use karty::cards::*;
+    /// use karty::hand::{HAND_OF_CLUBS, HAND_OF_DIAMONDS, HAND_OF_HEARTS, HAND_OF_SPADES};
+    /// use karty::suits::Suit::Spades;
+    /// let deal = SideMap::new(HAND_OF_SPADES, HAND_OF_HEARTS, HAND_OF_DIAMONDS, HAND_OF_CLUBS);
+    /// let mut contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 4).unwrap()));
+    /// // East is on lead, North ruffs every lead and then draws trumps
+    /// assert_eq!(contract.claim(Claim::new(South, 13), &deal, ClaimCheck::TopWinners),
+    ///     Err(BridgeCoreErrorGen::Contract(ContractErrorGen::ClaimRejected(South, 13))));
+    /// contract.claim(Claim::new(South, 13), &deal, ClaimCheck::DoubleDummy).unwrap();
+    /// assert!(contract.is_completed());
+    /// assert_eq!(contract.total_tricks_taken_axis(NorthSouth), 13);
+    /// assert_eq!(contract.total_tricks_taken_axis(EastWest), 0);
+    /// ```
+    pub fn claim(&mut self, claim: Claim, hands: &SideMap<CardSet>, check: ClaimCheck) -> Result<(), BridgeCoreError>{
+        match self.is_claim_valid(&claim, hands, check)?{
+            true => Ok(self.accept_claim(claim)?),
+            false => Err(ContractErrorGen::ClaimRejected(claim.side, claim.tricks).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::card_set;
+    use karty::cards::*;
+    use karty::suits::Suit::Hearts;
+    use crate::bidding::Bid;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::{Claim, Contract, ContractMechanics, ContractParametersGen};
+    use crate::contract::claim::top_winners;
+    use crate::error::ContractErrorGen;
+    use crate::player::axis::Axis::{EastWest, NorthSouth};
+    use crate::player::side::Side::*;
+    use crate::player::side::SideMap;
+
+    #[test]
+    fn top_winners_stop_at_ruff(){
+        let hands = SideMap::new(
+            card_set![ACE_SPADES, KING_SPADES, QUEEN_SPADES, ACE_CLUBS],
+            card_set![TWO_SPADES, THREE_SPADES, TWO_HEARTS, TWO_CLUBS],
+            card_set![FOUR_SPADES, FIVE_SPADES, SIX_SPADES, SEVEN_SPADES],
+            card_set![EIGHT_SPADES, NINE_SPADES, TEN_SPADES, THREE_HEARTS]);
+        assert_eq!(top_winners(&hands, North, &TrumpGen::NoTrump), 4);
+        // East ruffs the third spade and West ruffs the club
+        assert_eq!(top_winners(&hands, North, &TrumpGen::Colored(Hearts)), 2);
+    }
+
+    #[test]
+    fn claim_closes_contract(){
+        let mut contract = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+        contract.accept_claim(Claim::new(West, 4)).unwrap();
+        assert!(contract.is_completed());
+        assert_eq!(contract.total_tricks_taken_axis(EastWest), 4);
+        assert_eq!(contract.total_tricks_taken_axis(NorthSouth), 9);
+        assert_eq!(contract.total_tricks_taken_side(West), 4);
+        assert_eq!(contract.total_tricks_taken_side(North), 9);
+        assert_eq!(contract.insert_card(West, ACE_CLUBS), Err(ContractErrorGen::ContractClaimed));
+        assert_eq!(contract.undo(), Err(ContractErrorGen::ContractClaimed));
+        assert_eq!(contract.withdraw_claim(), Some(Claim::new(West, 4)));
+        assert!(!contract.is_completed());
+        assert!(contract.accept_claim(Claim::new(West, 14)).is_err());
+    }
+}
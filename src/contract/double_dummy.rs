@@ -0,0 +1,951 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use karty::cards::Card2SymTrait;
+use karty::hand::{CardSet, HandTrait};
+use karty::suits::Suit;
+use karty::symbol::CardSymbol;
+use crate::cards::trump::TrumpGen;
+use crate::contract::{Contract, ContractMechanics, ContractParameters, Trick};
+use crate::error::{BridgeCoreError, ContractErrorGen, DistributionError, TrickErrorGen};
+use crate::player::axis::Axis;
+use crate::player::side::{Side, SideMap, SIDES};
+
+const SUIT_WIDTH: usize = 13;
+const SUIT_MASK: u64 = 0x1fff;
+
+/// Cheap multiplicative hasher for transposition keys (they are already well mixed bit patterns).
+#[derive(Default)]
+struct KeyHasher{
+    hash: u64
+}
+
+impl Hasher for KeyHasher{
+    fn finish(&self) -> u64 {
+        let h = (self.hash ^ (self.hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^ (h >> 33)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes{
+            self.write_u64(*b as u64);
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ i).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write_u64(i as u64);
+        self.write_u64((i >> 64) as u64);
+    }
+}
+
+/// Position of counts of relevant cards in masks of [`TranspositionTable`] (above packed owners of suits)
+const COUNTS_SHIFT: usize = 8 * SUIT_WIDTH;
+
+/// Bounds on tricks North-South take in the rest of play.
+///
+/// Bounds are valid for every position with the same leader and suit lengths in hands
+/// in which the relevant (highest) cards of every suit have the same owners (relative ranks).
+/// Lower cards were irrelevant for the result.
+/// Owners of cards are packed by [`Search::owners`], mask (built by [`Search::relevance`]) selects
+/// the relevant ones and keeps their counts, so masked owners identify the bounds.
+#[derive(Debug, Clone, Default)]
+struct TranspositionTable{
+    /// Masks used with lengths key, each with bit filter of its stored patterns
+    /// (most of missing patterns are rejected without reaching `bounds`)
+    masks: HashMap<u128, Vec<(u128, u64)>, BuildHasherDefault<KeyHasher>>,
+    bounds: HashMap<(u128, u128), (u8, u8), BuildHasherDefault<KeyHasher>>
+}
+
+impl TranspositionTable{
+    fn pattern(owners: u128, mask: u128) -> (u128, u64){
+        let pattern = (owners | (!0u128 << COUNTS_SHIFT)) & mask;
+        let bit = 1 << (((pattern as u64) ^ ((pattern >> 64) as u64)).wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 58);
+        (pattern, bit)
+    }
+
+    /// Finds bounds deciding if North-South takes `need` tricks, returns the decision and mask of bounds.
+    fn probe(&mut self, key: u128, owners: u128, need: u8) -> Option<(bool, u128)>{
+        let masks = self.masks.get_mut(&key)?;
+        let (i, result) = masks.iter().enumerate().find_map(|(i, (mask, filter))|{
+            let (pattern, bit) = Self::pattern(owners, *mask);
+            match filter & bit{
+                0 => None,
+                _ => match self.bounds.get(&(key, pattern)){
+                    Some((lower, _)) if *lower >= need => Some((i, true)),
+                    Some((_, upper)) if *upper < need => Some((i, false)),
+                    _ => None
+                }
+            }
+        })?;
+        //masks deciding often are checked first
+        masks[..=i].rotate_right(1);
+        Some((result, masks[0].0))
+    }
+
+    fn store(&mut self, key: u128, owners: u128, mask: u128, lower: u8, upper: u8){
+        let masks = self.masks.entry(key).or_default();
+        let (pattern, bit) = Self::pattern(owners, mask);
+        match masks.iter_mut().find(|(m, _)| *m == mask){
+            Some((_, filter)) => *filter |= bit,
+            None => masks.push((mask, bit))
+        }
+        let bounds = self.bounds.entry((key, pattern)).or_insert((lower, upper));
+        bounds.0 = bounds.0.max(lower);
+        bounds.1 = bounds.1.min(upper);
+    }
+
+    fn clear(&mut self){
+        self.masks.clear();
+        self.bounds.clear();
+    }
+}
+
+fn suit_bits(cards: u64, suit: usize) -> u64{
+    (cards >> (suit * SUIT_WIDTH)) & SUIT_MASK
+}
+
+/// The highest `n` cards of `bits`
+fn top_cards(bits: u64, n: u32) -> u64{
+    let mut rest = bits;
+    for _ in 0..(bits.count_ones().saturating_sub(n)){
+        rest &= rest - 1;
+    }
+    rest
+}
+
+fn card_suit(card: u8) -> usize{
+    card as usize / SUIT_WIDTH
+}
+
+/// Double dummy solver (every hand is visible to every player).
+///
+/// Solver works for one trump (denomination) and caches analysed positions between calls,
+/// therefore it is worth reusing it when many positions with the same trump are solved
+/// (e.g. different leaders of the same deal).
+/// Search is alpha-beta (in null window form) with transposition table keyed by relative ranks,
+/// equivalent card pruning and quick tricks cut-offs.
+/// # Example:
+/// ```
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::double_dummy::DoubleDummySolver;
+/// use brydz_core::contract::Trick;
+/// use brydz_core::player::axis::Axis::{EastWest, NorthSouth};
+/// use brydz_core::player::side::Side::*;
+/// use brydz_core::player::side::SideMap;
+/// use karty::card_set;
+/// use karty::cards::*;
+/// let hands = SideMap::new(
+///     card_set![ACE_SPADES, KING_SPADES],
+///     card_set![QUEEN_SPADES, JACK_SPADES],
+///     card_set![TWO_SPADES, TWO_HEARTS],
+///     card_set![ACE_HEARTS, KING_HEARTS]);
+/// let mut solver = DoubleDummySolver::new(TrumpGen::NoTrump);
+/// assert_eq!(solver.solve(&hands, &Trick::new(North), NorthSouth).unwrap(), 2);
+/// assert_eq!(solver.solve(&hands, &Trick::new(West), NorthSouth).unwrap(), 0);
+/// assert_eq!(solver.solve(&hands, &Trick::new(East), EastWest).unwrap(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DoubleDummySolver{
+    trump: TrumpGen<Suit>,
+    table: TranspositionTable,
+    /// Tricks of North-South in the last solved position, first guess for the next one
+    last: Option<u8>,
+}
+
+impl DoubleDummySolver{
+    pub fn new(trump: TrumpGen<Suit>) -> Self{
+        Self{trump, table: TranspositionTable::default(), last: None}
+    }
+
+    pub fn trump(&self) -> &TrumpGen<Suit>{
+        &self.trump
+    }
+
+    /// Forgets analysed positions.
+    pub fn clear(&mut self){
+        self.table.clear();
+        self.last = None;
+    }
+
+    /// Returns the maximal number of the remaining tricks (including current one) that `axis`
+    /// takes with perfect play of every side.
+    /// `hands` are cards still held by sides (cards already placed in `trick` must not be there),
+    /// `trick` is current (possibly empty) trick which tells whose turn it is.
+    pub fn solve(&mut self, hands: &SideMap<CardSet>, trick: &Trick, axis: Axis) -> Result<u8, BridgeCoreError>{
+        let mut search = Search::new(hands, trick, &self.trump)?;
+        let total = search.tricks_left();
+        let north_south = search.solve(&mut self.table, self.last);
+        self.last = Some(north_south);
+        Ok(match axis{
+            Axis::NorthSouth => north_south,
+            Axis::EastWest => total - north_south
+        })
+    }
+}
+
+/// Checks that `deal` is full deal with equal hands and computes the maximal number of tricks
+/// declarer takes with perfect play.
+/// # Example:
+/// ```
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::ContractParametersGen;
+/// use brydz_core::contract::double_dummy::solve_deal;
+/// use brydz_core::player::side::Side::*;
+/// use brydz_core::player::side::SideMap;
+/// use karty::hand::{CardSet, HAND_OF_CLUBS, HAND_OF_DIAMONDS, HAND_OF_HEARTS, HAND_OF_SPADES};
+/// use karty::suits::Suit::Spades;
+/// let deal = SideMap::new(HAND_OF_SPADES, HAND_OF_HEARTS, HAND_OF_DIAMONDS, HAND_OF_CLUBS);
+/// let contract = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 7).unwrap());
+/// assert_eq!(solve_deal(&deal, &contract).unwrap(), 13);
+/// let contract = ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 1).unwrap());
+/// //West leads clubs and takes every trick
+/// assert_eq!(solve_deal(&deal, &contract).unwrap(), 0);
+/// ```
+pub fn solve_deal(deal: &SideMap<CardSet>, parameters: &ContractParameters) -> Result<u8, BridgeCoreError>{
+    let mut solver = DoubleDummySolver::new(*parameters.bid().trump());
+    solver.solve(deal, &Trick::new(parameters.declarer().next()), parameters.declarer().axis())
+}
+
+/// Position of trump in rows of [`DoubleDummyTable`]: clubs, diamonds, hearts, spades, no trump.
+fn trump_index(trump: &TrumpGen<Suit>) -> usize{
+    match trump{
+        TrumpGen::Colored(s) => s.usize_index(),
+        TrumpGen::NoTrump => Suit::SYMBOL_SPACE
+    }
+}
+
+/// Double dummy tricks for every declarer and every trump (20 results of a deal).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoubleDummyTable{
+    tricks: SideMap<[u8; 5]>
+}
+
+impl DoubleDummyTable{
+    /// Creates table from rows of tricks of every declarer, row is in order:
+    /// clubs, diamonds, hearts, spades, no trump.
+    pub fn new(tricks: SideMap<[u8; 5]>) -> Self{
+        Self{tricks}
+    }
+
+    /// Tricks that `declarer` takes playing in `trump`.
+    pub fn tricks(&self, declarer: Side, trump: &TrumpGen<Suit>) -> u8{
+        self.tricks[&declarer][trump_index(trump)]
+    }
+
+    /// Tricks of `axis` when it chooses the better declarer for `trump`.
+    pub fn tricks_axis(&self, axis: Axis, trump: &TrumpGen<Suit>) -> u8{
+        SIDES.iter().filter(|s| s.axis() == axis)
+            .map(|s| self.tricks(*s, trump)).max().unwrap_or(0)
+    }
+}
+
+/// Solves full deal for every declarer and every trump.
+/// # Example:
+/// ```
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::double_dummy::solve_table;
+/// use brydz_core::player::axis::Axis::EastWest;
+/// use brydz_core::player::side::Side::*;
+/// use brydz_core::player::side::SideMap;
+/// use karty::hand::{HAND_OF_CLUBS, HAND_OF_DIAMONDS, HAND_OF_HEARTS, HAND_OF_SPADES};
+/// use karty::suits::Suit::{Clubs, Hearts, Spades};
+/// let deal = SideMap::new(HAND_OF_SPADES, HAND_OF_HEARTS, HAND_OF_DIAMONDS, HAND_OF_CLUBS);
+/// let table = solve_table(&deal).unwrap();
+/// assert_eq!(table.tricks(North, &TrumpGen::Colored(Spades)), 13);
+/// assert_eq!(table.tricks(South, &TrumpGen::Colored(Spades)), 13);
+/// assert_eq!(table.tricks(North, &TrumpGen::Colored(Hearts)), 0);
+/// assert_eq!(table.tricks(West, &TrumpGen::NoTrump), 0);
+/// assert_eq!(table.tricks(East, &TrumpGen::NoTrump), 0);
+/// assert_eq!(table.tricks_axis(EastWest, &TrumpGen::Colored(Clubs)), 13);
+/// ```
+pub fn solve_table(deal: &SideMap<CardSet>) -> Result<DoubleDummyTable, BridgeCoreError>{
+    let mut tricks = SideMap::new_symmetric([0u8; 5]);
+    for trump in [TrumpGen::Colored(Suit::Clubs), TrumpGen::Colored(Suit::Diamonds),
+        TrumpGen::Colored(Suit::Hearts), TrumpGen::Colored(Suit::Spades), TrumpGen::NoTrump]{
+        //one solver per trump shares transposition table between leaders,
+        //declarers of the same axis follow each other, so the previous result is a close first guess
+        let mut solver = DoubleDummySolver::new(trump);
+        for declarer in [Side::North, Side::South, Side::East, Side::West]{
+            tricks[&declarer][trump_index(&trump)] =
+                solver.solve(deal, &Trick::new(declarer.next()), declarer.axis())?;
+        }
+    }
+    Ok(DoubleDummyTable{tricks})
+}
+
+/// Removes cards already played in `contract` from the original `deal`.
+/// Returns error if some played card was not in hand of side that played it.
+pub fn remaining_hands(deal: &SideMap<CardSet>, contract: &Contract) -> Result<SideMap<CardSet>, BridgeCoreError>{
+    let mut hands = *deal;
+    let completed = contract.count_completed_tricks();
+    for trick in (0..completed).map(|i| &contract[i]).chain(std::iter::once(contract.current_trick())){
+        for side in SIDES{
+            if let Some(card) = trick[side]{
+                hands[&side].remove_card(&card)
+                    .map_err(|_| ContractErrorGen::CardNotInHand(side, card))?;
+            }
+        }
+    }
+    Ok(hands)
+}
+
+/// Computes the maximal number of tricks declarer takes in total (already taken tricks
+/// are included) when play continues perfectly from current state of `contract`.
+/// `deal` is the original (complete) distribution of cards.
+/// # Example:
+/// ```
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::{Contract, ContractMechanics, ContractParametersGen};
+/// use brydz_core::contract::double_dummy::solve_contract;
+/// use brydz_core::player::side::Side::*;
+/// use brydz_core::player::side::SideMap;
+/// use karty::cards::*;
+/// use karty::hand::{HAND_OF_CLUBS, HAND_OF_DIAMONDS, HAND_OF_HEARTS, HAND_OF_SPADES};
+/// use karty::suits::Suit::Hearts;
+/// let deal = SideMap::new(HAND_OF_SPADES, HAND_OF_HEARTS, HAND_OF_DIAMONDS, HAND_OF_CLUBS);
+/// let mut contract = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Hearts), 2).unwrap()));
+/// assert_eq!(solve_contract(&deal, &contract).unwrap(), 0);
+/// contract.insert_card(West, ACE_CLUBS).unwrap();
+/// contract.insert_card(North, TWO_SPADES).unwrap();
+/// assert_eq!(solve_contract(&deal, &contract).unwrap(), 0);
+/// ```
+pub fn solve_contract(deal: &SideMap<CardSet>, contract: &Contract) -> Result<u8, BridgeCoreError>{
+    let hands = remaining_hands(deal, contract)?;
+    let axis = contract.declarer().axis();
+    let taken = contract.total_tricks_taken_axis(axis) as u8;
+    if contract.is_completed(){
+        return Ok(taken);
+    }
+    let mut solver = DoubleDummySolver::new(*contract.contract_spec().bid().trump());
+    Ok(taken + solver.solve(&hands, contract.current_trick(), axis)?)
+}
+
+/// Search state, hands are indexed with [`Side::index`].
+struct Search{
+    hands: [u64; 4],
+    /// Cards not yet in completed tricks (cards of current trick are still here)
+    remaining: u64,
+    trump: Option<usize>,
+    leader: usize,
+    played: [u8; 4],
+    in_trick: usize,
+    ns_won: u8,
+    /// Owners of remaining cards, every suit takes 26 bits, valid at the beginning of trick
+    owners: u128,
+}
+
+impl Search{
+    fn new(hands: &SideMap<CardSet>, trick: &Trick, trump: &TrumpGen<Suit>) -> Result<Self, BridgeCoreError>{
+        let mut masks = [0u64; 4];
+        let mut union = 0u64;
+        for side in SIDES{
+            let mask = u64::from(hands[&side]);
+            if let Some(c) = CardSet::from(union & mask).into_iter().next(){
+                return Err(ContractErrorGen::DuplicateCard(c).into());
+            }
+            union |= mask;
+            masks[side.index() as usize] = mask;
+        }
+        let leader = trick.first_player_side().index() as usize;
+        let mut played = [0u8; 4];
+        let in_trick = trick.count_cards() as usize;
+        for i in 0..in_trick{
+            let side = SIDES[(leader + i) & 3];
+            let card = trick[side].ok_or(ContractErrorGen::BadTrick(TrickErrorGen::MissingCard(side)))?;
+            if union & card.mask() != 0{
+                return Err(ContractErrorGen::DuplicateCard(card).into());
+            }
+            let led = card_suit(played[0]);
+            if i > 0 && card.suit().usize_index() != led && suit_bits(masks[side.index() as usize], led) != 0{
+                return Err(ContractErrorGen::IgnoredCalledSuit(side, Suit::from_usize_index(led).unwrap()).into());
+            }
+            played[i] = card.usize_index() as u8;
+        }
+        //hands of sides that already played to current trick hold one card less
+        let lead_len = masks[leader].count_ones() as usize + usize::from(in_trick > 0);
+        let consistent = (0..4).all(|seat|{
+            let pos = (seat + 4 - leader) & 3;
+            let len = masks[seat].count_ones() as usize;
+            len + usize::from(pos < in_trick) == lead_len
+        });
+        if !consistent{
+            return Err(DistributionError::NotEqualCardNumbers(hands.transform(|h| h.len())).into())
+        }
+        let remaining = played[..in_trick].iter().fold(union, |acc, c| acc | (1u64 << c));
+        let mut search = Self{
+            hands: masks,
+            remaining,
+            trump: match trump{
+                TrumpGen::Colored(s) => Some(s.usize_index()),
+                TrumpGen::NoTrump => None
+            },
+            leader,
+            played,
+            in_trick,
+            ns_won: 0,
+            owners: 0,
+        };
+        search.update_owners(0b1111);
+        Ok(search)
+    }
+
+    fn tricks_left(&self) -> u8{
+        (self.remaining.count_ones() / 4) as u8
+    }
+
+    /// Finds tricks of North-South with null window searches, the first two probe `guess` and its neighbour
+    /// (searches close to the result are the most expensive), then the rest of range is bisected.
+    fn solve(&mut self, table: &mut TranspositionTable, guess: Option<u8>) -> u8{
+        let mut low = 0u8;
+        let mut high = self.tricks_left();
+        let mut probes = 0;
+        while low < high{
+            let target = match (guess, probes){
+                (Some(g), 0) => g.clamp(low + 1, high),
+                (Some(_), 1) if low > 0 => low + 1,
+                (Some(_), 1) => high,
+                _ => (low + high).div_ceil(2)
+            };
+            probes += 1;
+            if self.reaches(table, target).0{
+                low = target;
+            } else {
+                high = target - 1;
+            }
+        }
+        low
+    }
+
+    fn beats(&self, card: u8, winning: u8) -> bool{
+        let (suit, winning_suit) = (card_suit(card), card_suit(winning));
+        if suit == winning_suit{
+            card > winning
+        } else {
+            Some(suit) == self.trump
+        }
+    }
+
+    /// Returns position in `played` of currently winning card
+    fn winning_position(&self) -> usize{
+        (1..self.in_trick).fold(0, |best, i|{
+            if self.beats(self.played[i], self.played[best]) { i } else { best }
+        })
+    }
+
+    /// Number of tricks side on `seat` surely takes cashing top cards from own hand,
+    /// cards that decide about it and mask of suits in which it has at least one such card.
+    fn cashable(&self, seat: usize) -> (u32, u64, u8){
+        let own = self.hands[seat];
+        let left = self.hands[(seat + 1) & 3];
+        let right = self.hands[(seat + 3) & 3];
+        let others = left | right | self.hands[(seat + 2) & 3];
+        let mut tricks = 0u32;
+        let mut relevant = 0u64;
+        let mut suits = 0u8;
+        for suit in 0..4{
+            let mine = suit_bits(own, suit);
+            if mine == 0{
+                continue;
+            }
+            //own cards higher than any card of other hands
+            let winners = match suit_bits(others, suit){
+                0 => mine,
+                r => mine >> (64 - r.leading_zeros()) << (64 - r.leading_zeros())
+            };
+            let mut top = winners.count_ones();
+            //when the others are exhausted by top cards, the low ones win too
+            let longest_other = suit_bits(left, suit).count_ones().max(suit_bits(right, suit).count_ones())
+                .max(suit_bits(self.hands[(seat + 2) & 3], suit).count_ones());
+            if top >= longest_other{
+                top = mine.count_ones();
+            }
+            if let Some(trump) = self.trump{
+                if trump != suit{
+                    for opponent in [left, right]{
+                        if suit_bits(opponent, trump) != 0{
+                            top = top.min(suit_bits(opponent, suit).count_ones());
+                        }
+                    }
+                }
+            }
+            if top > 0{
+                relevant |= winners << (suit * SUIT_WIDTH);
+                suits |= 1 << suit;
+                tricks += top;
+            }
+        }
+        (tricks.min(own.count_ones()), relevant, suits)
+    }
+
+    /// Tricks that side of `seat` surely takes with top trumps (every trump higher than
+    /// any trump of other hands wins its own trick) and these trumps.
+    fn sure_trump_tricks(&self, seat: usize) -> (u8, u64){
+        let trump = match self.trump{
+            Some(t) => t,
+            None => return (0, 0)
+        };
+        let mut tricks = 0u32;
+        let mut relevant = 0u64;
+        for hand in [seat, (seat + 2) & 3]{
+            let mine = suit_bits(self.hands[hand], trump);
+            let others = (0..4).filter(|h| *h != hand).fold(0u64, |acc, h| acc | suit_bits(self.hands[h], trump));
+            let top = match others{
+                0 => mine,
+                r => top_cards(mine, (mine >> (64 - r.leading_zeros())).count_ones())
+            };
+            tricks += top.count_ones();
+            relevant |= top << (trump * SUIT_WIDTH);
+        }
+        (tricks as u8, relevant)
+    }
+
+    /// Number of tricks leader surely takes cashing top cards (own, then partner's when
+    /// partner can be reached) and cards that decide about it.
+    fn quick_tricks(&self, seat: usize) -> (u8, u64){
+        let (own, own_cards, _) = self.cashable(seat);
+        let (partner, partner_cards, partner_suits) = self.cashable((seat + 2) & 3);
+        let entry = (0..4).any(|suit| partner_suits & (1 << suit) != 0 && suit_bits(self.hands[seat], suit) != 0);
+        //partner keeps its winners while leader cashes, when it has enough other cards to play
+        let spare = self.hands[(seat + 2) & 3].count_ones() - partner;
+        match (entry, spare >= own){
+            (true, true) => ((own + partner) as u8, own_cards | partner_cards),
+            (true, false) if partner > own => (partner as u8, partner_cards),
+            _ => (own as u8, own_cards)
+        }
+    }
+
+    /// Suit lengths in hands and leader (exact part of position key)
+    fn lengths_key(&self) -> u128{
+        let mut key = self.leader as u128;
+        for suit in 0..4{
+            for hand in self.hands{
+                key = (key << 4) | suit_bits(hand, suit).count_ones() as u128;
+            }
+        }
+        key
+    }
+
+    /// Owners of remaining cards of suit (2 bits per card) filled from the highest card,
+    /// patterns of suits are packed in [`Search::owners`].
+    fn suit_owners(&self, suit: usize) -> u128{
+        let remaining = suit_bits(self.remaining, suit);
+        let (first, second) = (suit_bits(self.hands[1] | self.hands[3], suit), suit_bits(self.hands[2] | self.hands[3], suit));
+        let mut bits = remaining;
+        let mut pattern = 0u128;
+        let mut shift = 2 * SUIT_WIDTH;
+        while bits != 0{
+            let top = 63 - bits.leading_zeros();
+            bits ^= 1 << top;
+            shift -= 2;
+            let owner = ((first >> top) & 1) | (((second >> top) & 1) << 1);
+            pattern |= (owner as u128) << shift;
+        }
+        pattern << (2 * SUIT_WIDTH * suit)
+    }
+
+    /// Updates packed owners of suits in `suits` mask
+    fn update_owners(&mut self, suits: u8){
+        for suit in (0..4).filter(|s| suits & (1 << s) != 0){
+            let field = ((1u128 << (2 * SUIT_WIDTH)) - 1) << (2 * SUIT_WIDTH * suit);
+            self.owners = (self.owners & !field) | self.suit_owners(suit);
+        }
+    }
+
+    /// Mask of owners of relevant cards (every card above the lowest relevant one becomes relevant)
+    /// with their counts in suits.
+    fn relevance(&self, relevant: u64) -> u128{
+        let mut mask = 0u128;
+        for suit in 0..4{
+            let rel = suit_bits(relevant, suit);
+            if rel != 0{
+                let count = (suit_bits(self.remaining, suit) >> rel.trailing_zeros()).count_ones() as usize;
+                let field = ((1u128 << (2 * count)) - 1) << (2 * (SUIT_WIDTH - count));
+                mask |= (field << (2 * SUIT_WIDTH * suit)) | ((count as u128) << (COUNTS_SHIFT + 4 * suit));
+            }
+        }
+        mask
+    }
+
+    /// Cards of current position that are described by mask
+    fn masked_cards(&self, mask: u128) -> u64{
+        (0..4).fold(0u64, |acc, suit|{
+            let count = (mask >> (COUNTS_SHIFT + 4 * suit)) as u32 & 0xf;
+            acc | (top_cards(suit_bits(self.remaining, suit), count) << (suit * SUIT_WIDTH))
+        })
+    }
+
+    /// Returns cards worth considering for side on move, the most promising first.
+    fn moves(&self, seat: usize) -> ([u8; 13], usize){
+        let hand = self.hands[seat];
+        let candidates = match self.in_trick{
+            0 => hand,
+            _ => {
+                let led = card_suit(self.played[0]);
+                match suit_bits(hand, led){
+                    0 => hand,
+                    b => b << (led * SUIT_WIDTH)
+                }
+            }
+        };
+        let winning = match self.in_trick{
+            0 => None,
+            _ => {
+                let w = self.winning_position();
+                Some((self.played[w], (self.leader + w) & 3))
+            }
+        };
+        let mut moves = [0u8; 13];
+        let mut scores = [0i32; 13];
+        let mut n = 0;
+        for suit in 0..4{
+            let mine = suit_bits(candidates, suit);
+            if mine == 0{
+                continue;
+            }
+            let rest = suit_bits(self.remaining, suit);
+            let own = suit_bits(hand, suit);
+            let others = rest & !own;
+            let mut bits = mine;
+            while bits != 0{
+                let top = 63 - bits.leading_zeros() as usize;
+                bits ^= 1 << top;
+                //of cards with no other remaining card between them only the highest is tried
+                let above = rest >> (top + 1);
+                if above == 0 || (own >> (top + 1 + above.trailing_zeros() as usize)) & 1 == 0{
+                    let card = (top + suit * SUIT_WIDTH) as u8;
+                    let score = self.order_score(seat, card, others, winning);
+                    //insertion keeping descending scores
+                    let mut i = n;
+                    while i > 0 && scores[i - 1] < score{
+                        moves[i] = moves[i - 1];
+                        scores[i] = scores[i - 1];
+                        i -= 1;
+                    }
+                    moves[i] = card;
+                    scores[i] = score;
+                    n += 1;
+                }
+            }
+        }
+        (moves, n)
+    }
+
+    /// Checks if `card` (currently winning) stays winning after `opponent` plays to the trick.
+    fn survives(&self, card: u8, opponent: usize) -> bool{
+        let led = card_suit(self.played[0]);
+        let hand = self.hands[opponent];
+        let over = |bits: u64| bits >> (card as usize % SUIT_WIDTH + 1) == 0;
+        match (suit_bits(hand, led), self.trump){
+            (0, Some(trump)) if trump != led => match card_suit(card) == trump{
+                true => over(suit_bits(hand, trump)),
+                false => suit_bits(hand, trump) == 0
+            },
+            (0, _) => true,
+            (following, _) => card_suit(card) != led || over(following)
+        }
+    }
+
+    fn order_score(&self, seat: usize, card: u8, others_in_suit: u64, winning: Option<(u8, usize)>) -> i32{
+        let suit = card_suit(card);
+        let rank = (card as usize % SUIT_WIDTH) as i32;
+        let length = suit_bits(self.hands[seat], suit).count_ones() as i32;
+        let ruffs = |hand: u64| match self.trump{
+            Some(t) if t != suit => suit_bits(hand, suit) == 0 && suit_bits(hand, t) != 0,
+            _ => false
+        };
+        match winning{
+            None => {
+                let is_top = others_in_suit >> rank == 0;
+                let partner = self.hands[(seat + 2) & 3];
+                let partner_in_suit = suit_bits(partner, suit);
+                if ruffs(self.hands[(seat + 1) & 3]) || ruffs(self.hands[(seat + 3) & 3]){
+                    10 - rank
+                } else if is_top{
+                    150 + rank
+                } else if ruffs(partner){
+                    140 - rank
+                } else if partner_in_suit != 0 && partner_in_suit > others_in_suit & !partner_in_suit{
+                    120 - rank
+                } else {
+                    50 - rank - length
+                }
+            },
+            Some((winning_card, winning_seat)) => {
+                let follows = suit == card_suit(self.played[0]);
+                let last = self.in_trick == 3;
+                let next = (seat + 1) & 3;
+                let trumping = !follows && Some(suit) == self.trump;
+                if (winning_seat & 1) == (seat & 1){
+                    if last || self.survives(winning_card, next){
+                        match (follows, trumping){
+                            (true, _) => 100 - rank,
+                            (false, true) => 10 - rank,
+                            (false, false) => 90 - rank + length
+                        }
+                    } else if self.beats(card, winning_card) && self.survives(card, next){
+                        150 - rank
+                    } else if follows{
+                        100 - rank
+                    } else {
+                        90 - rank + length - 20 * i32::from(trumping)
+                    }
+                } else if self.beats(card, winning_card){
+                    if last || self.survives(card, next){
+                        200 - rank
+                    } else if self.in_trick == 2{
+                        110 - rank
+                    } else {
+                        95 - rank
+                    }
+                } else if follows{
+                    100 - rank
+                } else {
+                    90 - rank + length
+                }
+            }
+        }
+    }
+
+    fn play(&mut self, seat: usize, card: u8){
+        self.hands[seat] &= !(1u64 << card);
+        self.played[self.in_trick] = card;
+        self.in_trick += 1;
+    }
+
+    fn unplay(&mut self, seat: usize, card: u8){
+        self.in_trick -= 1;
+        self.hands[seat] |= 1u64 << card;
+    }
+
+    /// Checks if North-South is able to reach `target` tricks (counting already won in search).
+    /// Returns also cards whose ranks decided about the result.
+    fn reaches(&mut self, table: &mut TranspositionTable, target: u8) -> (bool, u64){
+        if self.in_trick == 4{
+            return self.close_trick(table, target);
+        }
+        let seat = (self.leader + self.in_trick) & 3;
+        let north_south = seat & 1 == 0;
+        let mut stored = None;
+        if self.in_trick == 0{
+            let left = self.hands[self.leader].count_ones() as u8;
+            if self.ns_won >= target{
+                return (true, 0);
+            }
+            if self.ns_won + left < target{
+                return (false, 0);
+            }
+            let (quick, quick_cards) = self.quick_tricks(seat);
+            if north_south && self.ns_won + quick >= target{
+                return (true, quick_cards);
+            }
+            if !north_south && self.ns_won + left - quick < target{
+                return (false, quick_cards);
+            }
+            let (sure, sure_cards) = self.sure_trump_tricks((seat + 1) & 3);
+            if north_south && self.ns_won + left - sure < target{
+                return (false, sure_cards);
+            }
+            if !north_south && self.ns_won + sure >= target{
+                return (true, sure_cards);
+            }
+            let key = self.lengths_key();
+            let owners = self.owners;
+            let need = target - self.ns_won;
+            if let Some((result, mask)) = table.probe(key, owners, need){
+                return (result, self.masked_cards(mask));
+            }
+            stored = Some((key, owners, need, left));
+        }
+        let (moves, n) = self.moves(seat);
+        let mut result = !north_south;
+        let mut relevant = 0u64;
+        for card in &moves[..n]{
+            self.play(seat, *card);
+            let (r, cards) = self.reaches(table, target);
+            self.unplay(seat, *card);
+            if r == north_south{
+                result = r;
+                relevant = cards;
+                break;
+            }
+            relevant |= cards;
+        }
+        if let Some((key, owners, need, left)) = stored{
+            let (lower, upper) = match result{
+                true => (need, left),
+                false => (0, need - 1)
+            };
+            table.store(key, owners, self.relevance(relevant), lower, upper);
+        }
+        (result, relevant)
+    }
+
+    fn close_trick(&mut self, table: &mut TranspositionTable, target: u8) -> (bool, u64){
+        let position = self.winning_position();
+        let winner = (self.leader + position) & 3;
+        let (leader, played) = (self.leader, self.played);
+        let trick_mask = played.iter().fold(0u64, |acc, c| acc | (1u64 << c));
+        self.remaining &= !trick_mask;
+        self.leader = winner;
+        self.in_trick = 0;
+        let won = u8::from(winner & 1 == 0);
+        self.ns_won += won;
+        let owners = self.owners;
+        self.update_owners(played.iter().fold(0u8, |acc, c| acc | (1 << card_suit(*c))));
+        let (result, mut relevant) = self.reaches(table, target);
+        self.owners = owners;
+        self.ns_won -= won;
+        self.in_trick = 4;
+        self.leader = leader;
+        self.played = played;
+        self.remaining |= trick_mask;
+        let winning_card = played[position];
+        //rank matters only when winning card beat another card of its suit
+        if played.iter().filter(|c| card_suit(**c) == card_suit(winning_card)).count() > 1{
+            relevant |= 1u64 << winning_card;
+        }
+        (result, relevant)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::card_set;
+    use karty::cards::*;
+    use karty::hand::{CardSet, HandTrait};
+    use karty::suits::Suit::{Hearts, Spades};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::bidding::Bid;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::{Contract, ContractMechanics, ContractParametersGen, Trick};
+    use crate::contract::double_dummy::{DoubleDummySolver, solve_contract, solve_deal, solve_table};
+    use crate::deal::fair_bridge_deal_with_rng;
+    use crate::error::{BridgeCoreErrorGen, DistributionError};
+    use crate::player::axis::Axis::{EastWest, NorthSouth};
+    use crate::player::side::Side::*;
+    use crate::player::side::SideMap;
+
+    fn test_deal() -> SideMap<CardSet>{
+        SideMap::new(
+            card_set![ACE_SPADES, KING_SPADES, QUEEN_SPADES, TWO_SPADES, ACE_HEARTS, THREE_HEARTS,
+                TWO_HEARTS, KING_DIAMONDS, SEVEN_DIAMONDS, FOUR_DIAMONDS, ACE_CLUBS, NINE_CLUBS, THREE_CLUBS],
+            card_set![JACK_SPADES, TEN_SPADES, NINE_SPADES, KING_HEARTS, QUEEN_HEARTS, JACK_HEARTS,
+                ACE_DIAMONDS, SIX_DIAMONDS, FIVE_DIAMONDS, KING_CLUBS, QUEEN_CLUBS, EIGHT_CLUBS, TWO_CLUBS],
+            card_set![EIGHT_SPADES, SEVEN_SPADES, SIX_SPADES, TEN_HEARTS, NINE_HEARTS, EIGHT_HEARTS,
+                QUEEN_DIAMONDS, JACK_DIAMONDS, TEN_DIAMONDS, JACK_CLUBS, TEN_CLUBS, SEVEN_CLUBS, SIX_CLUBS],
+            card_set![FIVE_SPADES, FOUR_SPADES, THREE_SPADES, SEVEN_HEARTS, SIX_HEARTS, FIVE_HEARTS,
+                FOUR_HEARTS, NINE_DIAMONDS, EIGHT_DIAMONDS, THREE_DIAMONDS, TWO_DIAMONDS, FIVE_CLUBS, FOUR_CLUBS],
+        )
+    }
+
+    #[test]
+    fn squeeze_ending_in_no_trump(){
+        //North cashes the last spade and East is squeezed in hearts and clubs
+        let hands = SideMap::new(
+            card_set![ACE_SPADES, ACE_HEARTS, TWO_HEARTS, THREE_DIAMONDS],
+            card_set![KING_HEARTS, QUEEN_HEARTS, KING_CLUBS, TWO_DIAMONDS],
+            card_set![THREE_HEARTS, ACE_CLUBS, TWO_CLUBS, FOUR_DIAMONDS],
+            card_set![TEN_SPADES, NINE_SPADES, EIGHT_SPADES, SEVEN_SPADES],
+        );
+        let mut solver = DoubleDummySolver::new(TrumpGen::NoTrump);
+        assert_eq!(solver.solve(&hands, &Trick::new(North), NorthSouth).unwrap(), 4);
+    }
+
+    #[test]
+    fn ruffing_in_trump_contract(){
+        let hands = SideMap::new(
+            card_set![TWO_HEARTS, THREE_HEARTS, FOUR_HEARTS],
+            card_set![ACE_SPADES, KING_SPADES, QUEEN_SPADES],
+            card_set![FIVE_HEARTS, SIX_HEARTS, SEVEN_HEARTS],
+            card_set![TWO_SPADES, THREE_SPADES, FOUR_SPADES],
+        );
+        let mut no_trump = DoubleDummySolver::new(TrumpGen::NoTrump);
+        assert_eq!(no_trump.solve(&hands, &Trick::new(East), EastWest).unwrap(), 3);
+        let mut hearts = DoubleDummySolver::new(TrumpGen::Colored(Hearts));
+        assert_eq!(hearts.solve(&hands, &Trick::new(East), EastWest).unwrap(), 0);
+        assert_eq!(hearts.solve(&hands, &Trick::new(North), NorthSouth).unwrap(), 3);
+    }
+
+    #[test]
+    fn solve_mid_trick(){
+        let hands = SideMap::new(
+            card_set![TWO_HEARTS],
+            card_set![ACE_HEARTS],
+            card_set![FOUR_SPADES, THREE_HEARTS],
+            card_set![SIX_SPADES, FIVE_HEARTS],
+        );
+        let mut trick = Trick::new(North);
+        trick.insert_card(North, QUEEN_SPADES).unwrap();
+        trick.insert_card(East, ACE_SPADES).unwrap();
+        let mut solver = DoubleDummySolver::new(TrumpGen::NoTrump);
+        assert_eq!(solver.solve(&hands, &trick, EastWest).unwrap(), 2);
+    }
+
+    #[test]
+    fn inconsistent_hands(){
+        let hands = SideMap::new(
+            card_set![KING_SPADES, TWO_HEARTS],
+            card_set![ACE_HEARTS],
+            card_set![FOUR_SPADES, THREE_HEARTS],
+            card_set![SIX_SPADES, FIVE_HEARTS],
+        );
+        let mut solver = DoubleDummySolver::new(TrumpGen::NoTrump);
+        assert_eq!(solver.solve(&hands, &Trick::new(North), NorthSouth),
+            Err(BridgeCoreErrorGen::Distribution(DistributionError::NotEqualCardNumbers(SideMap::new(2, 1, 2, 2)))));
+    }
+
+    #[test]
+    fn full_deal_and_mid_play(){
+        let deal = test_deal();
+        let parameters = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 4).unwrap());
+        let tricks = solve_deal(&deal, &parameters).unwrap();
+        assert_eq!(tricks, 9);
+        let mut contract = Contract::new(parameters);
+        assert_eq!(solve_contract(&deal, &contract).unwrap(), tricks);
+        contract.insert_card(East, KING_CLUBS).unwrap();
+        contract.insert_card(South, SIX_CLUBS).unwrap();
+        contract.insert_card(West, FOUR_CLUBS).unwrap();
+        assert!(solve_contract(&deal, &contract).unwrap() >= tricks);
+        contract.insert_card(North, ACE_CLUBS).unwrap();
+        assert_eq!(contract.count_completed_tricks(), 1);
+        assert!(solve_contract(&deal, &contract).unwrap() >= tricks);
+    }
+
+    #[test]
+    fn table_agrees_with_single_solutions(){
+        let deal = test_deal();
+        let table = solve_table(&deal).unwrap();
+        assert_eq!(table.tricks(North, &TrumpGen::Colored(Spades)), 9);
+        for (declarer, trump) in [(East, TrumpGen::Colored(Hearts)), (South, TrumpGen::NoTrump)]{
+            let parameters = ContractParametersGen::new(declarer, Bid::init(trump, 1).unwrap());
+            assert_eq!(table.tricks(declarer, &trump), solve_deal(&deal, &parameters).unwrap());
+        }
+    }
+
+    #[test]
+    fn random_deals(){
+        let mut rng = StdRng::seed_from_u64(4);
+        for expected in [[6, 6], [8, 4], [4, 3], [4, 8]]{
+            let deal = fair_bridge_deal_with_rng::<CardSet, _>(&mut rng);
+            for (trump, tricks) in [TrumpGen::NoTrump, TrumpGen::Colored(Spades)].into_iter().zip(expected){
+                let parameters = ContractParametersGen::new(North, Bid::init(trump, 1).unwrap());
+                assert_eq!(solve_deal(&deal, &parameters).unwrap(), tricks);
+            }
+        }
+    }
+
+    #[test]
+    fn card_not_in_hand(){
+        let deal = test_deal();
+        let mut contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+        contract.insert_card(East, ACE_SPADES).unwrap();
+        assert!(solve_contract(&deal, &contract).is_err());
+        assert_eq!(deal.north.len(), 13);
+    }
+}
@@ -1,9 +1,11 @@
 use karty::cards::{Card2SymTrait};
 use crate::contract::trick::{TrickGen};
+use crate::contract::Claim;
 use crate::player::side::Side;
 use crate::player::axis::Axis;
 use crate::contract::spec::ContractParametersGen;
 use crate::error::ContractErrorGen;
+use crate::meta::HAND_SIZE;
 
 
 pub trait ContractMechanics {
@@ -28,7 +30,26 @@ pub trait ContractMechanics {
     fn dummy(&self) -> Side{
         self.contract_spec().declarer().partner()
     }
+    /// Number of cards still held by side
+    fn cards_left(&self, side: Side) -> usize{
+        HAND_SIZE - self.count_completed_tricks() - usize::from(self.current_trick()[side].is_some())
+    }
     fn undo(&mut self) -> Result<Self::Card, ContractErrorGen<Self::Card>>;
+    /// Claim closing the contract, if it was accepted
+    fn accepted_claim(&self) -> Option<&Claim>{
+        None
+    }
+    /// Closes contract with claim without checking it, remaining tricks are assigned by claim
+    /// to claiming axis and conceded ones to its opponents.
+    /// Per side counts of tricks after claim are not meaningful, use counts for axis.
+    /// By default claims are not supported and every claim is rejected.
+    fn accept_claim(&mut self, claim: Claim) -> Result<(), ContractErrorGen<Self::Card>>{
+        Err(ContractErrorGen::ClaimRejected(claim.side(), claim.tricks()))
+    }
+    /// Reopens contract closed with claim
+    fn withdraw_claim(&mut self) -> Option<Claim>{
+        None
+    }
 
 
 }
@@ -9,11 +9,18 @@ pub use spec::*;
 mod registering_contract;
 mod trick_solver;
 mod randomizer;
+mod claim;
+pub mod double_dummy;
+pub mod post_mortem;
+#[cfg(feature = "amfiteatr")]
+pub mod single_dummy;
+pub mod suit_combination;
 
 pub use trick_solver::*;
 
 pub use registering_contract::*;
 pub use randomizer::*;
+pub use claim::*;
 
 
 
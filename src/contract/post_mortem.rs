@@ -0,0 +1,163 @@
+//! Post-mortem analysis of play: every card of contract compared with double dummy optimum.
+use karty::cards::Card;
+use karty::hand::CardSet;
+use crate::contract::{Contract, ContractMechanics};
+use crate::contract::double_dummy::{remaining_hands, DoubleDummySolver};
+use crate::error::BridgeCoreError;
+use crate::player::side::{Side, SideMap};
+
+/// Double dummy value of position before and after one card, counted as total tricks of declarer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardAnalysis{
+    side: Side,
+    card: Card,
+    trick_number: usize,
+    tricks_before: u8,
+    tricks_after: u8
+}
+
+impl CardAnalysis{
+    pub fn side(&self) -> Side{
+        self.side
+    }
+    pub fn card(&self) -> Card{
+        self.card
+    }
+    /// Number of trick the card was played to, counted from 0
+    pub fn trick_number(&self) -> usize{
+        self.trick_number
+    }
+    /// Tricks declarer takes with perfect play before the card
+    pub fn tricks_before(&self) -> u8{
+        self.tricks_before
+    }
+    /// Tricks declarer takes with perfect play after the card
+    pub fn tricks_after(&self) -> u8{
+        self.tricks_after
+    }
+    /// Tricks lost by side of player with this card
+    pub fn cost(&self, declarer: Side) -> u8{
+        match self.side.axis() == declarer.axis(){
+            true => self.tricks_before.saturating_sub(self.tricks_after),
+            false => self.tricks_after.saturating_sub(self.tricks_before)
+        }
+    }
+}
+
+/// Card by card report of played contract.
+/// # Example:
+/// ```
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::{Contract, ContractMechanics, ContractParametersGen};
+/// use brydz_core::contract::post_mortem::PostMortem;
+/// use brydz_core::player::side::Side::*;
+/// use brydz_core::player::side::SideMap;
+/// use karty::cards::*;
+/// use karty::hand::{HAND_OF_CLUBS, HAND_OF_DIAMONDS, HAND_OF_HEARTS, HAND_OF_SPADES};
+/// use karty::suits::Suit::Hearts;
+/// let deal = SideMap::new(HAND_OF_SPADES, HAND_OF_HEARTS, HAND_OF_DIAMONDS, HAND_OF_CLUBS);
+/// let mut contract = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Hearts), 2).unwrap()));
+/// contract.insert_card(West, ACE_CLUBS).unwrap();
+/// contract.insert_card(North, TWO_SPADES).unwrap();
+/// let report = PostMortem::analyse(&deal, &contract).unwrap();
+/// assert_eq!(report.cards().len(), 2);
+/// assert_eq!(report.cards()[1].card(), TWO_SPADES);
+/// assert_eq!(report.mistakes().count(), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostMortem{
+    declarer: Side,
+    cards: Vec<CardAnalysis>
+}
+
+impl PostMortem{
+    /// Replays `contract` (finished, claimed or in progress) from the beginning against original `deal`
+    /// and solves double dummy position before and after every card.
+    pub fn analyse(deal: &SideMap<CardSet>, contract: &Contract) -> Result<Self, BridgeCoreError>{
+        let declarer = contract.declarer();
+        let mut replay = contract.clone();
+        replay.withdraw_claim();
+        let mut played = Vec::with_capacity(52);
+        while replay.count_completed_tricks() > 0 || !replay.current_trick().is_empty(){
+            let card = replay.undo()?;
+            played.push((replay.current_side(), card));
+        }
+        played.reverse();
+
+        let mut solver = DoubleDummySolver::new(*contract.contract_spec().bid().trump());
+        let mut value = |replay: &Contract| -> Result<u8, BridgeCoreError>{
+            let taken = replay.total_tricks_taken_axis(declarer.axis()) as u8;
+            match replay.is_completed(){
+                true => Ok(taken),
+                false => Ok(taken + solver.solve(&remaining_hands(deal, replay)?, replay.current_trick(), declarer.axis())?)
+            }
+        };
+        let mut tricks_before = value(&replay)?;
+        let mut cards = Vec::with_capacity(played.len());
+        for (side, card) in played{
+            let trick_number = replay.count_completed_tricks();
+            replay.insert_card(side, card)?;
+            let tricks_after = value(&replay)?;
+            cards.push(CardAnalysis{side, card, trick_number, tricks_before, tricks_after});
+            tricks_before = tricks_after;
+        }
+        Ok(Self{declarer, cards})
+    }
+    pub fn declarer(&self) -> Side{
+        self.declarer
+    }
+    pub fn cards(&self) -> &[CardAnalysis]{
+        &self.cards
+    }
+    /// Cards that cost their side at least one trick
+    pub fn mistakes(&self) -> impl Iterator<Item = &CardAnalysis>{
+        self.cards.iter().filter(|c| c.cost(self.declarer) > 0)
+    }
+    /// Tricks lost by mistakes of side and its partner
+    pub fn tricks_lost(&self, side: Side) -> u8{
+        self.mistakes().filter(|c| c.side.axis() == side.axis()).map(|c| c.cost(self.declarer)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::cards::Card2SymTrait;
+    use karty::hand::HandSuitedTrait;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::bidding::Bid;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::{Contract, ContractMechanics, ContractParametersGen};
+    use crate::contract::double_dummy::{remaining_hands, solve_deal};
+    use crate::contract::post_mortem::PostMortem;
+    use crate::deal::distribute_standard_deck_on_4;
+    use crate::player::axis::Axis;
+    use crate::player::side::Side::{East, South};
+
+    #[test]
+    fn costs_add_up_to_result(){
+        let deal = distribute_standard_deck_on_4(&mut StdRng::seed_from_u64(19));
+        let parameters = ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 3).unwrap());
+        let mut contract = Contract::new(parameters.clone());
+        // everyone plays the lowest legal card
+        while !contract.is_completed(){
+            let side = contract.current_side();
+            let hand = remaining_hands(&deal, &contract).unwrap()[&side];
+            let card = contract.current_trick().called_suit()
+                .and_then(|suit| hand.suit_iterator(&suit).min_by_key(|c| c.figure()))
+                .or_else(|| hand.into_iter().min_by_key(|c| c.figure()))
+                .unwrap();
+            contract.insert_card(side, card).unwrap();
+        }
+        let report = PostMortem::analyse(&deal, &contract).unwrap();
+        assert_eq!(report.cards().len(), 52);
+        assert_eq!(report.cards()[0].tricks_before(), solve_deal(&deal, &parameters).unwrap());
+        assert!(report.cards().windows(2).all(|w| w[0].tricks_after() == w[1].tricks_before()));
+        assert_eq!(report.cards()[51].tricks_after() as u32, contract.total_tricks_taken_axis(Axis::NorthSouth));
+        let start = report.cards()[0].tricks_before() as i32;
+        let end = report.cards()[51].tricks_after() as i32;
+        assert_eq!(end, start - report.tricks_lost(South) as i32 + report.tricks_lost(East) as i32);
+        assert!(report.mistakes().all(|c| c.cost(South) > 0));
+    }
+}
@@ -10,6 +10,14 @@ use crate::cards::trump::Trump;
 use crate::contract::ContractParameters;
 use crate::player::side::{Side, SIDES};
 
+/// Distribution of contract parameters, sampled with caller-provided random generator
+/// ```
+/// use brydz_core::contract::ContractRandomizer;
+/// use brydz_core::deal::board_rng;
+/// use rand::distributions::Distribution;
+/// let randomizer = ContractRandomizer::default();
+/// assert_eq!(randomizer.sample(&mut board_rng(11, 1)), randomizer.sample(&mut board_rng(11, 1)));
+/// ```
 pub struct ContractRandomizer{
     //min_contract: u8,
     //max_contract: u8,
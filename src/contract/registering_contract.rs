@@ -8,7 +8,7 @@ use crate::cards::trump::TrumpGen;
 use crate::contract::suit_exhaust::{SuitExhaust};
 use crate::contract::spec::ContractParametersGen;
 use crate::contract::maintainer::ContractMechanics;
-use crate::contract::{SmartTrickSolver, TrickGen, TrickSolver};
+use crate::contract::{Claim, SmartTrickSolver, TrickGen, TrickSolver};
 use crate::error::{ContractErrorGen, TrickErrorGen};
 use crate::error::ContractErrorGen::IndexedOverCurrentTrick;
 use crate::error::TrickErrorGen::MissingCard;
@@ -24,7 +24,8 @@ pub struct ContractGen<Crd: Card2SymTrait, Um: Register<Crd>, Se:Register<(Side,
     exhaust_table: Se,
     current_trick: TrickGen<Crd>,
     used_cards_memory: Um,
-    solver: SmartTrickSolver<Crd>
+    solver: SmartTrickSolver<Crd>,
+    claim: Option<Claim>
 
 }
 
@@ -89,6 +90,9 @@ impl<Crd: Card2SymTrait,
     ///
     /// ```
     fn insert_card(&mut self, side: Side, card: Crd) -> Result<Side, ContractErrorGen<Crd>>{
+        if self.claim.is_some(){
+            return Err(ContractErrorGen::ContractClaimed);
+        }
         if self.completed_tricks_number >= QUARTER_SIZE{
             return Err(ContractErrorGen::ContractFull);
         }
@@ -131,6 +135,9 @@ impl<Crd: Card2SymTrait,
     }
     
     fn is_completed(&self) -> bool{
+        if self.claim.is_some(){
+            return true;
+        }
         match self.completed_tricks_number {
             n if n < QUARTER_SIZE => false,
             QUARTER_SIZE => true,
@@ -145,7 +152,10 @@ impl<Crd: Card2SymTrait,
         }
         r
     }
-    /// Counts tricks taken by `Side` (one agent)
+    /// Counts tricks taken by `Side` (one agent).
+    /// Tricks assigned by claim are not won by particular player, here claimed tricks are counted for claimer
+    /// and conceded ones for side next to him, so after claim only sums for axis are meaningful
+    /// (see [`total_tricks_taken_axis`](ContractMechanics::total_tricks_taken_axis)).
     /// # Examples:
     /// ```
     /// use brydz_core::contract::TrickGen;
@@ -186,6 +196,7 @@ impl<Crd: Card2SymTrait,
     fn total_tricks_taken_side(&self, side: Side) -> u32{
         self.tricks[0..self.completed_tricks_number].iter()
             .filter(|t| self.solver.winner(t).unwrap() == side).count() as u32
+            + self.claimed_tricks(|claimer| claimer == side, |claimer| claimer.next() == side)
     }
 
     fn tricks_taken_side_in_n_first_tricks(&self, side: Side, n: usize) -> u32{
@@ -245,6 +256,7 @@ impl<Crd: Card2SymTrait,
     fn total_tricks_taken_axis(&self, axis: Axis) -> u32{
         self.tricks[0..self.completed_tricks_number].iter()
             .filter(|t| self.solver.winner(t).unwrap().axis() == axis).count() as u32
+            + self.claimed_tricks(|claimer| claimer.axis() == axis, |claimer| claimer.axis() != axis)
             //.filter(|t| t.taker(&self.solver).unwrap().axis() == axis).count()
 
     }
@@ -285,6 +297,9 @@ impl<Crd: Card2SymTrait,
     /// contract.insert_card(South, ACE_SPADES).unwrap();
     /// ```
     fn undo(&mut self) -> Result<Self::Card, ContractErrorGen<Self::Card>>{
+        if self.claim.is_some(){
+            return Err(ContractErrorGen::ContractClaimed);
+        }
         match self.current_trick.is_empty(){
             true => {
                 match self.completed_tricks_number{
@@ -318,9 +333,66 @@ impl<Crd: Card2SymTrait,
             }
         }
     }
+
+    fn accepted_claim(&self) -> Option<&Claim>{
+        self.claim.as_ref()
+    }
+
+    /// ```
+    /// use brydz_core::bidding::Bid;
+    /// use brydz_core::cards::trump::TrumpGen;
+    /// use brydz_core::contract::{Claim, Contract, ContractMechanics, ContractParametersGen};
+    /// use brydz_core::error::ContractErrorGen;
+    /// use brydz_core::player::side::Side::{East, North, South, West};
+    /// use karty::cards::{JACK_SPADES, TEN_SPADES};
+    /// use karty::suits::Suit::Diamonds;
+    /// let mut contract = Contract::new(ContractParametersGen::new(West, Bid::init(TrumpGen::Colored(Diamonds), 1).unwrap(),));
+    /// contract.insert_card(North, JACK_SPADES).unwrap();
+    /// contract.insert_card(East, TEN_SPADES).unwrap();
+    /// assert_eq!(contract.accept_claim(Claim::new(West, 14)), Err(ContractErrorGen::ClaimRejected(West, 14)));
+    /// contract.accept_claim(Claim::new(West, 7)).unwrap();
+    /// assert!(contract.is_completed());
+    /// assert_eq!(contract.total_tricks_taken_side(West), 7);
+    /// assert_eq!(contract.total_tricks_taken_side(North), 6);
+    /// assert_eq!(contract.accept_claim(Claim::new(South, 7)), Err(ContractErrorGen::ContractClaimed));
+    /// ```
+    fn accept_claim(&mut self, claim: Claim) -> Result<(), ContractErrorGen<Self::Card>>{
+        if self.claim.is_some(){
+            return Err(ContractErrorGen::ContractClaimed);
+        }
+        if self.completed_tricks_number >= QUARTER_SIZE{
+            return Err(ContractErrorGen::ContractFull);
+        }
+        if claim.tricks() > self.remaining_tricks(){
+            return Err(ContractErrorGen::ClaimRejected(claim.side(), claim.tricks()));
+        }
+        self.claim = Some(claim);
+        Ok(())
+    }
+
+    fn withdraw_claim(&mut self) -> Option<Claim>{
+        self.claim.take()
+    }
 }
 
 impl<Card: Card2SymTrait, Um: Register<Card>, Se: Register<(Side, Card::Suit)>> ContractGen<Card, Um, Se>{
+    /// Tricks that are not yet completed (current trick included)
+    pub fn remaining_tricks(&self) -> u8{
+        (QUARTER_SIZE - self.completed_tricks_number) as u8
+    }
+
+    /// Tricks assigned by claim: claimed ones when `to_claimer` holds and the rest when `to_opponent` holds
+    fn claimed_tricks<C: Fn(Side) -> bool, O: Fn(Side) -> bool>(&self, to_claimer: C, to_opponent: O) -> u32{
+        self.claim.map_or(0, |claim|{
+            let conceded = self.remaining_tricks() - claim.tricks();
+            match (to_claimer(claim.side()), to_opponent(claim.side())){
+                (true, _) => claim.tricks() as u32,
+                (false, true) => conceded as u32,
+                (false, false) => 0
+            }
+        })
+    }
+
     pub fn new(contract_parameters: ContractParametersGen<Card::Suit>) -> Self{
         let first_player = contract_parameters.declarer().next();
         let trump = contract_parameters.bid().trump().to_owned();
@@ -329,7 +401,7 @@ impl<Card: Card2SymTrait, Um: Register<Card>, Se: Register<(Side, Card::Suit)>>
         Self{
             contract_spec: contract_parameters, tricks, completed_tricks_number: 0,
             exhaust_table: Se::default(), current_trick: TrickGen::new(first_player), used_cards_memory: Um::default(),
-            solver: SmartTrickSolver::new(trump)}
+            solver: SmartTrickSolver::new(trump), claim: None}
     }
 
     pub fn card_used(&self) -> &Um{
@@ -556,10 +628,10 @@ mod tests{
                 West, Bid::init(TrumpGen::NoTrump, 1).unwrap(), ));
         for i in 0..QUARTER_SIZE{
 
-            deal.insert_card(Side::North,deck[num_of_sides*i].clone()).unwrap();
-            deal.insert_card(Side::East,deck[num_of_sides*i + 1].clone()).unwrap();
-            deal.insert_card(Side::South,deck[num_of_sides*i + 2].clone()).unwrap();
-            deal.insert_card(Side::West,deck[num_of_sides*i +3].clone()).unwrap();
+            deal.insert_card(Side::North,deck[num_of_sides*i]).unwrap();
+            deal.insert_card(Side::East,deck[num_of_sides*i + 1]).unwrap();
+            deal.insert_card(Side::South,deck[num_of_sides*i + 2]).unwrap();
+            deal.insert_card(Side::West,deck[num_of_sides*i +3]).unwrap();
 
         }
 
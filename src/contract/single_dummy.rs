@@ -0,0 +1,273 @@
+//! Single dummy analysis: play decisions evaluated over hidden hands sampled from distribution.
+use rand::Rng;
+use rand::seq::SliceRandom;
+use karty::cards::{Card, Card2SymTrait, STANDARD_DECK};
+use karty::hand::{CardSet, HandSuitedTrait, HandTrait};
+use karty::register::Register;
+use crate::contract::{Contract, ContractMechanics};
+use crate::contract::double_dummy::DoubleDummySolver;
+use crate::deal::{BiasedHandDistribution, DealConstraints, UniformDealSampler};
+use crate::error::{BridgeCoreError, ContractErrorGen, DistributionError};
+use crate::player::side::{Side, SideMap, SIDES};
+
+/// Result of single dummy analysis of one card, tricks are counted for declarer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CardEvaluation{
+    card: Card,
+    expected_tricks: f64,
+    making_probability: f64
+}
+
+impl CardEvaluation{
+    pub fn card(&self) -> Card{
+        self.card
+    }
+    /// Mean number of tricks declarer takes in total (including already taken) after playing card
+    pub fn expected_tricks(&self) -> f64{
+        self.expected_tricks
+    }
+    /// Fraction of sampled layouts in which contract is made after playing card
+    pub fn making_probability(&self) -> f64{
+        self.making_probability
+    }
+}
+
+/// Single dummy analyser: view of one player (own hand, dummy when it is already shown and contract played so far).
+/// Hidden hands are sampled from [`BiasedHandDistribution`] and made consistent with the view,
+/// then every legal card is evaluated double dummy in each layout.
+/// # Example:
+/// ```
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::{Contract, ContractParametersGen};
+/// use brydz_core::contract::single_dummy::SingleDummyAnalyser;
+/// use brydz_core::deal::BiasedHandDistribution;
+/// use brydz_core::player::side::Side::{South, West};
+/// use karty::hand::HAND_OF_SPADES;
+/// let contract = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 1).unwrap()));
+/// // West holds every spade and leads against 1NT
+/// let analyser = SingleDummyAnalyser::new(West, HAND_OF_SPADES, None, contract).with_samples(4);
+/// let evaluations = analyser.analyse(&BiasedHandDistribution::default(), &mut rand::thread_rng()).unwrap();
+/// assert_eq!(evaluations.len(), 13);
+/// assert!(evaluations.iter().all(|e| e.expected_tricks() == 0.0 && e.making_probability() == 0.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SingleDummyAnalyser{
+    side: Side,
+    hand: CardSet,
+    dummy_hand: Option<CardSet>,
+    contract: Contract,
+    samples: usize,
+    max_attempts: usize,
+}
+
+impl SingleDummyAnalyser{
+    /// `hand` and `dummy_hand` are cards still held (not yet played), dummy is `None` before it is shown.
+    pub fn new(side: Side, hand: CardSet, dummy_hand: Option<CardSet>, contract: Contract) -> Self{
+        Self{side, hand, dummy_hand, contract, samples: 100, max_attempts: 1000}
+    }
+    /// Number of layouts to analyse (default 100)
+    pub fn with_samples(mut self, samples: usize) -> Self{
+        self.samples = samples;
+        self
+    }
+    /// Limit of sampling tries, failed tries (layout not consistent with view) count as well (default 1000)
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self{
+        self.max_attempts = max_attempts;
+        self
+    }
+    pub fn contract(&self) -> &Contract{
+        &self.contract
+    }
+
+    fn known_hand(&self, side: Side) -> Option<CardSet>{
+        if side == self.side{
+            Some(self.hand)
+        } else if side == self.contract.dummy(){
+            self.dummy_hand
+        } else {
+            None
+        }
+    }
+
+    /// Samples cards still held by every side. Distribution places cards in hidden hands when it is consistent with play
+    /// (number of cards and suits that side showed out of), other unknown cards are placed at random.
+    /// Returns `None` when distribution fails to sample or no layout is consistent with play.
+    pub fn sample_layout<R: Rng + ?Sized>(&self, distribution: &BiasedHandDistribution, rng: &mut R) -> Option<SideMap<CardSet>>{
+        let sample = distribution.sample_deal_crossing(rng).ok()?;
+        let mut layout = SideMap::new_symmetric(CardSet::empty());
+        let mut known = CardSet::empty();
+        let mut hidden = Vec::with_capacity(3);
+        for side in SIDES{
+            match self.known_hand(side){
+                Some(hand) => {
+                    layout[&side] = hand;
+                    known = known.union(&hand);
+                },
+                None => hidden.push(side)
+            }
+        }
+        let mut unknown: Vec<Card> = STANDARD_DECK.iter()
+            .filter(|c| !known.contains(c) && !self.contract.used_cards().is_registered(c))
+            .copied().collect();
+        unknown.shuffle(rng);
+        let mut capacity: Vec<usize> = hidden.iter().map(|s| self.contract.cards_left(*s)).collect();
+        if capacity.iter().sum::<usize>() != unknown.len() || !self.is_feasible(&unknown, &hidden, &capacity){
+            return None
+        }
+        for i in 0..unknown.len(){
+            let card = unknown[i];
+            let mut candidates: Vec<usize> = (0..hidden.len())
+                .filter(|h| capacity[*h] > 0 && self.contract.side_possibly_has_card(hidden[*h], &card))
+                .collect();
+            candidates.shuffle(rng);
+            //side chosen by distribution is checked first
+            candidates.sort_by_key(|h| !sample[&hidden[*h]].contains(&card));
+            let chosen = candidates.into_iter().find(|h|{
+                capacity[*h] -= 1;
+                let feasible = self.is_feasible(&unknown[i+1..], &hidden, &capacity);
+                capacity[*h] += 1;
+                feasible
+            })?;
+            capacity[chosen] -= 1;
+            layout[&hidden[chosen]].insert_card(card).ok()?;
+        }
+        Some(layout)
+    }
+
+    /// Checks if `cards` can be dealt to `hidden` sides with given free places (Hall's condition
+    /// for every subset of sides).
+    fn is_feasible(&self, cards: &[Card], hidden: &[Side], capacity: &[usize]) -> bool{
+        let masks: Vec<usize> = cards.iter().map(|c| (0..hidden.len())
+            .filter(|h| self.contract.side_possibly_has_card(hidden[*h], c))
+            .fold(0, |acc, h| acc | (1 << h))).collect();
+        (1..(1usize << hidden.len())).all(|subset|{
+            let free: usize = (0..hidden.len()).filter(|h| subset & (1 << h) != 0).map(|h| capacity[h]).sum();
+            masks.iter().filter(|m| **m & !subset == 0).count() <= free
+        })
+    }
+
+    /// Cards that side to play may play according to view
+    fn legal_cards(&self) -> Result<Vec<Card>, BridgeCoreError>{
+        let current = self.contract.current_side();
+        let hand = self.known_hand(current).filter(|_| current == self.side || self.side == self.contract.declarer())
+            .ok_or(ContractErrorGen::CurrentSidePresume(current, self.side))?;
+        Ok(match self.contract.current_trick().called_suit(){
+            Some(suit) if hand.contains_in_suit(&suit) => hand.into_iter().filter(|c| c.suit() == suit).collect(),
+            _ => hand.into_iter().collect()
+        })
+    }
+
+    /// Evaluates every legal card of side to play (own card or dummy's card when view is declarer's).
+    pub fn analyse<R: Rng + ?Sized>(&self, distribution: &BiasedHandDistribution, rng: &mut R) -> Result<Vec<CardEvaluation>, BridgeCoreError>{
+        self.evaluate(|| self.sample_layout(distribution, rng))
+    }
+
+    /// Evaluates every legal card of side to play, hidden hands are drawn uniformly from all layouts
+    /// consistent with view (see [`DealConstraints`]).
+    pub fn analyse_uniform<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Vec<CardEvaluation>, BridgeCoreError>{
+        let known = SideMap::new_with_fn(|side| self.known_hand(side).unwrap_or(CardSet::empty()));
+        let constraints = DealConstraints::from_contract(&self.contract, &known)?;
+        let sampler = UniformDealSampler::new(&constraints);
+        if sampler.count_layouts() == 0{
+            return Err(DistributionError::NoConsistentLayout.into())
+        }
+        self.evaluate(|| sampler.sample(rng).ok())
+    }
+
+    fn evaluate<F: FnMut() -> Option<SideMap<CardSet>>>(&self, mut next_layout: F) -> Result<Vec<CardEvaluation>, BridgeCoreError>{
+        let cards = self.legal_cards()?;
+        let current = self.contract.current_side();
+        let axis = self.contract.declarer().axis();
+        let required = self.contract.contract_spec().bid().number_normalised();
+        let mut solver = DoubleDummySolver::new(*self.contract.contract_spec().bid().trump());
+        let mut tricks_sum = vec![0u64; cards.len()];
+        let mut made = vec![0u64; cards.len()];
+        let mut analysed = 0usize;
+        let mut attempts = 0usize;
+        while analysed < self.samples{
+            if attempts >= self.max_attempts{
+                return Err(DistributionError::NoConsistentLayout.into())
+            }
+            attempts += 1;
+            let layout = match next_layout(){
+                Some(l) => l,
+                None => continue
+            };
+            for (i, card) in cards.iter().enumerate(){
+                let mut contract = self.contract.clone();
+                contract.insert_card(current, *card)?;
+                let mut hands = layout;
+                hands[&current].remove_card(card)?;
+                let taken = contract.total_tricks_taken_axis(axis) as u8;
+                let tricks = match contract.is_completed(){
+                    true => taken,
+                    false => taken + solver.solve(&hands, contract.current_trick(), axis)?
+                };
+                tricks_sum[i] += u64::from(tricks);
+                made[i] += u64::from(tricks >= required);
+            }
+            analysed += 1;
+        }
+        let n = analysed.max(1) as f64;
+        Ok(cards.into_iter().enumerate().map(|(i, card)| CardEvaluation{
+            card,
+            expected_tricks: tricks_sum[i] as f64 / n,
+            making_probability: made[i] as f64 / n
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::cards::{Card2SymTrait, FOUR_DIAMONDS, THREE_CLUBS, THREE_DIAMONDS, THREE_SPADES, TWO_CLUBS, TWO_HEARTS, TWO_SPADES};
+    use karty::hand::{HandTrait, HAND_OF_HEARTS, HAND_OF_SPADES};
+    use karty::suits::Suit::{Clubs, Diamonds, Hearts};
+    use crate::bidding::Bid;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::{Contract, ContractMechanics, ContractParametersGen};
+    use crate::contract::single_dummy::SingleDummyAnalyser;
+    use crate::deal::BiasedHandDistribution;
+    use crate::player::side::Side::{East, North, South, West};
+
+    #[test]
+    fn layouts_respect_shown_out_suits(){
+        let mut contract = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 1).unwrap()));
+        contract.insert_card(West, TWO_CLUBS).unwrap();
+        contract.insert_card(North, TWO_SPADES).unwrap();
+        contract.insert_card(East, THREE_DIAMONDS).unwrap();
+        contract.insert_card(South, TWO_HEARTS).unwrap();
+        let mut hand = HAND_OF_HEARTS;
+        hand.remove_card(&TWO_HEARTS).unwrap();
+        let mut dummy = HAND_OF_SPADES;
+        dummy.remove_card(&TWO_SPADES).unwrap();
+        let analyser = SingleDummyAnalyser::new(South, hand, Some(dummy), contract.clone());
+        let mut rng = rand::thread_rng();
+        let layout = analyser.sample_layout(&BiasedHandDistribution::default(), &mut rng).unwrap();
+        // East showed out of clubs, so West has the rest of them
+        assert!(layout[&East].into_iter().all(|c| c.suit() == Diamonds));
+        assert!(layout[&West].into_iter().all(|c| c.suit() == Clubs));
+        assert_eq!(layout[&West].len(), 12);
+
+        contract.insert_card(West, THREE_CLUBS).unwrap();
+        contract.insert_card(North, THREE_SPADES).unwrap();
+        contract.insert_card(East, FOUR_DIAMONDS).unwrap();
+        dummy.remove_card(&THREE_SPADES).unwrap();
+        let analyser = SingleDummyAnalyser::new(South, hand, Some(dummy), contract).with_samples(3);
+        let evaluations = analyser.analyse(&BiasedHandDistribution::default(), &mut rng).unwrap();
+        assert_eq!(evaluations.len(), 12);
+        assert!(evaluations.iter().all(|e| e.card().suit() == Hearts && e.expected_tricks() == 0.0));
+        assert_eq!(analyser.analyse_uniform(&mut rng).unwrap(), evaluations);
+    }
+
+    #[test]
+    fn defender_does_not_choose_dummy_card(){
+        let mut contract = Contract::new(ContractParametersGen::new(South, Bid::init(TrumpGen::NoTrump, 1).unwrap()));
+        contract.insert_card(West, TWO_CLUBS).unwrap();
+        let mut dummy = HAND_OF_SPADES;
+        dummy.remove_card(&TWO_SPADES).unwrap();
+        let analyser = SingleDummyAnalyser::new(East, HAND_OF_HEARTS, Some(HAND_OF_SPADES), contract);
+        assert!(analyser.analyse(&BiasedHandDistribution::default(), &mut rand::thread_rng()).is_err());
+        assert_eq!(analyser.contract().current_side(), North);
+    }
+}
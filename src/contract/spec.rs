@@ -165,7 +165,7 @@ mod serde_for_contract_spec{
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "serde_dedicate"))]
 mod tests{
     use karty::suits::Suit;
     use karty::suits::Suit::Diamonds;
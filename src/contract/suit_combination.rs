@@ -0,0 +1,458 @@
+//! Suit combination analysis: lines of play in single suit evaluated over every layout of defenders' cards.
+//!
+//! Declarer holds cards in South and dummy in North. Declarer is assumed to have unlimited entries,
+//! so every trick is led by declarer's side, and ruffs and discards are ignored. A line is a sequence
+//! of [`LinePlay`]s, after it is exhausted declarer cashes from the top. Defenders see all cards
+//! and play to minimise declarer's tricks. Layouts are weighted with a priori probability of the split
+//! (hypergeometric distribution of missing cards between two hands of 13 cards).
+//! ```
+//! use brydz_core::contract::suit_combination::SuitCombinationAnalyser;
+//! use karty::card_set;
+//! use karty::cards::*;
+//! use karty::suits::Suit::Spades;
+//! let north = card_set![ACE_SPADES, QUEEN_SPADES];
+//! let south = card_set![FIVE_SPADES, THREE_SPADES];
+//! let analyser = SuitCombinationAnalyser::new(Spades, north, south).unwrap();
+//! let best = &analyser.analyse(2)[0];
+//! // finesse of the queen: lead low from South, cover the card of West
+//! assert_eq!(best.line().to_string(), "S low (N cover), S low (N cover)");
+//! assert!((best.success_probability() - 0.5).abs() < 1e-9);
+//! ```
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use karty::cards::{Card, Card2SymTrait};
+use karty::figures::Figure;
+use karty::hand::{CardSet, HandSuitedTrait, HandTrait};
+use karty::suits::{Suit, SUITS};
+use karty::symbol::CardSymbol;
+use crate::contract::{NoTrumpTrickSolver, TrickGen, TrickSolver};
+use crate::error::DistributionError;
+use crate::meta::HAND_SIZE;
+use crate::player::side::{Side, SideMap};
+use crate::player::side::Side::{North, South};
+
+/// Holding in single suit, bit `i` is set when card of figure with index `i` is held
+type Holding = u16;
+
+/// Card led by declarer's hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeadCard{
+    Highest,
+    Lowest
+}
+
+/// Card played by the other hand of declarer's side after one defender has played
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Response{
+    /// The cheapest card beating opponent's card, the lowest card when partner's card wins or nothing beats
+    Cover,
+    /// The lowest card
+    Duck,
+    /// The highest card
+    Rise
+}
+
+/// Single trick of line. When leading hand has no cards in suit, the lead is made from the other hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LinePlay{
+    /// North or South (other sides are treated as South)
+    pub leader: Side,
+    pub lead: LeadCard,
+    pub response: Response
+}
+
+impl LinePlay{
+    pub fn new(leader: Side, lead: LeadCard, response: Response) -> Self{
+        Self{leader, lead, response}
+    }
+}
+
+impl Display for LinePlay{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (leader, other) = match self.leader{
+            North => ('N', 'S'),
+            _ => ('S', 'N')
+        };
+        let lead = match self.lead{
+            LeadCard::Highest => "high",
+            LeadCard::Lowest => "low"
+        };
+        let response = match self.response{
+            Response::Cover => "cover",
+            Response::Duck => "duck",
+            Response::Rise => "rise"
+        };
+        write!(f, "{leader} {lead} ({other} {response})")
+    }
+}
+
+/// Line of play: leads of first tricks, then declarer cashes from the top
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Line{
+    plays: Vec<LinePlay>
+}
+
+impl Line{
+    pub fn new(plays: Vec<LinePlay>) -> Self{
+        Self{plays}
+    }
+    pub fn plays(&self) -> &[LinePlay]{
+        &self.plays
+    }
+}
+
+impl Display for Line{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.plays.is_empty(){
+            true => write!(f, "cash"),
+            false => {
+                let plays: Vec<String> = self.plays.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}", plays.join(", "))
+            }
+        }
+    }
+}
+
+/// Layout of missing cards between defenders with its a priori probability
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuitLayout{
+    pub west: CardSet,
+    pub east: CardSet,
+    pub probability: f64
+}
+
+/// Evaluation of line over all layouts
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineEvaluation{
+    line: Line,
+    success_probability: f64,
+    expected_tricks: f64
+}
+
+impl LineEvaluation{
+    pub fn line(&self) -> &Line{
+        &self.line
+    }
+    /// Probability of taking at least required number of tricks
+    pub fn success_probability(&self) -> f64{
+        self.success_probability
+    }
+    /// Success probability in percents
+    pub fn percentage(&self) -> f64{
+        100.0 * self.success_probability
+    }
+    pub fn expected_tricks(&self) -> f64{
+        self.expected_tricks
+    }
+}
+
+impl Display for LineEvaluation{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {:.2}% ({:.2} tricks)", self.line, self.percentage(), self.expected_tricks)
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64{
+    match k > n{
+        true => 0.0,
+        false => (0..k.min(n - k)).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+    }
+}
+
+fn highest(holding: Holding) -> Option<usize>{
+    (holding != 0).then(|| 15 - holding.leading_zeros() as usize)
+}
+
+fn lowest(holding: Holding) -> Option<usize>{
+    (holding != 0).then(|| holding.trailing_zeros() as usize)
+}
+
+/// Analyser of declarer's holding in single suit
+#[derive(Debug, Clone)]
+pub struct SuitCombinationAnalyser{
+    suit: Suit,
+    north: Holding,
+    south: Holding,
+    max_steps: usize,
+    solver: NoTrumpTrickSolver<Card>
+}
+
+impl SuitCombinationAnalyser{
+    /// Takes cards of suit from North (dummy) and South (declarer), cards of other suits are ignored.
+    /// Fails when the same card is in both hands.
+    pub fn new(suit: Suit, north: CardSet, south: CardSet) -> Result<Self, DistributionError>{
+        let holding = |hand: &CardSet| hand.suit_iterator(&suit)
+            .fold(0, |acc, c| acc | (1 << c.figure().usize_index()));
+        let (north, south) = (holding(&north), holding(&south));
+        if let Some(common) = lowest(north & south){
+            return Err(DistributionError::CardNotAvailable(Self::card_of(suit, common)))
+        }
+        Ok(Self{suit, north, south, max_steps: 2, solver: NoTrumpTrickSolver::new()})
+    }
+    /// Number of tricks of analysed lines, later tricks are cashed from the top (default 2)
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self{
+        self.max_steps = max_steps;
+        self
+    }
+
+    fn card_of(suit: Suit, rank: usize) -> Card{
+        Card::new(Figure::from_usize_index(rank).expect("rank of figure is below 13"), suit)
+    }
+    fn card(&self, rank: usize) -> Card{
+        Self::card_of(self.suit, rank)
+    }
+    fn cards(&self, holding: Holding) -> CardSet{
+        let mut hand = CardSet::empty();
+        for rank in (0..Figure::SYMBOL_SPACE).filter(|r| holding & (1 << r) != 0){
+            hand.insert_card(self.card(rank)).expect("ranks are distinct");
+        }
+        hand
+    }
+    /// Card of other suit played by side void in analysed suit
+    fn discard(&self, side: Side) -> Card{
+        let suit = SUITS.into_iter().find(|s| *s != self.suit).expect("there are four suits");
+        Self::card_of(suit, side.index() as usize)
+    }
+    fn missing(&self) -> Holding{
+        ((1 << Figure::SYMBOL_SPACE) - 1) & !(self.north | self.south)
+    }
+
+    /// Every layout of missing cards, probabilities sum to 1
+    /// ```
+    /// use brydz_core::contract::suit_combination::SuitCombinationAnalyser;
+    /// use karty::hand::HAND_OF_SPADES;
+    /// use karty::hand::{CardSet, HandTrait};
+    /// use karty::cards::{ACE_SPADES, KING_SPADES, QUEEN_SPADES};
+    /// use karty::suits::Suit::Spades;
+    /// let mut north = HAND_OF_SPADES;
+    /// for card in [ACE_SPADES, KING_SPADES, QUEEN_SPADES]{
+    ///     north.remove_card(&card).unwrap();
+    /// }
+    /// let analyser = SuitCombinationAnalyser::new(Spades, north, CardSet::empty()).unwrap();
+    /// let layouts = analyser.layouts();
+    /// assert_eq!(layouts.len(), 8);
+    /// assert!((layouts.iter().map(|l| l.probability).sum::<f64>() - 1.0).abs() < 1e-9);
+    /// // 2-1 split of three cards has probability 0.78
+    /// let two_one: f64 = layouts.iter().filter(|l| l.west.len().abs_diff(l.east.len()) == 1).map(|l| l.probability).sum();
+    /// assert!((two_one - 0.78).abs() < 1e-9);
+    /// ```
+    pub fn layouts(&self) -> Vec<SuitLayout>{
+        self.holding_layouts().into_iter().map(|(west, east, probability)| SuitLayout{
+            west: self.cards(west), east: self.cards(east), probability
+        }).collect()
+    }
+
+    fn holding_layouts(&self) -> Vec<(Holding, Holding, f64)>{
+        let missing = self.missing();
+        let ranks: Vec<usize> = (0..Figure::SYMBOL_SPACE).filter(|r| missing & (1 << r) != 0).collect();
+        let n = ranks.len();
+        let unknown = 2 * HAND_SIZE;
+        let all = binomial(unknown, HAND_SIZE);
+        (0..1u32 << n).map(|subset|{
+            let west = ranks.iter().enumerate().filter(|(i, _)| subset & (1 << i) != 0)
+                .fold(0, |acc, (_, r)| acc | (1 << r));
+            let k = subset.count_ones() as usize;
+            let probability = match k <= HAND_SIZE && n - k <= HAND_SIZE{
+                true => binomial(unknown - n, HAND_SIZE - k) / all,
+                false => 0.0
+            };
+            (west, missing & !west, probability)
+        }).collect()
+    }
+
+    /// Tricks declarer takes with line when defenders hold `west` and `east` (cards of other suits are ignored)
+    pub fn tricks(&self, line: &Line, west: CardSet, east: CardSet) -> u8{
+        let holding = |hand: &CardSet| hand.suit_iterator(&self.suit)
+            .fold(0, |acc, c| acc | (1 << c.figure().usize_index()));
+        let hands = SideMap::new(self.north, holding(&east) & !(self.north | self.south),
+            self.south, holding(&west) & !(self.north | self.south));
+        self.play(line, hands, 0, &mut HashMap::new())
+    }
+
+    /// Lead of trick: step of line or cashing from the top
+    fn line_play(&self, line: &Line, hands: &SideMap<Holding>, step: usize) -> LinePlay{
+        line.plays.get(step).copied().unwrap_or_else(||{
+            let leader = match highest(hands.north) > highest(hands.south){
+                true => North,
+                false => South
+            };
+            LinePlay::new(leader, LeadCard::Highest, Response::Duck)
+        })
+    }
+
+    /// Ranks defender may play, one from every group of cards equivalent in current position
+    fn defender_options(&self, hand: Holding, in_play: Holding) -> Vec<Option<usize>>{
+        if hand == 0{
+            return vec![None]
+        }
+        (0..Figure::SYMBOL_SPACE).filter(|r| hand & (1 << r) != 0)
+            .filter(|r| {
+                let below = in_play & ((1 << r) - 1);
+                highest(below).is_none_or(|b| hand & (1 << b) == 0)
+            })
+            .map(Some).collect()
+    }
+
+    fn insert(&self, trick: &mut TrickGen<Card>, side: Side, rank: Option<usize>){
+        let card = rank.map(|r| self.card(r)).unwrap_or_else(|| self.discard(side));
+        trick.insert_card(side, card).expect("cards in trick are distinct");
+    }
+
+    fn response_rank(&self, response: Response, hand: Holding, trick: &TrickGen<Card>, partner: Side) -> Option<usize>{
+        match response{
+            Response::Duck => lowest(hand),
+            Response::Rise => highest(hand),
+            Response::Cover => {
+                let winning = self.solver.leader(trick)
+                    .filter(|side| *side != partner)
+                    .and_then(|side| trick[side])
+                    .filter(|c| c.suit() == self.suit);
+                match winning{
+                    None => lowest(hand),
+                    Some(card) => {
+                        let above = hand & !((2 << card.figure().usize_index()) - 1);
+                        lowest(above).or_else(|| lowest(hand))
+                    }
+                }
+            }
+        }
+    }
+
+    fn play(&self, line: &Line, hands: SideMap<Holding>, step: usize,
+            memo: &mut HashMap<(usize, [Holding; 4]), u8>) -> u8{
+        if hands.north | hands.south == 0{
+            return 0
+        }
+        let key = (step.min(line.plays.len()), [hands.north, hands.east, hands.south, hands.west]);
+        if let Some(tricks) = memo.get(&key){
+            return *tricks
+        }
+        let line_play = self.line_play(line, &hands, step);
+        let leader = match (line_play.leader, hands.north == 0, hands.south == 0){
+            (North, true, _) => South,
+            (North, false, _) => North,
+            (_, _, true) => North,
+            _ => South
+        };
+        let lead = match line_play.lead{
+            LeadCard::Highest => highest(hands[&leader]),
+            LeadCard::Lowest => lowest(hands[&leader])
+        };
+        let (second, third, fourth) = (leader.next(), leader.partner(), leader.next().partner());
+        let mut trick = TrickGen::new(leader);
+        self.insert(&mut trick, leader, lead);
+        let in_play = hands.fold_on_ref(0, |acc, h| acc | *h);
+        let mut best = u8::MAX;
+        for second_rank in self.defender_options(hands[&second], in_play){
+            let mut trick = trick;
+            self.insert(&mut trick, second, second_rank);
+            let third_rank = self.response_rank(line_play.response, hands[&third], &trick, leader);
+            self.insert(&mut trick, third, third_rank);
+            for fourth_rank in self.defender_options(hands[&fourth], in_play){
+                let mut trick = trick;
+                self.insert(&mut trick, fourth, fourth_rank);
+                let won = self.solver.winner(&trick).map_or(0, |w| u8::from(w.axis() == leader.axis()));
+                let mut next = hands;
+                for (side, rank) in [(leader, lead), (second, second_rank), (third, third_rank), (fourth, fourth_rank)]{
+                    if let Some(r) = rank{
+                        next[&side] &= !(1 << r);
+                    }
+                }
+                best = best.min(won + self.play(line, next, step + 1, memo));
+            }
+        }
+        memo.insert(key, best);
+        best
+    }
+
+    /// Evaluates line with required number of tricks
+    pub fn evaluate(&self, line: &Line, needed: u8) -> LineEvaluation{
+        let mut memo = HashMap::new();
+        let (mut success, mut expected) = (0.0, 0.0);
+        for (west, east, probability) in self.holding_layouts(){
+            let tricks = self.play(line, SideMap::new(self.north, east, self.south, west), 0, &mut memo);
+            expected += probability * tricks as f64;
+            if tricks >= needed{
+                success += probability;
+            }
+        }
+        LineEvaluation{line: line.clone(), success_probability: success, expected_tricks: expected}
+    }
+
+    /// Every line of [`with_max_steps`](Self::with_max_steps) tricks, lines with the same results in every layout
+    /// are reported once. Lines are sorted by probability of taking `needed` tricks, then by expected tricks.
+    pub fn analyse(&self, needed: u8) -> Vec<LineEvaluation>{
+        let options: Vec<LinePlay> = [South, North].into_iter().flat_map(|leader|
+            [LeadCard::Lowest, LeadCard::Highest].into_iter().flat_map(move |lead|
+                [Response::Cover, Response::Duck, Response::Rise].into_iter()
+                    .map(move |response| LinePlay::new(leader, lead, response))))
+            .collect();
+        let tricks = (self.north.count_ones().max(self.south.count_ones()) as usize).min(self.max_steps);
+        let mut lines = vec![Vec::new()];
+        for _ in 0..tricks{
+            lines = lines.into_iter().flat_map(|plays: Vec<LinePlay>| options.iter().map(move |option|{
+                let mut plays = plays.clone();
+                plays.push(*option);
+                plays
+            })).collect();
+        }
+        let layouts = self.holding_layouts();
+        let mut seen = HashMap::new();
+        let mut evaluations = Vec::new();
+        for plays in lines{
+            let line = Line::new(plays);
+            let mut memo = HashMap::new();
+            let results: Vec<u8> = layouts.iter()
+                .map(|(west, east, _)| self.play(&line, SideMap::new(self.north, *east, self.south, *west), 0, &mut memo))
+                .collect();
+            if seen.insert(results.clone(), ()).is_some(){
+                continue;
+            }
+            let (mut success, mut expected) = (0.0, 0.0);
+            for ((_, _, probability), tricks) in layouts.iter().zip(results){
+                expected += probability * tricks as f64;
+                if tricks >= needed{
+                    success += probability;
+                }
+            }
+            evaluations.push(LineEvaluation{line, success_probability: success, expected_tricks: expected});
+        }
+        evaluations.sort_by(|a, b| b.success_probability.total_cmp(&a.success_probability)
+            .then(b.expected_tricks.total_cmp(&a.expected_tricks)));
+        evaluations
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::card_set;
+    use karty::cards::*;
+    use karty::suits::Suit::Hearts;
+    use crate::contract::suit_combination::{LeadCard, Line, LinePlay, Response, SuitCombinationAnalyser};
+    use crate::player::side::Side::{North, South};
+
+    #[test]
+    fn drop_against_finesse(){
+        // AKJxx opposite xxx, four missing including the queen: finesse beats playing for the drop
+        let analyser = SuitCombinationAnalyser::new(Hearts,
+            card_set![ACE_HEARTS, KING_HEARTS, JACK_HEARTS, TEN_HEARTS, NINE_HEARTS],
+            card_set![FOUR_HEARTS, THREE_HEARTS, TWO_HEARTS]).unwrap();
+        let cash = analyser.evaluate(&Line::default(), 5);
+        let finesse = analyser.evaluate(&Line::new(vec![
+            LinePlay::new(South, LeadCard::Lowest, Response::Cover)]), 5);
+        assert!(finesse.success_probability() > cash.success_probability());
+        let best = &analyser.analyse(5)[0];
+        assert!(best.success_probability() >= finesse.success_probability());
+        assert!((best.percentage() - 100.0 * best.success_probability()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn solid_suit_always_makes(){
+        let analyser = SuitCombinationAnalyser::new(Hearts,
+            card_set![ACE_HEARTS, KING_HEARTS, QUEEN_HEARTS], card_set![JACK_HEARTS, TEN_HEARTS]).unwrap();
+        let evaluations = analyser.analyse(3);
+        assert!((evaluations[0].success_probability() - 1.0).abs() < 1e-9);
+        assert!(SuitCombinationAnalyser::new(Hearts, card_set![ACE_HEARTS], card_set![ACE_HEARTS]).is_err());
+        assert_eq!(LinePlay::new(North, LeadCard::Highest, Response::Rise).to_string(), "N high (S rise)");
+    }
+}
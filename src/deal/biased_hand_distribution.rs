@@ -5,18 +5,26 @@ use rand::prelude::{Distribution};
 use rand::Rng;
 use rand::seq::SliceRandom;
 use smallvec::SmallVec;
-use karty::cards::{Card, DECK_SIZE, STANDARD_DECK};
+use karty::cards::{Card, Card2SymTrait, DECK_SIZE, STANDARD_DECK};
 use karty::figures::Figure;
 use karty::hand::{CardSet, HandTrait};
-use karty::suits::{Suit, SuitMap};
+use karty::suits::{Suit, SuitMap, SUITS};
 use karty::suits::Suit::Spades;
 use karty::symbol::CardSymbol;
-use crate::error::FuzzyCardSetErrorGen;
+use crate::bidding::{AuctionStack, DeclarationStorage};
+use crate::bidding::system::BiddingSystem;
+use crate::deal::{DealFilter, DealGenerator};
+use crate::error::{DistributionError, FuzzyCardSetErrorGen};
 use crate::meta::HAND_SIZE;
 use crate::player::side::{Side, SideMap, SIDES};
 use crate::player::side::Side::{East, North, South, West};
 use crate::amfi::state::{FProbability, FuzzyCardSet};
 
+const RENORMALISATION_ITERATIONS: usize = 200;
+const RENORMALISATION_TOLERANCE: f32 = 0.001;
+/// Pseudo count added to every possible placement of card when estimating distribution from sample
+const LAPLACE_SMOOTHING: f32 = 1.0;
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug)]
 pub struct BiasedHandDistribution {
@@ -25,6 +33,106 @@ pub struct BiasedHandDistribution {
 
 impl BiasedHandDistribution{
 
+    pub fn new(side_probabilities: SideMap<FuzzyCardSet>) -> Self{
+        Self{side_probabilities}
+    }
+
+    /// Distribution estimated from sample of deals with Laplace smoothing: every side that `possible(side, card)`
+    /// allows keeps nonzero probability of holding card, even when card was never dealt to it in sample.
+    /// Probabilities are then renormalised so that every side holds thirteen cards.
+    /// ```
+    /// use brydz_core::deal::{fair_bridge_deal, BiasedHandDistribution};
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::cards::ACE_SPADES;
+    /// use karty::hand::{CardSet, HandTrait};
+    /// let deal = fair_bridge_deal::<CardSet>();
+    /// let holder = [North, East, South, West].into_iter().find(|side| deal[side].contains(&ACE_SPADES)).unwrap();
+    /// let distribution = BiasedHandDistribution::from_deals_smoothed(&[deal], |_, _| true).unwrap();
+    /// assert!(distribution[holder.next()][&ACE_SPADES].is_uncertain());
+    /// assert!(f32::from(distribution[holder][&ACE_SPADES]) > f32::from(distribution[holder.next()][&ACE_SPADES]));
+    /// ```
+    pub fn from_deals_smoothed<F: Fn(Side, &Card) -> bool>(deals: &[SideMap<CardSet>], possible: F) -> Result<Self, FuzzyCardSetErrorGen<Card>>{
+        let mut weights = SideMap::new_symmetric(SuitMap::new_from_f(|_| [0.0f32; HAND_SIZE]));
+        for card in STANDARD_DECK{
+            let sides: SmallVec<[Side; 4]> = SIDES.into_iter().filter(|s| possible(*s, &card)).collect();
+            let total = deals.len() as f32 + LAPLACE_SMOOTHING * sides.len() as f32;
+            for side in sides{
+                let count = deals.iter().filter(|deal| deal[&side].contains(&card)).count();
+                weights[&side][card.suit()][card.figure().usize_index()] = (count as f32 + LAPLACE_SMOOTHING) / total;
+            }
+        }
+        let mut side_probabilities = SideMap::new_symmetric(FuzzyCardSet::empty());
+        for side in SIDES{
+            side_probabilities[&side] = FuzzyCardSet::new_from_f32_derive_sum(weights[&side])?;
+        }
+        let mut distribution = Self{side_probabilities};
+        distribution.renormalise(possible, &SideMap::new_symmetric(HAND_SIZE as u8))?;
+        Ok(distribution)
+    }
+
+    /// Beliefs about hidden hands at the start of play: deals consistent with conditions that calls of `auction`
+    /// show in `system` are sampled and frequencies of cards in hands are estimated.
+    /// `known` are cards seen by player (own hand, dummy), conditions on sides whose whole hand is known are ignored.
+    /// Frequencies are smoothed (see [`from_deals_smoothed`](Self::from_deals_smoothed)), so card that is
+    /// not known to be elsewhere stays possible for every side with unknown cards, even if it is never sampled there.
+    /// ```
+    /// use brydz_core::bidding::Call;
+    /// use brydz_core::bidding::consts::BID_NT1;
+    /// use brydz_core::bidding::parser::parse_auction;
+    /// use brydz_core::bidding::system::{BiddingSystem, CallMeaning, SystemNode};
+    /// use brydz_core::deal::{BiasedHandDistribution, HandCondition};
+    /// use brydz_core::player::side::SideMap;
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::card_set;
+    /// use karty::cards::*;
+    /// use karty::hand::{CardSet, HandTrait};
+    /// let system = BiddingSystem::new("Example").opening(SystemNode::new(Call::NewBid(BID_NT1),
+    ///     CallMeaning::new("Strong notrump").condition(HandCondition::Hcp(15..=17)).condition(HandCondition::Balanced)));
+    /// let auction = parse_auction("1NT P P P", North).unwrap();
+    /// let east = card_set![ACE_SPADES, ACE_HEARTS, ACE_DIAMONDS, ACE_CLUBS, TWO_SPADES, THREE_SPADES, FOUR_SPADES,
+    ///     TWO_HEARTS, THREE_HEARTS, TWO_DIAMONDS, THREE_DIAMONDS, TWO_CLUBS, THREE_CLUBS];
+    /// let known = SideMap::new(CardSet::empty(), east, CardSet::empty(), CardSet::empty());
+    /// let distribution = BiasedHandDistribution::from_auction(&system, &auction, &known, 200, &mut rand::thread_rng()).unwrap();
+    /// assert!(distribution[East][&ACE_SPADES].is_one());
+    /// assert!(distribution[North][&ACE_SPADES].is_zero());
+    /// // without aces North needs most of kings and queens
+    /// assert!(f32::from(distribution[North][&KING_SPADES]) > f32::from(distribution[West][&KING_SPADES]));
+    /// ```
+    pub fn from_auction<DS: DeclarationStorage<Suit>, R: Rng + ?Sized>(system: &BiddingSystem, auction: &AuctionStack<Suit, DS>,
+        known: &SideMap<CardSet>, samples: usize, rng: &mut R) -> Result<Self, DistributionError>{
+        let filter = system.auction_filter(auction).conditions().iter()
+            .filter(|(side, _)| known[side].len() < HAND_SIZE)
+            .fold(DealFilter::new(), |filter, (side, condition)| filter.condition(*side, condition.clone()));
+        Self::from_generator(&filter.generator(), known, samples, rng)
+    }
+
+    /// Beliefs about hidden hands estimated from deals of `generator` that place `known` cards in sides' hands.
+    /// Frequencies are smoothed as in [`from_auction`](Self::from_auction).
+    /// ```
+    /// use brydz_core::deal::{BiasedHandDistribution, DealFilter};
+    /// use brydz_core::player::side::SideMap;
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::cards::TWO_CLUBS;
+    /// use karty::hand::{CardSet, HandTrait, HAND_OF_SPADES};
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// let generator = "North 7+ clubs".parse::<DealFilter>().unwrap().generator();
+    /// let known = SideMap::new(CardSet::empty(), HAND_OF_SPADES, CardSet::empty(), CardSet::empty());
+    /// let distribution = BiasedHandDistribution::from_generator(&generator, &known, 200, &mut StdRng::seed_from_u64(5)).unwrap();
+    /// assert!(distribution[East][&TWO_CLUBS].is_zero());
+    /// assert!(f32::from(distribution[North][&TWO_CLUBS]) > f32::from(distribution[South][&TWO_CLUBS]));
+    /// ```
+    pub fn from_generator<R: Rng + ?Sized>(generator: &DealGenerator, known: &SideMap<CardSet>, samples: usize, rng: &mut R)
+        -> Result<Self, DistributionError>{
+        let generator = SIDES.iter().fold(generator.clone(), |generator, side| generator.known_cards(*side, known[side]));
+        let deals = generator.generate_many(samples.max(1), rng)?;
+        let possible = |side: Side, card: &Card| match SIDES.iter().find(|s| known[s].contains(card)){
+            Some(holder) => *holder == side,
+            None => known[&side].len() < HAND_SIZE
+        };
+        Self::from_deals_smoothed(&deals, possible).map_err(|_| DistributionError::NoConsistentLayout)
+    }
+
     pub fn card_probabilities(&self, card: &Card) -> SideMap<FProbability>{
         SideMap::new(
                 self.side_probabilities[&North].card_probability(card),
@@ -34,6 +142,114 @@ impl BiasedHandDistribution{
         )
     }
 
+    /// Renormalises distribution to what is known about the game.
+    /// `possible(side, card)` tells whether side may still hold card (cards already played or in suit that side
+    /// showed out of are not possible), `cards_left` is the number of cards every side still holds.
+    /// Card possible for exactly one side becomes certain, card possible for nobody is zeroed.
+    /// Probabilities of uncertain cards are rescaled (iterative proportional fitting) so that every card sums to one
+    /// across sides and every side sums to number of cards it holds.
+    /// ```
+    /// use approx::assert_abs_diff_eq;
+    /// use brydz_core::deal::BiasedHandDistribution;
+    /// use brydz_core::player::side::{Side, SideMap};
+    /// use brydz_core::player::side::Side::{East, North};
+    /// use karty::cards::{Card, Card2SymTrait, ACE_SPADES, KING_SPADES};
+    /// use karty::suits::Suit::Spades;
+    /// let mut distribution = BiasedHandDistribution::default();
+    /// // North played ace of spades, East showed out of spades
+    /// distribution.renormalise(|side: Side, card: &Card| card != &ACE_SPADES
+    ///     && !(side == East && card.suit() == Spades), &SideMap::new(12, 13, 13, 13)).unwrap();
+    /// assert!(distribution[East][&KING_SPADES].is_zero());
+    /// assert!(distribution.card_probabilities(&ACE_SPADES).fold_on_ref(true, |acc, p| acc && p.is_zero()));
+    /// assert_abs_diff_eq!(distribution.card_probabilities(&KING_SPADES)
+    ///     .fold_on_ref(0.0f32, |acc, p| acc + f32::from(*p)), 1.0, epsilon=0.001);
+    /// assert_abs_diff_eq!(distribution[North].sum_probabilities(), 12.0, epsilon=0.01);
+    /// ```
+    pub fn renormalise<F: Fn(Side, &Card) -> bool>(&mut self, possible: F, cards_left: &SideMap<u8>) -> Result<(), FuzzyCardSetErrorGen<Card>>{
+        let mut weights = SideMap::new_symmetric(SuitMap::new_from_f(|_| [0.0f32; HAND_SIZE]));
+        let mut certain = SideMap::new_symmetric(SuitMap::new_from_f(|_| [false; HAND_SIZE]));
+        let mut uncertain_cards: SmallVec<[Card; 64]> = SmallVec::new();
+        let mut targets = SideMap::new_with_fn(|side| cards_left[&side] as f32);
+        for card in STANDARD_DECK{
+            let sides: SmallVec<[Side; 4]> = SIDES.into_iter().filter(|s| possible(*s, &card)).collect();
+            match sides.len(){
+                0 => {},
+                1 => {
+                    certain[&sides[0]][card.suit()][card.figure().usize_index()] = true;
+                    targets[&sides[0]] -= 1.0;
+                },
+                _ => {
+                    for side in sides{
+                        weights[&side][card.suit()][card.figure().usize_index()] = f32::from(self.side_probabilities[&side][&card]);
+                    }
+                    uncertain_cards.push(card);
+                }
+            }
+        }
+        for side in SIDES{
+            if targets[&side] < 0.0{
+                return Err(FuzzyCardSetErrorGen::BadProbabilitiesSum{expected: cards_left[&side] as f32, found: cards_left[&side] as f32 - targets[&side]})
+            }
+        }
+
+        let mut converged = false;
+        for _ in 0..RENORMALISATION_ITERATIONS{
+            for side in SIDES{
+                let sum: f32 = uncertain_cards.iter()
+                    .map(|c| weights[&side][c.suit()][c.figure().usize_index()]).sum();
+                if sum <= 0.0{
+                    if targets[&side] > 0.0{
+                        return Err(FuzzyCardSetErrorGen::OutOfUncertainCardsForSide(side))
+                    }
+                    continue;
+                }
+                let scale = targets[&side] / sum;
+                for c in uncertain_cards.iter(){
+                    weights[&side][c.suit()][c.figure().usize_index()] *= scale;
+                }
+            }
+            for c in uncertain_cards.iter(){
+                let sum: f32 = SIDES.iter().map(|s| weights[s][c.suit()][c.figure().usize_index()]).sum();
+                if sum <= 0.0{
+                    return Err(FuzzyCardSetErrorGen::ImpossibleSideSelection)
+                }
+                for side in SIDES{
+                    weights[&side][c.suit()][c.figure().usize_index()] /= sum;
+                }
+            }
+            converged = SIDES.iter().all(|side|{
+                let sum: f32 = uncertain_cards.iter()
+                    .map(|c| weights[side][c.suit()][c.figure().usize_index()]).sum();
+                (sum - targets[side]).abs() < RENORMALISATION_TOLERANCE
+            });
+            if converged{
+                break;
+            }
+        }
+        if !converged{
+            let found: f32 = uncertain_cards.iter().map(|c| weights[&North][c.suit()][c.figure().usize_index()]).sum();
+            return Err(FuzzyCardSetErrorGen::BadProbabilitiesSum{expected: targets[&North], found})
+        }
+
+        let mut side_probabilities = SideMap::new_symmetric(FuzzyCardSet::empty());
+        for side in SIDES{
+            let mut probabilities = weights[&side];
+            for suit in SUITS{
+                for i in 0..HAND_SIZE{
+                    probabilities[suit][i] = match certain[&side][suit][i]{
+                        true => 1.0,
+                        false => probabilities[suit][i].clamp(0.0, 1.0)
+                    };
+                }
+            }
+            let mut set = FuzzyCardSet::new_from_f32_derive_sum(probabilities)?;
+            set.assert_expected_card_num_with_epsilon(cards_left[&side], RENORMALISATION_TOLERANCE * 2.0)?;
+            side_probabilities[&side] = set;
+        }
+        self.side_probabilities = side_probabilities;
+        Ok(())
+    }
+
     fn pick_side_for_card<R: Rng + ?Sized>(&self, card: &Card, map_of_closed: &SideMap<bool>, rng: &mut R) -> Result<Side, FuzzyCardSetErrorGen<Card>>{
         let card_probabilities = self.card_probabilities(card);
         let top_north = match map_of_closed[&North]{
@@ -477,4 +693,44 @@ impl Distribution<SideMap<CardSet>> for BiasedHandDistribution{
 
 
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests{
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use karty::card_set;
+    use karty::cards::*;
+    use karty::hand::{CardSet, HandTrait};
+    use crate::bidding::Call;
+    use crate::bidding::consts::BID_C2;
+    use crate::bidding::parser::parse_auction;
+    use crate::bidding::system::{BiddingSystem, CallMeaning, SystemNode};
+    use crate::deal::{BiasedHandDistribution, HandCondition};
+    use crate::player::side::SideMap;
+    use crate::player::side::Side::{East, North, West};
+
+    #[test]
+    fn rare_card_stays_possible(){
+        let system = BiddingSystem::new("Example").opening(SystemNode::new(Call::NewBid(BID_C2),
+            CallMeaning::new("Strong").condition(HandCondition::Hcp(20..=37))));
+        let auction = parse_auction("2C P P P", North).unwrap();
+        let east = card_set![TEN_SPADES, NINE_SPADES, EIGHT_SPADES, SEVEN_SPADES, TEN_HEARTS, NINE_HEARTS, EIGHT_HEARTS,
+            TEN_DIAMONDS, NINE_DIAMONDS, EIGHT_DIAMONDS, TEN_CLUBS, NINE_CLUBS, EIGHT_CLUBS];
+        let known = SideMap::new(CardSet::empty(), east, CardSet::empty(), CardSet::empty());
+        let distribution = BiasedHandDistribution::from_auction(&system, &auction, &known, 20, &mut StdRng::seed_from_u64(24)).unwrap();
+        let aces = [ACE_SPADES, ACE_HEARTS, ACE_DIAMONDS, ACE_CLUBS];
+        // West rarely holds aces opposite strong opening, yet every unseen card remains possible
+        for card in STANDARD_DECK.iter().filter(|c| !east.contains(c)){
+            assert!(distribution[West][card].is_uncertain());
+            assert!(distribution[North][card].is_uncertain());
+            assert!(distribution[East][card].is_zero());
+        }
+        let aces_of = |side| aces.iter().map(|ace| f32::from(distribution[side][ace])).sum::<f32>();
+        assert!(aces_of(West) < aces_of(North));
+        for card in east{
+            assert!(distribution[East][&card].is_one());
+            assert!(distribution[West][&card].is_zero());
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::{Display, Formatter};
+use karty::hand::CardSet;
+use karty::suits::SuitTrait;
+use crate::bidding::{AuctionStack, DeclarationStorage};
+use crate::player::axis::Axis;
+use crate::player::side::{Side, SideMap};
+
+/// Vulnerability of axes on board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Vulnerability{
+    #[default]
+    None,
+    NorthSouth,
+    EastWest,
+    Both
+}
+
+/// Vulnerability of boards 1 to 16, cycle repeats for next boards
+const VULNERABILITY_CYCLE: [Vulnerability; 16] = [
+    Vulnerability::None, Vulnerability::NorthSouth, Vulnerability::EastWest, Vulnerability::Both,
+    Vulnerability::NorthSouth, Vulnerability::EastWest, Vulnerability::Both, Vulnerability::None,
+    Vulnerability::EastWest, Vulnerability::Both, Vulnerability::None, Vulnerability::NorthSouth,
+    Vulnerability::Both, Vulnerability::None, Vulnerability::NorthSouth, Vulnerability::EastWest
+];
+
+impl Vulnerability{
+    pub fn from_axes(ns_vulnerable: bool, ew_vulnerable: bool) -> Self{
+        match (ns_vulnerable, ew_vulnerable){
+            (false, false) => Self::None,
+            (true, false) => Self::NorthSouth,
+            (false, true) => Self::EastWest,
+            (true, true) => Self::Both
+        }
+    }
+    /// Vulnerability of board in standard 16-board cycle, boards are numbered from 1.
+    /// ```
+    /// use brydz_core::deal::Vulnerability;
+    /// assert_eq!(Vulnerability::of_board(1), Vulnerability::None);
+    /// assert_eq!(Vulnerability::of_board(7), Vulnerability::Both);
+    /// assert_eq!(Vulnerability::of_board(12), Vulnerability::NorthSouth);
+    /// assert_eq!(Vulnerability::of_board(25), Vulnerability::EastWest);
+    /// ```
+    pub fn of_board(number: u32) -> Self{
+        VULNERABILITY_CYCLE[(number.saturating_sub(1) % 16) as usize]
+    }
+    pub fn is_vulnerable(&self, axis: Axis) -> bool{
+        match axis{
+            Axis::NorthSouth => self.ns_vulnerable(),
+            Axis::EastWest => self.ew_vulnerable()
+        }
+    }
+    pub fn ns_vulnerable(&self) -> bool{
+        matches!(self, Self::NorthSouth | Self::Both)
+    }
+    pub fn ew_vulnerable(&self) -> bool{
+        matches!(self, Self::EastWest | Self::Both)
+    }
+}
+
+impl Display for Vulnerability{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self{
+            Vulnerability::None => "None",
+            Vulnerability::NorthSouth => "NS",
+            Vulnerability::EastWest => "EW",
+            Vulnerability::Both => "Both"
+        })
+    }
+}
+
+/// Dealer of board in standard cycle: North deals board 1, then dealer rotates clockwise.
+/// ```
+/// use brydz_core::deal::board_dealer;
+/// use brydz_core::player::side::Side::{North, West};
+/// assert_eq!(board_dealer(1), North);
+/// assert_eq!(board_dealer(4), West);
+/// assert_eq!(board_dealer(17), North);
+/// ```
+pub fn board_dealer(number: u32) -> Side{
+    Side::North.next_i((number.saturating_sub(1) % 4) as u8)
+}
+
+/// Board: numbered deal with dealer and vulnerability.
+/// ```
+/// use brydz_core::deal::{Board, fair_bridge_deal, Vulnerability};
+/// use brydz_core::player::side::Side::South;
+/// use karty::hand::CardSet;
+/// let board = Board::new(3, fair_bridge_deal::<CardSet>());
+/// assert_eq!(board.dealer(), South);
+/// assert_eq!(board.vulnerability(), Vulnerability::EastWest);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board{
+    number: u32,
+    dealer: Side,
+    vulnerability: Vulnerability,
+    deal: SideMap<CardSet>
+}
+
+impl Board{
+    /// Creates board with dealer and vulnerability derived from number
+    pub fn new(number: u32, deal: SideMap<CardSet>) -> Self{
+        Self{number, dealer: board_dealer(number), vulnerability: Vulnerability::of_board(number), deal}
+    }
+    pub fn with_dealer(mut self, dealer: Side) -> Self{
+        self.dealer = dealer;
+        self
+    }
+    pub fn with_vulnerability(mut self, vulnerability: Vulnerability) -> Self{
+        self.vulnerability = vulnerability;
+        self
+    }
+    pub fn number(&self) -> u32{
+        self.number
+    }
+    pub fn dealer(&self) -> Side{
+        self.dealer
+    }
+    pub fn vulnerability(&self) -> Vulnerability{
+        self.vulnerability
+    }
+    pub fn deal(&self) -> &SideMap<CardSet>{
+        &self.deal
+    }
+    /// Empty auction started by dealer of this board
+    pub fn auction<S: SuitTrait, DS: DeclarationStorage<S>>(&self) -> AuctionStack<S, DS>{
+        AuctionStack::new_with_dealer(self.dealer)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::deal::{board_dealer, Vulnerability};
+    use crate::player::axis::Axis::{EastWest, NorthSouth};
+
+    #[test]
+    fn vulnerability_cycle_is_balanced(){
+        let boards: Vec<Vulnerability> = (1..=16).map(Vulnerability::of_board).collect();
+        assert_eq!(boards.iter().filter(|v| v.is_vulnerable(NorthSouth)).count(), 8);
+        assert_eq!(boards.iter().filter(|v| v.is_vulnerable(EastWest)).count(), 8);
+        for n in 1..=16{
+            assert_eq!(Vulnerability::of_board(n), Vulnerability::of_board(n + 16));
+            assert_eq!(board_dealer(n), board_dealer(n + 16));
+        }
+        assert_eq!(Vulnerability::from_axes(true, false), Vulnerability::NorthSouth);
+    }
+}
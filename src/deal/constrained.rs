@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use karty::cards::{Card, Card2SymTrait, STANDARD_DECK};
+use karty::hand::{CardSet, HandTrait};
+use karty::register::Register;
+use karty::suits::{Suit, SuitMap, SUITS};
+use crate::contract::{Contract, ContractMechanics};
+use crate::error::DistributionError;
+use crate::meta::HAND_SIZE;
+use crate::player::side::{Side, SideMap, SIDES};
+
+/// Knowledge about deal: cards fixed in hands, bounds of suit lengths (void is suit of length 0),
+/// cards removed from game (played) and number of unknown cards every side holds (vacant places).
+/// Deals consistent with constraints can be counted exactly and sampled uniformly.
+/// ```
+/// use brydz_core::deal::DealConstraints;
+/// let constraints = DealConstraints::new();
+/// // 52! / (13!)^4
+/// assert_eq!(constraints.count_layouts(), 53644737765488792839237440000);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DealConstraints{
+    fixed: SideMap<CardSet>,
+    lengths: SideMap<SuitMap<(u8, u8)>>,
+    vacant: SideMap<u8>,
+    removed: CardSet,
+}
+
+impl Default for DealConstraints{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DealConstraints{
+    /// Constraints of fresh deal: nothing is known, every side has 13 vacant places
+    pub fn new() -> Self{
+        Self{
+            fixed: SideMap::new_symmetric(CardSet::empty()),
+            lengths: SideMap::new_symmetric(SuitMap::new_symmetric((0, HAND_SIZE as u8))),
+            vacant: SideMap::new_symmetric(HAND_SIZE as u8),
+            removed: CardSet::empty()
+        }
+    }
+
+    /// Constraints of contract in progress: played cards are removed, voids are taken from exhaust table
+    /// and `known` cards (e.g. own hand and dummy) are fixed. Vacant places are cards left in hand that are not known.
+    /// ```
+    /// use brydz_core::bidding::Bid;
+    /// use brydz_core::cards::trump::TrumpGen;
+    /// use brydz_core::contract::{Contract, ContractMechanics, ContractParametersGen};
+    /// use brydz_core::deal::DealConstraints;
+    /// use brydz_core::player::side::SideMap;
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::cards::*;
+    /// use karty::hand::{CardSet, HandTrait};
+    /// use karty::suits::Suit::Spades;
+    /// let mut contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+    /// contract.insert_card(East, KING_SPADES).unwrap();
+    /// contract.insert_card(South, TWO_HEARTS).unwrap();
+    /// let constraints = DealConstraints::from_contract(&contract, &SideMap::new_symmetric(CardSet::empty())).unwrap();
+    /// assert_eq!(constraints.vacant(East), 12);
+    /// assert_eq!(constraints.vacant(West), 13);
+    /// assert!(constraints.is_void(South, Spades));
+    /// ```
+    pub fn from_contract(contract: &Contract, known: &SideMap<CardSet>) -> Result<Self, DistributionError>{
+        let mut constraints = Self::new();
+        for card in STANDARD_DECK{
+            if contract.used_cards().is_registered(&card){
+                constraints.removed.insert_card(card).map_err(|_| DistributionError::CardNotAvailable(card))?;
+            }
+        }
+        for side in SIDES{
+            constraints.vacant[&side] = contract.cards_left(side) as u8;
+            for suit in SUITS{
+                if contract.suits_exhausted().is_registered(&(side, suit)){
+                    constraints.lengths[&side][suit] = (0, 0);
+                }
+            }
+        }
+        for side in SIDES{
+            constraints.fix_cards(side, &known[&side])?;
+        }
+        Ok(constraints)
+    }
+
+    /// Fixes card in side's hand, it takes one of side's vacant places.
+    /// ```
+    /// use brydz_core::deal::DealConstraints;
+    /// use brydz_core::error::DistributionError;
+    /// use brydz_core::player::side::Side::{North, South};
+    /// use karty::cards::ACE_SPADES;
+    /// let mut constraints = DealConstraints::new();
+    /// constraints.fix_card(North, ACE_SPADES).unwrap();
+    /// assert_eq!(constraints.vacant(North), 12);
+    /// assert_eq!(constraints.fix_card(South, ACE_SPADES), Err(DistributionError::CardNotAvailable(ACE_SPADES)));
+    /// ```
+    pub fn fix_card(&mut self, side: Side, card: Card) -> Result<(), DistributionError>{
+        if self.removed.contains(&card) || self.fixed_in_suit(side, card.suit()) >= self.lengths[&side][card.suit()].1
+            || SIDES.iter().any(|s| self.fixed[s].contains(&card)){
+            return Err(DistributionError::CardNotAvailable(card))
+        }
+        if self.vacant[&side] == 0{
+            return Err(DistributionError::HandOverflow(side))
+        }
+        self.fixed[&side].insert_card(card).map_err(|_| DistributionError::CardNotAvailable(card))?;
+        self.vacant[&side] -= 1;
+        Ok(())
+    }
+
+    pub fn fix_cards(&mut self, side: Side, cards: &CardSet) -> Result<(), DistributionError>{
+        for card in *cards{
+            self.fix_card(side, card)?;
+        }
+        Ok(())
+    }
+
+    /// Marks side as void in suit, fails if side already has fixed card in this suit
+    pub fn set_void(&mut self, side: Side, suit: Suit) -> Result<(), DistributionError>{
+        if let Some(card) = self.fixed[&side].into_iter().find(|c| c.suit() == suit){
+            return Err(DistributionError::CardNotAvailable(card))
+        }
+        self.lengths[&side][suit] = (0, 0);
+        Ok(())
+    }
+
+    /// Restricts length of side's suit (fixed cards included) to `min..=max`, bounds set earlier are intersected.
+    /// ```
+    /// use brydz_core::deal::DealConstraints;
+    /// use brydz_core::player::side::Side::South;
+    /// use karty::hand::HandSuitedTrait;
+    /// use karty::suits::Suit::Hearts;
+    /// let mut constraints = DealConstraints::new();
+    /// constraints.set_suit_length(South, Hearts, 5, 13).unwrap();
+    /// constraints.set_suit_length(South, Hearts, 0, 6).unwrap();
+    /// assert_eq!(constraints.suit_length(South, Hearts), (5, 6));
+    /// let deal = constraints.sample(&mut rand::thread_rng()).unwrap();
+    /// assert!((5..=6).contains(&deal[&South].suit_iterator(&Hearts).count()));
+    /// ```
+    pub fn set_suit_length(&mut self, side: Side, suit: Suit, min: u8, max: u8) -> Result<(), DistributionError>{
+        let (old_min, old_max) = self.lengths[&side][suit];
+        let bounds = (old_min.max(min), old_max.min(max));
+        if bounds.0 > bounds.1 || self.fixed_in_suit(side, suit) > bounds.1{
+            return Err(DistributionError::NoConsistentLayout)
+        }
+        self.lengths[&side][suit] = bounds;
+        Ok(())
+    }
+
+    pub fn fixed(&self, side: Side) -> &CardSet{
+        &self.fixed[&side]
+    }
+    pub fn vacant(&self, side: Side) -> u8{
+        self.vacant[&side]
+    }
+    pub fn is_void(&self, side: Side, suit: Suit) -> bool{
+        self.lengths[&side][suit].1 == 0
+    }
+    /// Bounds (minimal, maximal) of length of side's suit
+    pub fn suit_length(&self, side: Side, suit: Suit) -> (u8, u8){
+        self.lengths[&side][suit]
+    }
+    fn fixed_in_suit(&self, side: Side, suit: Suit) -> u8{
+        self.fixed[&side].into_iter().filter(|c| c.suit() == suit).count() as u8
+    }
+    pub fn removed(&self) -> &CardSet{
+        &self.removed
+    }
+
+    /// Cards that are neither removed nor fixed
+    pub fn unknown_cards(&self) -> CardSet{
+        let mut unknown = CardSet::empty();
+        for card in STANDARD_DECK{
+            if !self.removed.contains(&card) && !SIDES.iter().any(|s| self.fixed[s].contains(&card)){
+                //card is not yet in set, insertion cannot fail
+                let _ = unknown.insert_card(card);
+            }
+        }
+        unknown
+    }
+
+    /// Exact number of layouts of unknown cards consistent with constraints
+    /// ```
+    /// use brydz_core::deal::DealConstraints;
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::cards::{Card2SymTrait, STANDARD_DECK};
+    /// use karty::figures::Ace;
+    /// use karty::suits::Suit::*;
+    /// let mut constraints = DealConstraints::new();
+    /// // only aces are unknown
+    /// for card in STANDARD_DECK.iter().filter(|c| c.figure() != Ace){
+    ///     let side = match card.suit(){ Clubs => North, Diamonds => East, Hearts => South, Spades => West };
+    ///     constraints.fix_card(side, *card).unwrap();
+    /// }
+    /// assert_eq!(constraints.count_layouts(), 24);
+    /// constraints.set_void(North, Spades).unwrap();
+    /// assert_eq!(constraints.count_layouts(), 18);
+    /// ```
+    pub fn count_layouts(&self) -> u128{
+        UniformDealSampler::new(self).count_layouts()
+    }
+
+    /// Samples full deal (fixed cards with unknown cards placed in vacant places),
+    /// every layout consistent with constraints is equally probable.
+    /// To draw many deals use [`UniformDealSampler`], which keeps counted layouts between samples.
+    /// ```
+    /// use brydz_core::deal::DealConstraints;
+    /// use brydz_core::player::side::Side::*;
+    /// use karty::cards::{Card2SymTrait, ACE_SPADES};
+    /// use karty::hand::HandTrait;
+    /// use karty::suits::Suit::Hearts;
+    /// let mut constraints = DealConstraints::new();
+    /// constraints.fix_card(West, ACE_SPADES).unwrap();
+    /// constraints.set_void(East, Hearts).unwrap();
+    /// let deal = constraints.sample(&mut rand::thread_rng()).unwrap();
+    /// assert!(deal[&West].contains(&ACE_SPADES));
+    /// assert_eq!(deal[&East].len(), 13);
+    /// assert!(!deal[&East].into_iter().any(|c| c.suit() == Hearts));
+    /// ```
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError>{
+        UniformDealSampler::new(self).sample(rng)
+    }
+
+    fn vacant_array(&self) -> [u8; 4]{
+        [self.vacant[&SIDES[0]], self.vacant[&SIDES[1]], self.vacant[&SIDES[2]], self.vacant[&SIDES[3]]]
+    }
+}
+
+/// Splits of suit among sides paired with cumulative number of layouts
+type WeightedSplits = Arc<Vec<([u8; 4], u128)>>;
+
+/// Uniform sampler of deals consistent with [`DealConstraints`].
+/// Layouts are counted suit by suit, number of ways to fill remaining vacant places with cards of remaining suits
+/// is memorised, so sampler should be reused when many deals are drawn from the same constraints.
+/// Sampler keeps its own copy of constraints and can be shared between threads.
+#[derive(Debug)]
+pub struct UniformDealSampler{
+    constraints: DealConstraints,
+    unknown: [Vec<Card>; 4],
+    /// Bounds of number of unknown cards of suit (first index) side (second index) receives
+    bounds: [[(u8, u8); 4]; 4],
+    binomial: Vec<Vec<u128>>,
+    memory: Mutex<HashMap<(usize, [u8; 4]), WeightedSplits>>
+}
+
+impl UniformDealSampler{
+    pub fn new(constraints: &DealConstraints) -> Self{
+        let unknown_set = constraints.unknown_cards();
+        let unknown = SUITS.map(|suit| unknown_set.into_iter().filter(|c| c.suit() == suit).collect());
+        let mut binomial = vec![vec![0u128; HAND_SIZE + 1]; HAND_SIZE + 1];
+        for n in 0..=HAND_SIZE{
+            binomial[n][0] = 1;
+            for k in 1..=n{
+                binomial[n][k] = binomial[n - 1][k - 1] + binomial[n - 1][k];
+            }
+        }
+        let bounds = SUITS.map(|suit| SIDES.map(|side|{
+            let (min, max) = constraints.suit_length(side, suit);
+            let fixed = constraints.fixed_in_suit(side, suit);
+            (min.saturating_sub(fixed), max.saturating_sub(fixed))
+        }));
+        Self{constraints: constraints.clone(), unknown, bounds, binomial, memory: Mutex::new(HashMap::new())}
+    }
+
+    /// Exact number of layouts of unknown cards consistent with constraints
+    pub fn count_layouts(&self) -> u128{
+        self.count(0, self.constraints.vacant_array())
+    }
+
+    /// Samples full deal, every layout consistent with constraints is equally probable
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError>{
+        let mut vacant = self.constraints.vacant_array();
+        if self.count(0, vacant) == 0{
+            return Err(DistributionError::NoConsistentLayout)
+        }
+        let mut deal = self.constraints.fixed;
+        for suit_index in 0..SUITS.len(){
+            let splits = self.weighted_splits(suit_index, vacant);
+            let total = splits.last().map(|(_, c)| *c).ok_or(DistributionError::NoConsistentLayout)?;
+            let pick = rng.gen_range(0..total);
+            let (split, _) = splits[splits.partition_point(|(_, c)| *c <= pick)];
+            let rest = Self::rest(&vacant, &split);
+            let mut cards = self.unknown[suit_index].clone();
+            cards.shuffle(rng);
+            let mut offset = 0;
+            for (i, side) in SIDES.iter().enumerate(){
+                for card in &cards[offset..offset + split[i] as usize]{
+                    deal[side].insert_card(*card).map_err(|_| DistributionError::CardNotAvailable(*card))?;
+                }
+                offset += split[i] as usize;
+            }
+            vacant = rest;
+        }
+        Ok(deal)
+    }
+
+    fn multinomial(&self, split: &[u8; 4]) -> u128{
+        let mut left = split.iter().map(|k| *k as usize).sum::<usize>();
+        let mut result = 1;
+        for k in split{
+            result *= self.binomial[left][*k as usize];
+            left -= *k as usize;
+        }
+        result
+    }
+
+    /// Every way to split unknown cards of suit among sides respecting suit length bounds and vacant places
+    fn splits(&self, suit_index: usize, vacant: &[u8; 4]) -> Vec<[u8; 4]>{
+        let limits: Vec<(u8, u8)> = (0..SIDES.len())
+            .map(|i| (self.bounds[suit_index][i].0, self.bounds[suit_index][i].1.min(vacant[i])))
+            .collect();
+        let cards = self.unknown[suit_index].len() as u8;
+        let mut result = Vec::new();
+        for a in limits[0].0..=limits[0].1.min(cards){
+            for b in limits[1].0..=limits[1].1.min(cards - a){
+                for c in limits[2].0..=limits[2].1.min(cards - a - b){
+                    let d = cards - a - b - c;
+                    if (limits[3].0..=limits[3].1).contains(&d){
+                        result.push([a, b, c, d]);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn count(&self, suit_index: usize, vacant: [u8; 4]) -> u128{
+        if suit_index == SUITS.len(){
+            return match vacant.iter().all(|v| *v == 0){
+                true => 1,
+                false => 0
+            }
+        }
+        self.weighted_splits(suit_index, vacant).last().map(|(_, c)| *c).unwrap_or(0)
+    }
+
+    /// Splits of suit that can be completed with remaining suits, paired with cumulative number of layouts
+    fn weighted_splits(&self, suit_index: usize, vacant: [u8; 4]) -> WeightedSplits{
+        let memorised = self.memory.lock().expect("Memory of sampler is not poisoned").get(&(suit_index, vacant)).cloned();
+        if let Some(splits) = memorised{
+            return splits
+        }
+        let mut cumulative = 0;
+        let mut weighted = Vec::new();
+        for split in self.splits(suit_index, &vacant){
+            let tail = self.count(suit_index + 1, Self::rest(&vacant, &split));
+            if tail > 0{
+                cumulative += self.multinomial(&split) * tail;
+                weighted.push((split, cumulative));
+            }
+        }
+        let weighted = Arc::new(weighted);
+        self.memory.lock().expect("Memory of sampler is not poisoned").insert((suit_index, vacant), weighted.clone());
+        weighted
+    }
+
+    fn rest(vacant: &[u8; 4], split: &[u8; 4]) -> [u8; 4]{
+        [vacant[0] - split[0], vacant[1] - split[1], vacant[2] - split[2], vacant[3] - split[3]]
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use std::collections::HashMap;
+    use karty::cards::{Card2SymTrait, STANDARD_DECK};
+    use karty::figures::{Ace, King};
+    use karty::hand::HandTrait;
+    use karty::suits::Suit::{Clubs, Diamonds, Hearts, Spades};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::deal::{DealConstraints, UniformDealSampler};
+    use crate::player::side::Side::*;
+    use crate::player::side::SIDES;
+
+    #[test]
+    fn uniform_over_consistent_layouts(){
+        let mut constraints = DealConstraints::new();
+        // unknown: aces and kings, two vacant places per side
+        for card in STANDARD_DECK.iter().filter(|c| c.figure() != Ace && c.figure() != King){
+            let side = match card.suit(){
+                Clubs => North,
+                Diamonds => South,
+                Hearts => East,
+                Spades => West
+            };
+            constraints.fix_card(side, *card).unwrap();
+        }
+        constraints.set_void(North, Hearts).unwrap();
+        constraints.set_void(South, Hearts).unwrap();
+        // hearts go to East and West: both to one of them (2 * 6!/(2!2!2!)) or split (2 * 6!/(2!1!2!1!))
+        assert_eq!(constraints.count_layouts(), 2 * 90 + 2 * 180);
+        let sampler = UniformDealSampler::new(&constraints);
+        assert_eq!(sampler.count_layouts(), 540);
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut frequencies = HashMap::new();
+        let samples = 54000;
+        for _ in 0..samples{
+            let deal = sampler.sample(&mut rng).unwrap();
+            for side in SIDES{
+                assert_eq!(deal[&side].len(), 13);
+            }
+            assert!(!deal[&North].into_iter().any(|c| c.suit() == Hearts));
+            *frequencies.entry(deal.destruct()).or_insert(0u32) += 1;
+        }
+        assert_eq!(frequencies.len(), 540);
+        for count in frequencies.values(){
+            assert!(*count > 60 && *count < 140, "{count}");
+        }
+    }
+
+    #[test]
+    fn inconsistent_constraints(){
+        let mut constraints = DealConstraints::new();
+        for card in STANDARD_DECK.iter().filter(|c| c.suit() != Hearts).take(13){
+            constraints.fix_card(North, *card).unwrap();
+        }
+        for side in [East, South, West]{
+            constraints.set_void(side, Hearts).unwrap();
+        }
+        assert_eq!(constraints.count_layouts(), 0);
+        assert!(constraints.sample(&mut rand::thread_rng()).is_err());
+    }
+}
@@ -2,17 +2,64 @@ use std::boxed::Box;
 use rand::distributions::Distribution;
 use rand::Rng;
 use karty::hand::CardSet;
-use crate::deal::{BiasedHandDistribution, distribute_standard_deck_on_4};
+use crate::deal::{BiasedHandDistribution, DealGenerator, DealSource, distribute_standard_deck_on_4};
+use crate::error::DistributionError;
 use crate::player::side::SideMap;
 
+/// Distribution of deals
+/// ```
+/// use brydz_core::deal::{DealDistribution, DealFilter};
+/// use rand::distributions::Distribution;
+/// let filter: DealFilter = "South 20+ hcp".parse().unwrap();
+/// let distribution = DealDistribution::constrained(filter.generator()).unwrap();
+/// let deal = distribution.sample(&mut rand::thread_rng());
+/// assert!(filter.matches(&deal));
+/// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive( Clone, Debug)]
 pub enum DealDistribution{
     Fair,
     Biased(Box<BiasedHandDistribution>),
+    /// Deals satisfying filter, prefer building it with [`DealDistribution::constrained`]
+    Constrained(Box<DealGenerator>),
+}
+
+impl DealDistribution{
+    /// Distribution of deals from generator, fails when no layout of cards is consistent with its filter
+    /// ```
+    /// use brydz_core::deal::{DealDistribution, DealFilter};
+    /// let filter: DealFilter = "North 7+ hearts, South 7+ hearts".parse().unwrap();
+    /// assert!(DealDistribution::constrained(filter.generator()).is_err());
+    /// ```
+    pub fn constrained(generator: DealGenerator) -> Result<Self, DistributionError>{
+        generator.validate()?;
+        Ok(Self::Constrained(Box::new(generator)))
+    }
+
+    /// Samples deal, fails when constrained generator does not find deal satisfying filter
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError>{
+        match self{
+            DealDistribution::Constrained(generator) => generator.generate(rng),
+            distribution => Ok(distribution.sample(rng))
+        }
+    }
+}
+
+impl DealSource for DealDistribution{
+    fn generate_deal<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError> {
+        self.generate(rng)
+    }
+}
+
+impl DealSource for BiasedHandDistribution{
+    fn generate_deal<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError> {
+        Ok(self.sample(rng))
+    }
 }
 
 impl Distribution<SideMap<CardSet>> for DealDistribution{
+    /// # Panics
+    /// When constrained generator fails to find deal (see [`DealDistribution::generate`] for fallible version).
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SideMap<CardSet> {
         match self{
             DealDistribution::Biased(distr) => {
@@ -21,6 +68,9 @@ impl Distribution<SideMap<CardSet>> for DealDistribution{
             DealDistribution::Fair => {
                 distribute_standard_deck_on_4(rng)
 
+            },
+            DealDistribution::Constrained(generator) => {
+                generator.as_ref().sample(rng)
             }
         }
     }
@@ -0,0 +1,632 @@
+//! Conditions on hands of deal and generator of deals satisfying them.
+//!
+//! Filter can be built with methods of [`DealFilter`] or parsed from text, where clauses for sides are separated
+//! with `,` or `;` and every clause is side followed by conditions separated with spaces:
+//! - `15-17 hcp`, `12+ hcp`, `10- hcp` (at most 10), `20 hcp` (exactly 20),
+//! - `5+ hearts` (suit names, letters or symbols are accepted),
+//! - `balanced` (4-3-3-3, 4-4-3-2 or 5-3-3-2),
+//! - `4432` or `4-4-3-2` (shape in any suit order), `5=4=2=2` (exact lengths of spades, hearts, diamonds and clubs),
+//! - `3+ controls`, `7- losers`.
+//! ```
+//! use brydz_core::deal::DealFilter;
+//! use brydz_core::player::side::Side::{North, South};
+//! use karty::suits::Suit::Hearts;
+//! let filter: DealFilter = "North 15-17 HCP balanced, South 5+ hearts".parse().unwrap();
+//! assert_eq!(filter, DealFilter::new().hcp(North, 15..=17).balanced(North).suit_length(South, Hearts, 5..=13));
+//! let deal = filter.generator().generate(&mut rand::thread_rng()).unwrap();
+//! assert!(filter.matches(&deal));
+//! ```
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::{char, digit1, multispace0, one_of, space0, space1};
+use nom::combinator::{all_consuming, map, map_res, opt, success, verify};
+use nom::IResult;
+use nom::multi::{many1, separated_list0};
+use nom::sequence::{delimited, pair, preceded, tuple};
+use rand::distributions::Distribution;
+use rand::Rng;
+use karty::hand::CardSet;
+use karty::suits::parse::parse_suit;
+use karty::suits::Suit;
+use crate::deal::{DealConstraints, UniformDealSampler};
+use crate::error::{DistributionError, FormatError};
+use crate::hand_eval::{controls, high_card_points, losing_trick_count, shape, suit_length, suit_lengths, BALANCED_SHAPES, SUIT_ORDER};
+use crate::meta::HAND_SIZE;
+use crate::player::side::{Side, SideMap};
+use crate::player::side::Side::{East, North, South, West};
+
+/// Limit of combinations of sides' shape alternatives allocated exactly,
+/// above it only common bounds of suit lengths are allocated
+const MAX_SHAPE_COMBINATIONS: usize = 4096;
+
+/// Bounds (minimal, maximal) of lengths of spades, hearts, diamonds and clubs
+type LengthBounds = [(u8, u8); 4];
+
+/// Distinct orderings of suit lengths
+fn permutations(lengths: [u8; 4]) -> Vec<[u8; 4]>{
+    let mut result: Vec<[u8; 4]> = Vec::new();
+    for a in 0..4{
+        for b in (0..4).filter(|b| *b != a){
+            for c in (0..4).filter(|c| *c != a && *c != b){
+                let d = 6 - a - b - c;
+                let p = [lengths[a], lengths[b], lengths[c], lengths[d]];
+                if !result.contains(&p){
+                    result.push(p);
+                }
+            }
+        }
+    }
+    result
+}
+
+fn intersect_bounds(first: &LengthBounds, second: &LengthBounds) -> Option<LengthBounds>{
+    let mut result = *first;
+    for i in 0..4{
+        result[i] = (first[i].0.max(second[i].0), first[i].1.min(second[i].1));
+        if result[i].0 > result[i].1{
+            return None
+        }
+    }
+    let hand = HAND_SIZE as u8;
+    match result.iter().map(|b| b.0).sum::<u8>() <= hand && result.iter().map(|b| b.1 as u16).sum::<u16>() >= hand as u16{
+        true => Some(result),
+        false => None
+    }
+}
+
+/// Pattern of hand shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShapePattern{
+    /// Lengths in any suit order, sorted from the longest
+    Any([u8; 4]),
+    /// Lengths of spades, hearts, diamonds and clubs
+    Exact([u8; 4])
+}
+
+impl ShapePattern{
+    /// Pattern of lengths in any order
+    /// ```
+    /// use brydz_core::deal::ShapePattern;
+    /// assert_eq!(ShapePattern::any([3, 4, 2, 4]), ShapePattern::Any([4, 4, 3, 2]));
+    /// ```
+    pub fn any(mut lengths: [u8; 4]) -> Self{
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        Self::Any(lengths)
+    }
+    pub fn matches(&self, hand: &CardSet) -> bool{
+        match self{
+            ShapePattern::Any(lengths) => &shape(hand) == lengths,
+            ShapePattern::Exact(lengths) => suit_lengths(hand) == *lengths
+        }
+    }
+    fn lengths(&self) -> [u8; 4]{
+        match self{
+            ShapePattern::Any(l) | ShapePattern::Exact(l) => *l
+        }
+    }
+}
+
+impl Display for ShapePattern{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (l, separator) = match self{
+            ShapePattern::Any(l) => (l, '-'),
+            ShapePattern::Exact(l) => (l, '=')
+        };
+        write!(f, "{}{separator}{}{separator}{}{separator}{}", l[0], l[1], l[2], l[3])
+    }
+}
+
+/// Condition on single hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandCondition{
+    Hcp(RangeInclusive<u8>),
+    SuitLength(Suit, RangeInclusive<u8>),
+    Shape(ShapePattern),
+    Balanced,
+    Controls(RangeInclusive<u8>),
+    Losers(RangeInclusive<u8>)
+}
+
+impl HandCondition{
+    pub fn matches(&self, hand: &CardSet) -> bool{
+        match self{
+            HandCondition::Hcp(r) => r.contains(&high_card_points(hand)),
+            HandCondition::SuitLength(suit, r) => r.contains(&suit_length(hand, *suit)),
+            HandCondition::Shape(pattern) => pattern.matches(hand),
+            HandCondition::Balanced => BALANCED_SHAPES.contains(&shape(hand)),
+            HandCondition::Controls(r) => r.contains(&controls(hand)),
+            HandCondition::Losers(r) => r.contains(&losing_trick_count(hand))
+        }
+    }
+
+    /// Disjoint alternatives of suit length bounds (spades, hearts, diamonds, clubs) implied by condition,
+    /// used to allocate suits before cards are dealt. `None` when condition does not restrict lengths.
+    fn length_alternatives(&self) -> Option<Vec<LengthBounds>>{
+        let any = (0, HAND_SIZE as u8);
+        match self{
+            HandCondition::SuitLength(suit, r) => {
+                let mut bounds = [any; 4];
+                bounds[SUIT_ORDER.iter().position(|s| s == suit).unwrap_or_default()] = (*r.start(), (*r.end()).min(any.1));
+                Some(vec![bounds])
+            },
+            HandCondition::Shape(ShapePattern::Exact(l)) => Some(vec![l.map(|length| (length, length))]),
+            HandCondition::Shape(ShapePattern::Any(l)) => Some(permutations(*l).into_iter()
+                .map(|p| p.map(|length| (length, length))).collect()),
+            HandCondition::Balanced => Some(BALANCED_SHAPES.iter().flat_map(|shape| permutations(*shape))
+                .map(|p| p.map(|length| (length, length))).collect()),
+            _ => None
+        }
+    }
+}
+
+fn fmt_range(f: &mut Formatter<'_>, range: &RangeInclusive<u8>, limit: u8) -> std::fmt::Result{
+    match (*range.start(), *range.end()){
+        (a, b) if a == b => write!(f, "{a}"),
+        (a, b) if b >= limit => write!(f, "{a}+"),
+        (0, b) => write!(f, "{b}-"),
+        (a, b) => write!(f, "{a}-{b}")
+    }
+}
+
+impl Display for HandCondition{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self{
+            HandCondition::Hcp(r) => {
+                fmt_range(f, r, u8::MAX)?;
+                write!(f, " hcp")
+            },
+            HandCondition::SuitLength(suit, r) => {
+                fmt_range(f, r, HAND_SIZE as u8)?;
+                write!(f, " {suit}")
+            },
+            HandCondition::Shape(pattern) => write!(f, "{pattern}"),
+            HandCondition::Balanced => write!(f, "balanced"),
+            HandCondition::Controls(r) => {
+                fmt_range(f, r, u8::MAX)?;
+                write!(f, " controls")
+            },
+            HandCondition::Losers(r) => {
+                fmt_range(f, r, u8::MAX)?;
+                write!(f, " losers")
+            }
+        }
+    }
+}
+
+/// Conditions on hands of deal
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DealFilter{
+    conditions: Vec<(Side, HandCondition)>
+}
+
+impl DealFilter{
+    /// Filter accepting every deal
+    pub fn new() -> Self{
+        Self{conditions: Vec::new()}
+    }
+    pub fn condition(mut self, side: Side, condition: HandCondition) -> Self{
+        self.conditions.push((side, condition));
+        self
+    }
+    pub fn hcp(self, side: Side, range: RangeInclusive<u8>) -> Self{
+        self.condition(side, HandCondition::Hcp(range))
+    }
+    pub fn suit_length(self, side: Side, suit: Suit, range: RangeInclusive<u8>) -> Self{
+        self.condition(side, HandCondition::SuitLength(suit, range))
+    }
+    pub fn shape(self, side: Side, pattern: ShapePattern) -> Self{
+        self.condition(side, HandCondition::Shape(pattern))
+    }
+    pub fn balanced(self, side: Side) -> Self{
+        self.condition(side, HandCondition::Balanced)
+    }
+    pub fn controls(self, side: Side, range: RangeInclusive<u8>) -> Self{
+        self.condition(side, HandCondition::Controls(range))
+    }
+    pub fn losers(self, side: Side, range: RangeInclusive<u8>) -> Self{
+        self.condition(side, HandCondition::Losers(range))
+    }
+    pub fn conditions(&self) -> &[(Side, HandCondition)]{
+        &self.conditions
+    }
+
+    pub fn matches(&self, deal: &SideMap<CardSet>) -> bool{
+        self.conditions.iter().all(|(side, condition)| condition.matches(&deal[side]))
+    }
+
+    /// Disjoint alternatives of suit lengths implied by conditions, deal satisfying filter satisfies exactly one of them.
+    /// When sides' shape alternatives have too many combinations, single constraints with common bounds are returned.
+    /// ```
+    /// use brydz_core::deal::DealFilter;
+    /// use brydz_core::player::side::Side::{North, South};
+    /// use karty::suits::Suit::{Clubs, Hearts};
+    /// let filter: DealFilter = "North balanced 5+ clubs; South 5+ hearts".parse().unwrap();
+    /// let alternatives = filter.constraints().unwrap();
+    /// // 5 clubs and 3-3-2 in other suits
+    /// assert_eq!(alternatives.len(), 3);
+    /// assert!(alternatives.iter().all(|c| c.suit_length(North, Clubs) == (5, 5)));
+    /// assert!(alternatives.iter().all(|c| c.suit_length(South, Hearts) == (5, 13)));
+    /// ```
+    pub fn constraints(&self) -> Result<Vec<DealConstraints>, DistributionError>{
+        let mut side_alternatives: Vec<(Side, Vec<LengthBounds>)> = Vec::new();
+        for (side, condition) in self.conditions.iter(){
+            if let Some(alternatives) = condition.length_alternatives(){
+                match side_alternatives.iter_mut().find(|(s, _)| s == side){
+                    None => side_alternatives.push((*side, alternatives.into_iter()
+                        .filter_map(|a| intersect_bounds(&a, &[(0, HAND_SIZE as u8); 4])).collect())),
+                    Some((_, current)) => {
+                        *current = current.iter().flat_map(|c| alternatives.iter().filter_map(|a| intersect_bounds(c, a)))
+                            .collect();
+                    }
+                }
+            }
+        }
+        let combinations = side_alternatives.iter().fold(1usize, |acc, (_, a)| acc.saturating_mul(a.len()));
+        if combinations == 0{
+            return Err(DistributionError::NoConsistentLayout)
+        }
+        if combinations > MAX_SHAPE_COMBINATIONS{
+            for (_, alternatives) in side_alternatives.iter_mut(){
+                let hull = alternatives.iter().skip(1).fold(alternatives[0], |acc, a|
+                    [0, 1, 2, 3].map(|i| (acc[i].0.min(a[i].0), acc[i].1.max(a[i].1))));
+                *alternatives = vec![hull];
+            }
+        }
+        let mut result = vec![DealConstraints::new()];
+        for (side, alternatives) in side_alternatives.iter(){
+            let mut next = Vec::with_capacity(result.len() * alternatives.len());
+            for constraints in result.iter(){
+                for bounds in alternatives.iter(){
+                    let mut c = constraints.clone();
+                    for (suit, (min, max)) in SUIT_ORDER.iter().zip(bounds.iter()){
+                        c.set_suit_length(*side, *suit, *min, *max)?;
+                    }
+                    next.push(c);
+                }
+            }
+            result = next;
+        }
+        Ok(result)
+    }
+
+    pub fn generator(&self) -> DealGenerator{
+        DealGenerator::new(self.clone())
+    }
+}
+
+impl Display for DealFilter{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut clauses: Vec<(Side, Vec<String>)> = Vec::new();
+        for (side, condition) in self.conditions.iter(){
+            match clauses.iter_mut().find(|(s, _)| s == side){
+                Some((_, conditions)) => conditions.push(condition.to_string()),
+                None => clauses.push((*side, vec![condition.to_string()]))
+            }
+        }
+        let clauses: Vec<String> = clauses.into_iter().map(|(side, conditions)| format!("{side} {}", conditions.join(" "))).collect();
+        write!(f, "{}", clauses.join(", "))
+    }
+}
+
+fn parse_side_name(s: &str) -> IResult<&str, Side>{
+    alt((
+        map(alt((tag_no_case("north"), tag_no_case("n"))), |_| North),
+        map(alt((tag_no_case("east"), tag_no_case("e"))), |_| East),
+        map(alt((tag_no_case("south"), tag_no_case("s"))), |_| South),
+        map(alt((tag_no_case("west"), tag_no_case("w"))), |_| West),
+    ))(s)
+}
+
+fn parse_number(s: &str) -> IResult<&str, u8>{
+    map_res(digit1, |d: &str| d.parse::<u8>())(s)
+}
+
+/// Parses range of values: `a-b`, `a+` (at least), `a-` (at most) or `a` (exactly)
+/// ```
+/// use brydz_core::deal::parse_value_range;
+/// assert_eq!(parse_value_range("15-17 hcp"), Ok((" hcp", 15..=17)));
+/// assert_eq!(parse_value_range("5+ hearts"), Ok((" hearts", 5..=u8::MAX)));
+/// assert_eq!(parse_value_range("7- losers"), Ok((" losers", 0..=7)));
+/// assert_eq!(parse_value_range("3 controls"), Ok((" controls", 3..=3)));
+/// ```
+pub fn parse_value_range(s: &str) -> IResult<&str, RangeInclusive<u8>>{
+    let (rest, start) = parse_number(s)?;
+    alt((
+        map(preceded(char('-'), parse_number), move |end| start..=end),
+        map(char('+'), move |_| start..=u8::MAX),
+        map(char('-'), move |_| 0..=start),
+        success(start..=start)
+    ))(rest)
+}
+
+fn parse_shape_digit(s: &str) -> IResult<&str, u8>{
+    map(one_of("0123456789"), |c| c as u8 - b'0')(s)
+}
+
+/// Parses shape pattern: `4432` or `4-4-3-2` (any suit order), `5=4=2=2` (spades, hearts, diamonds, clubs)
+/// ```
+/// use brydz_core::deal::{parse_shape_pattern, ShapePattern};
+/// assert_eq!(parse_shape_pattern("5=4=2=2"), Ok(("", ShapePattern::Exact([5, 4, 2, 2]))));
+/// assert_eq!(parse_shape_pattern("3-4-4-2"), Ok(("", ShapePattern::Any([4, 4, 3, 2]))));
+/// assert!(parse_shape_pattern("4444").is_err());
+/// ```
+pub fn parse_shape_pattern(s: &str) -> IResult<&str, ShapePattern>{
+    let d = parse_shape_digit;
+    verify(alt((
+        map(tuple((d, char('='), d, char('='), d, char('='), d)), |(a, _, b, _, c, _, e)| ShapePattern::Exact([a, b, c, e])),
+        map(tuple((d, char('-'), d, char('-'), d, char('-'), d)), |(a, _, b, _, c, _, e)| ShapePattern::any([a, b, c, e])),
+        map(tuple((d, d, d, d)), |(a, b, c, e)| ShapePattern::any([a, b, c, e])),
+    )), |p| p.lengths().iter().sum::<u8>() == HAND_SIZE as u8)(s)
+}
+
+fn parse_ranged_condition(s: &str) -> IResult<&str, HandCondition>{
+    let (rest, range) = parse_value_range(s)?;
+    let (min, max) = (*range.start(), *range.end());
+    preceded(space0, alt((
+        map(tag_no_case("hcp"), move |_| HandCondition::Hcp(min..=max)),
+        map(tag_no_case("controls"), move |_| HandCondition::Controls(min..=max)),
+        map(tag_no_case("losers"), move |_| HandCondition::Losers(min..=max)),
+        map(parse_suit, move |suit| HandCondition::SuitLength(suit, min..=max.min(HAND_SIZE as u8))),
+    )))(rest)
+}
+
+/// Parses single hand condition
+pub fn parse_hand_condition(s: &str) -> IResult<&str, HandCondition>{
+    alt((
+        map(parse_shape_pattern, HandCondition::Shape),
+        map(tag_no_case("balanced"), |_| HandCondition::Balanced),
+        parse_ranged_condition
+    ))(s)
+}
+
+/// Parses hand conditions separated with spaces
+/// ```
+/// use brydz_core::deal::{parse_hand_conditions, HandCondition};
+/// assert_eq!(parse_hand_conditions("15-17 hcp balanced"), Ok(("", vec![HandCondition::Hcp(15..=17), HandCondition::Balanced])));
+/// assert_eq!(parse_hand_conditions(""), Ok(("", vec![])));
+/// ```
+pub fn parse_hand_conditions(s: &str) -> IResult<&str, Vec<HandCondition>>{
+    delimited(space0, separated_list0(space1, parse_hand_condition), space0)(s)
+}
+
+fn parse_clause(s: &str) -> IResult<&str, (Side, Vec<HandCondition>)>{
+    pair(parse_side_name, many1(preceded(space1, parse_hand_condition)))(s)
+}
+
+/// Parses deal filter, clauses are separated with `,` or `;`
+pub fn parse_deal_filter(s: &str) -> IResult<&str, DealFilter>{
+    map(delimited(multispace0,
+        separated_list0(delimited(space0, one_of(",;"), multispace0), parse_clause),
+        pair(opt(one_of(",;")), multispace0)),
+        |clauses| DealFilter{conditions: clauses.into_iter()
+            .flat_map(|(side, conditions)| conditions.into_iter().map(move |c| (side, c))).collect()}
+    )(s)
+}
+
+impl FromStr for DealFilter{
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all_consuming(parse_deal_filter)(s)
+            .map(|(_, filter)| filter)
+            .map_err(|_| FormatError::DeserializeError)
+    }
+}
+
+/// Generator of deals satisfying [`DealFilter`].
+/// Suit lengths implied by filter are allocated first: every shape alternative is sampled with
+/// exact uniform sampling (see [`DealConstraints`]) with probability proportional to its number of layouts.
+/// Remaining conditions (points, controls, losers) are checked on generated deals.
+/// Samplers of shape alternatives are built on first draw and reused by following draws.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DealGenerator{
+    filter: DealFilter,
+    known: Vec<(Side, CardSet)>,
+    max_attempts: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    samplers: SamplerCache
+}
+
+/// Samplers of shape alternatives paired with cumulative numbers of their layouts
+#[derive(Debug)]
+struct WeightedSamplers{
+    samplers: Vec<UniformDealSampler>,
+    cumulative: Vec<u128>
+}
+
+impl WeightedSamplers{
+    fn new(filter: &DealFilter, known: &[(Side, CardSet)]) -> Result<Self, DistributionError>{
+        let samplers: Vec<UniformDealSampler> = filter.constraints()?.into_iter()
+            .filter_map(|mut c| known.iter().try_for_each(|(side, cards)| c.fix_cards(*side, cards)).ok().map(|_| c))
+            .map(|c| UniformDealSampler::new(&c))
+            .collect();
+        let mut cumulative = Vec::with_capacity(samplers.len());
+        let mut total = 0u128;
+        for sampler in samplers.iter(){
+            total += sampler.count_layouts();
+            cumulative.push(total);
+        }
+        match total{
+            0 => Err(DistributionError::NoConsistentLayout),
+            _ => Ok(Self{samplers, cumulative})
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError>{
+        let total = self.cumulative.last().copied().unwrap_or(0);
+        let pick = rng.gen_range(0..total);
+        self.samplers[self.cumulative.partition_point(|c| *c <= pick)].sample(rng)
+    }
+}
+
+/// Lazily built samplers of generator, cache is not cloned nor compared
+#[derive(Debug, Default)]
+struct SamplerCache(OnceLock<Result<WeightedSamplers, DistributionError>>);
+
+impl Clone for SamplerCache{
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for SamplerCache{
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SamplerCache{}
+
+impl DealGenerator{
+    pub fn new(filter: DealFilter) -> Self{
+        Self{filter, known: Vec::new(), max_attempts: 100_000, samplers: SamplerCache::default()}
+    }
+    /// Cards known to be in side's hand (e.g. own hand or dummy), they are placed in every generated deal.
+    /// Shape alternatives of filter inconsistent with known cards are skipped.
+    /// ```
+    /// use brydz_core::deal::DealFilter;
+    /// use brydz_core::player::side::Side::North;
+    /// use karty::hand::HAND_OF_SPADES;
+    /// let filter: DealFilter = "East 5+ hearts".parse().unwrap();
+    /// let deals = filter.generator().known_cards(North, HAND_OF_SPADES).generate_many(5, &mut rand::thread_rng()).unwrap();
+    /// assert!(deals.iter().all(|d| filter.matches(d) && d[&North] == HAND_OF_SPADES));
+    /// ```
+    pub fn known_cards(mut self, side: Side, cards: CardSet) -> Self{
+        self.known.push((side, cards));
+        self.samplers = SamplerCache::default();
+        self
+    }
+    /// Limit of rejected deals while generating one deal (default 100 000)
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self{
+        self.max_attempts = max_attempts;
+        self
+    }
+    pub fn filter(&self) -> &DealFilter{
+        &self.filter
+    }
+
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError>{
+        self.generate_many(1, rng).map(|mut deals| deals.remove(0))
+    }
+
+    /// Checks that some layout of cards is consistent with filter and known cards.
+    /// Deals matching layouts are not searched, so generation may still fail for filter on points.
+    /// ```
+    /// use brydz_core::deal::DealFilter;
+    /// let filter: DealFilter = "North 7+ hearts, South 7+ hearts".parse().unwrap();
+    /// assert!(filter.generator().validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), DistributionError>{
+        self.weighted_samplers().map(|_| ())
+    }
+
+    fn weighted_samplers(&self) -> Result<&WeightedSamplers, DistributionError>{
+        self.samplers.0.get_or_init(|| WeightedSamplers::new(&self.filter, &self.known)).as_ref().map_err(|e| e.clone())
+    }
+
+    /// Generates number of deals, suit allocation is computed once for generator
+    /// ```
+    /// use brydz_core::deal::{DealFilter, ShapePattern};
+    /// use brydz_core::player::side::Side::*;
+    /// // rare: both North and South hold 7-6-0-0
+    /// let filter = DealFilter::new().shape(North, ShapePattern::any([7, 6, 0, 0])).shape(South, ShapePattern::any([7, 6, 0, 0]));
+    /// let deals = filter.generator().generate_many(10, &mut rand::thread_rng()).unwrap();
+    /// assert!(deals.iter().all(|d| filter.matches(d)));
+    /// ```
+    pub fn generate_many<R: Rng + ?Sized>(&self, number: usize, rng: &mut R) -> Result<Vec<SideMap<CardSet>>, DistributionError>{
+        let samplers = self.weighted_samplers()?;
+        let mut deals = Vec::with_capacity(number);
+        while deals.len() < number{
+            let mut attempts = 0;
+            let deal = loop{
+                if attempts >= self.max_attempts{
+                    return Err(DistributionError::NoConsistentLayout)
+                }
+                attempts += 1;
+                let deal = samplers.sample(rng)?;
+                if self.filter.matches(&deal){
+                    break deal
+                }
+            };
+            deals.push(deal);
+        }
+        Ok(deals)
+    }
+}
+
+impl Distribution<SideMap<CardSet>> for DealGenerator{
+    /// # Panics
+    /// When no deal satisfying filter is found (see [`DealGenerator::generate`] for fallible version).
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SideMap<CardSet> {
+        match self.generate(rng){
+            Ok(deal) => deal,
+            Err(e) => panic!("No deal satisfying filter {} was generated: {e:?}", self.filter)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::hand::{CardSet, HandTrait};
+    use karty::cards::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::deal::{DealFilter, HandCondition, ShapePattern};
+    use crate::player::side::Side::{East, North, West};
+
+    #[test]
+    fn hand_conditions(){
+        let mut hand = CardSet::empty();
+        for card in [ACE_SPADES, KING_SPADES, FIVE_SPADES, FOUR_SPADES, KING_HEARTS, QUEEN_HEARTS, TWO_HEARTS,
+            ACE_DIAMONDS, THREE_DIAMONDS, QUEEN_CLUBS, JACK_CLUBS, TEN_CLUBS, TWO_CLUBS]{
+            hand.insert_card(card).unwrap();
+        }
+        assert!(HandCondition::Hcp(19..=19).matches(&hand));
+        assert!(HandCondition::Controls(6..=6).matches(&hand));
+        assert!(HandCondition::Losers(0..=5).matches(&hand));
+        assert!(HandCondition::Balanced.matches(&hand));
+        assert!(ShapePattern::Exact([4, 3, 2, 4]).matches(&hand));
+    }
+
+    #[test]
+    fn parse_and_display_round_trip(){
+        let filter: DealFilter = "north 20-21 hcp 4333 ; East 6+ s 2- controls,W 5=4=2=2 8- losers 10 hcp".parse().unwrap();
+        assert_eq!(filter, DealFilter::new()
+            .hcp(North, 20..=21).shape(North, ShapePattern::Any([4, 3, 3, 3]))
+            .suit_length(East, karty::suits::Suit::Spades, 6..=13).controls(East, 0..=2)
+            .shape(West, ShapePattern::Exact([5, 4, 2, 2])).losers(West, 0..=8).hcp(West, 10..=10));
+        assert_eq!(filter.to_string().parse::<DealFilter>().unwrap(), filter);
+        assert!("North".parse::<DealFilter>().is_err());
+        assert!("North 5+ hcpx".parse::<DealFilter>().is_err());
+        assert_eq!("".parse::<DealFilter>().unwrap(), DealFilter::new());
+    }
+
+    #[test]
+    fn generates_rare_deals(){
+        let filter: DealFilter = "North 22+ hcp 4-4-4-1, South 7+ hearts 4- hcp".parse().unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let deals = filter.generator().generate_many(5, &mut rng).unwrap();
+        assert!(deals.iter().all(|d| filter.matches(d)));
+        let impossible: DealFilter = "North 7+ hearts, South 7+ hearts".parse().unwrap();
+        assert!(impossible.generator().generate(&mut rng).is_err());
+    }
+
+    #[test]
+    fn known_cards_rebuild_samplers(){
+        let mut rng = StdRng::seed_from_u64(14);
+        let generator = "South 5+ hearts".parse::<DealFilter>().unwrap().generator();
+        assert!(generator.generate_many(3, &mut rng).is_ok());
+        let generator = generator.known_cards(North, karty::hand::HAND_OF_SPADES);
+        let deals = generator.generate_many(3, &mut rng).unwrap();
+        assert!(deals.iter().all(|d| d[&North] == karty::hand::HAND_OF_SPADES && generator.filter().matches(d)));
+        assert_eq!(generator.clone(), generator);
+    }
+}
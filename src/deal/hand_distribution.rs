@@ -1,5 +1,6 @@
 use karty::symbol::CardSymbol;
 use rand::{prelude::SliceRandom, Rng, thread_rng};
+use rand::distributions::Distribution;
 
 use karty::cards::STANDARD_DECK;
 use karty::hand::{CardSet, HandTrait};
@@ -46,16 +47,29 @@ pub struct HandDistribution{
 ///
 /// ```
 pub fn fair_bridge_deal<H: HandTrait>() -> SideMap<H>{
+    fair_bridge_deal_with_rng(&mut thread_rng())
+}
+
+/// Creates fair distribution of cards like [`fair_bridge_deal`] using provided random generator
+/// ```
+/// use brydz_core::deal::fair_bridge_deal_with_rng;
+/// use karty::hand::CardSet;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let first = fair_bridge_deal_with_rng::<CardSet, _>(&mut StdRng::seed_from_u64(17));
+/// let second = fair_bridge_deal_with_rng::<CardSet, _>(&mut StdRng::seed_from_u64(17));
+/// assert_eq!(first, second);
+/// ```
+pub fn fair_bridge_deal_with_rng<H: HandTrait, R: Rng + ?Sized>(rng: &mut R) -> SideMap<H>{
     let mut result = SideMap::<H>{
         north: H::empty(),
         east: H::empty(),
         south: H::empty(),
         west: H::empty(),
     };
-    let mut rng = thread_rng();
     let mut v  = Vec::from_iter(H::CardType::iterator()); 
     
-    v.shuffle(&mut rng);
+    v.shuffle(rng);
     let hand_size = v.len()/4;
     /*let north = &v[..hand_size];
     let east = &v[hand_size..2*hand_size];
@@ -79,6 +93,16 @@ pub fn fair_bridge_deal<H: HandTrait>() -> SideMap<H>{
 }
 
 
+/// Fair distribution of standard deck, sampled with [`distribute_standard_deck_on_4`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FairDeal;
+
+impl Distribution<SideMap<CardSet>> for FairDeal{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SideMap<CardSet> {
+        distribute_standard_deck_on_4(rng)
+    }
+}
+
 pub fn distribute_standard_deck_on_4<R: Rng + ?Sized>(rng: &mut R) -> SideMap<CardSet>{
     let mut cards = STANDARD_DECK;
     let mut result = SideMap::<CardSet>::new_symmetric(CardSet::empty());
@@ -128,7 +152,12 @@ pub fn distribute_standard_deck_on_4<R: Rng + ?Sized>(rng: &mut R) -> SideMap<Ca
 /// assert_eq!(hands[&West].len(), 3);
 /// assert_eq!(hands[&South].len(), 2);
 /// ```
-pub fn fair_bridge_partial_deal<H: HandTrait>(mut card_supply: Vec<H::CardType>, first_side: Side ) -> SideMap<H> {
+pub fn fair_bridge_partial_deal<H: HandTrait>(card_supply: Vec<H::CardType>, first_side: Side ) -> SideMap<H> {
+    fair_bridge_partial_deal_with_rng(card_supply, first_side, &mut thread_rng())
+}
+
+/// Distributes card supply like [`fair_bridge_partial_deal`] using provided random generator
+pub fn fair_bridge_partial_deal_with_rng<H: HandTrait, R: Rng + ?Sized>(mut card_supply: Vec<H::CardType>, first_side: Side, rng: &mut R) -> SideMap<H> {
     let mut result = SideMap::<H>{
         north: H::empty(),
         east: H::empty(),
@@ -149,8 +178,7 @@ pub fn fair_bridge_partial_deal<H: HandTrait>(mut card_supply: Vec<H::CardType>,
             tmp
         }
     };
-    let mut rng = thread_rng();
-    card_supply.shuffle(&mut rng);
+    card_supply.shuffle(rng);
 
     for _ in 0..hand_sizes[&North]{
         result.north.insert_card(card_supply.pop().unwrap()).unwrap();
@@ -1,4 +1,9 @@
 mod hand_distribution;
+mod board;
+mod constrained;
+mod filter;
+mod numbering;
+mod stream;
 #[cfg(feature = "amfiteatr")]
 mod biased_hand_distribution;
 #[cfg(feature = "amfiteatr")]
@@ -11,6 +16,11 @@ mod deal_distribution;
 //pub mod hand;
 
 pub use hand_distribution::*;
+pub use board::*;
+pub use constrained::*;
+pub use filter::*;
+pub use numbering::*;
+pub use stream::*;
 #[cfg(feature = "amfiteatr")]
 pub use biased_hand_distribution::*;
 #[cfg(feature = "amfiteatr")]
@@ -0,0 +1,180 @@
+//! Numbering of deals: bijection between full deals and integers in `[0, DEAL_COUNT)`.
+//!
+//! Pavlicek's numbering is used. Cards are taken from the ace of spades down to the two of clubs;
+//! every card splits the remaining range of indices among sides (in order North, East, South, West)
+//! proportionally to their vacant places. Index 0 gives all spades to North, hearts to East, diamonds
+//! to South and clubs to West. Index fits in 96 bits.
+//! ```
+//! use brydz_core::deal::{deal_at_index, deal_index, random_deal_index};
+//! let index = random_deal_index(&mut rand::thread_rng());
+//! let deal = deal_at_index(index).unwrap();
+//! assert_eq!(deal_index(&deal), Ok(index));
+//! ```
+use std::ops::Range;
+use rand::Rng;
+use karty::cards::Card;
+use karty::symbol::CardSymbol;
+use karty::figures::FIGURES;
+use karty::hand::{CardSet, HandTrait};
+use karty::suits::SUITS;
+use crate::error::DistributionError;
+use crate::meta::HAND_SIZE;
+use crate::player::side::{SideMap, SIDES};
+
+/// Number of distinct deals: 52! / (13!)^4
+pub const DEAL_COUNT: u128 = 53_644_737_765_488_792_839_237_440_000;
+
+/// Cards in order of numbering, from the ace of spades to the two of clubs
+fn numbering_order() -> impl Iterator<Item = Card>{
+    SUITS.into_iter().rev().flat_map(|suit| FIGURES.into_iter().rev().map(move |figure| Card::new(figure, suit)))
+}
+
+/// Index of full deal
+/// ```
+/// use brydz_core::deal::{deal_at_index, deal_index, DEAL_COUNT};
+/// use brydz_core::error::DistributionError;
+/// use karty::cards::{ACE_SPADES, TWO_CLUBS};
+/// use karty::hand::{HandSuitedTrait, HandTrait};
+/// use karty::suits::Suit::Spades;
+/// let full = deal_at_index(0).unwrap();
+/// assert_eq!(full.north.suit_iterator(&Spades).count(), 13);
+/// assert_eq!(deal_index(&full), Ok(0));
+/// let mut reversed = full;
+/// std::mem::swap(&mut reversed.north, &mut reversed.west);
+/// std::mem::swap(&mut reversed.east, &mut reversed.south);
+/// assert_eq!(deal_index(&reversed), Ok(DEAL_COUNT - 1));
+/// let mut broken = full;
+/// broken.north.remove_card(&ACE_SPADES).unwrap();
+/// broken.north.insert_card(TWO_CLUBS).unwrap();
+/// assert_eq!(deal_index(&broken), Err(DistributionError::MissingCard(ACE_SPADES)));
+/// ```
+pub fn deal_index(deal: &SideMap<CardSet>) -> Result<u128, DistributionError>{
+    let lengths = deal.transform(|hand| hand.len());
+    if SIDES.iter().any(|side| lengths[side] != HAND_SIZE){
+        return Err(DistributionError::NotEqualCardNumbers(lengths))
+    }
+    let mut vacant = SideMap::new_symmetric(HAND_SIZE as u128);
+    let mut count = DEAL_COUNT;
+    let mut index = 0u128;
+    for (card, remaining) in numbering_order().zip((1..=Card::SYMBOL_SPACE as u128).rev()){
+        let side = SIDES.iter().find(|side| deal[side].contains(&card))
+            .ok_or(DistributionError::MissingCard(card))?;
+        for before in SIDES.iter().take_while(|s| *s != side){
+            index += count * vacant[before] / remaining;
+        }
+        count = count * vacant[side] / remaining;
+        vacant[side] -= 1;
+    }
+    Ok(index)
+}
+
+/// Deal of given index
+/// ```
+/// use brydz_core::deal::{deal_at_index, DEAL_COUNT};
+/// use brydz_core::error::DistributionError;
+/// use karty::cards::{ACE_SPADES, TWO_CLUBS};
+/// use karty::hand::HandTrait;
+/// let first = deal_at_index(0).unwrap();
+/// assert!(first.north.contains(&ACE_SPADES));
+/// assert!(first.west.contains(&TWO_CLUBS));
+/// let last = deal_at_index(DEAL_COUNT - 1).unwrap();
+/// assert!(last.west.contains(&ACE_SPADES));
+/// assert_eq!(deal_at_index(DEAL_COUNT), Err(DistributionError::IndexOutOfRange(DEAL_COUNT)));
+/// ```
+pub fn deal_at_index(index: u128) -> Result<SideMap<CardSet>, DistributionError>{
+    if index >= DEAL_COUNT{
+        return Err(DistributionError::IndexOutOfRange(index))
+    }
+    let mut deal = SideMap::new_symmetric(CardSet::empty());
+    let mut vacant = SideMap::new_symmetric(HAND_SIZE as u128);
+    let mut count = DEAL_COUNT;
+    let mut rest = index;
+    for (card, remaining) in numbering_order().zip((1..=Card::SYMBOL_SPACE as u128).rev()){
+        for side in SIDES{
+            let share = count * vacant[&side] / remaining;
+            if rest < share{
+                deal[&side].insert_card(card).map_err(|_| DistributionError::CardNotAvailable(card))?;
+                vacant[&side] -= 1;
+                count = share;
+                break;
+            }
+            rest -= share;
+        }
+    }
+    Ok(deal)
+}
+
+/// Index of deal drawn uniformly
+pub fn random_deal_index<R: Rng + ?Sized>(rng: &mut R) -> u128{
+    rng.gen_range(0..DEAL_COUNT)
+}
+
+/// Deals of indices in range (limited to valid indices), in order of index
+/// ```
+/// use brydz_core::deal::{deal_index, deals_in_range, DEAL_COUNT};
+/// let deals: Vec<_> = deals_in_range(DEAL_COUNT - 3..DEAL_COUNT + 10).collect();
+/// assert_eq!(deals.len(), 3);
+/// assert_eq!(deal_index(&deals[2]), Ok(DEAL_COUNT - 1));
+/// ```
+pub fn deals_in_range(range: Range<u128>) -> impl Iterator<Item = SideMap<CardSet>>{
+    (range.start.min(DEAL_COUNT)..range.end.min(DEAL_COUNT))
+        .map(|index| deal_at_index(index).expect("index is limited to valid range"))
+}
+
+/// Part of range of indices assigned to one of `shards` workers. Parts are contiguous, disjoint,
+/// cover the whole range and differ in length by at most one.
+/// # Panics
+/// When `shard >= shards`.
+/// ```
+/// use brydz_core::deal::{shard_range, DEAL_COUNT};
+/// assert_eq!(shard_range(0..10, 0, 3), 0..4);
+/// assert_eq!(shard_range(0..10, 1, 3), 4..7);
+/// assert_eq!(shard_range(0..10, 2, 3), 7..10);
+/// assert_eq!(shard_range(0..DEAL_COUNT, 7, 8).end, DEAL_COUNT);
+/// ```
+pub fn shard_range(range: Range<u128>, shard: usize, shards: usize) -> Range<u128>{
+    assert!(shard < shards, "shard {shard} out of {shards} shards");
+    let length = range.end.saturating_sub(range.start);
+    let (base, extra) = (length / shards as u128, length % shards as u128);
+    let start_of = |i: u128| range.start + i * base + i.min(extra);
+    start_of(shard as u128)..start_of(shard as u128 + 1)
+}
+
+#[cfg(test)]
+mod tests{
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use karty::hand::HandTrait;
+    use crate::deal::{deal_at_index, deal_index, deals_in_range, distribute_standard_deck_on_4, random_deal_index,
+        shard_range, DEAL_COUNT};
+    use crate::meta::HAND_SIZE;
+    use crate::player::side::SIDES;
+
+    #[test]
+    fn round_trip_of_random_deals(){
+        let mut rng = StdRng::seed_from_u64(16);
+        for _ in 0..100{
+            let deal = distribute_standard_deck_on_4(&mut rng);
+            let index = deal_index(&deal).unwrap();
+            assert!(index < DEAL_COUNT);
+            assert_eq!(deal_at_index(index).unwrap(), deal);
+            let index = random_deal_index(&mut rng);
+            assert_eq!(deal_index(&deal_at_index(index).unwrap()), Ok(index));
+        }
+    }
+
+    #[test]
+    fn shards_cover_range(){
+        let range = 1000..1017;
+        let shards: Vec<_> = (0..4).map(|i| shard_range(range.clone(), i, 4)).collect();
+        assert_eq!(shards[0].start, range.start);
+        assert_eq!(shards[3].end, range.end);
+        assert!(shards.windows(2).all(|w| w[0].end == w[1].start));
+        let indices: Vec<u128> = shards.iter().flat_map(|s| deals_in_range(s.clone()))
+            .map(|d| deal_index(&d).unwrap()).collect();
+        assert_eq!(indices, range.collect::<Vec<_>>());
+        for deal in deals_in_range(0..2){
+            assert!(SIDES.iter().all(|s| deal[s].len() == HAND_SIZE));
+        }
+    }
+}
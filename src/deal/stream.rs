@@ -0,0 +1,102 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use karty::hand::CardSet;
+use crate::deal::{distribute_standard_deck_on_4, Board, DealGenerator, FairDeal};
+use crate::error::DistributionError;
+use crate::player::side::SideMap;
+
+/// Source of deals which may fail to produce deal, e.g. generator of deals satisfying unsatisfiable filter
+pub trait DealSource{
+    fn generate_deal<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError>;
+}
+
+impl DealSource for FairDeal{
+    fn generate_deal<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError> {
+        Ok(distribute_standard_deck_on_4(rng))
+    }
+}
+
+impl DealSource for DealGenerator{
+    fn generate_deal<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<SideMap<CardSet>, DistributionError> {
+        self.generate(rng)
+    }
+}
+
+/// Random generator of board in experiment. Depends only on seed of experiment and number of board,
+/// so every board can be reproduced independently of other boards.
+/// ```
+/// use brydz_core::deal::board_rng;
+/// use rand::Rng;
+/// assert_eq!(board_rng(5, 3).gen::<u64>(), board_rng(5, 3).gen::<u64>());
+/// assert_ne!(board_rng(5, 3).gen::<u64>(), board_rng(5, 4).gen::<u64>());
+/// ```
+pub fn board_rng(seed: u64, number: u32) -> StdRng{
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    bytes[8..12].copy_from_slice(&number.to_le_bytes());
+    StdRng::from_seed(bytes)
+}
+
+/// Deterministic sequence of boards: board of every number is dealt from distribution
+/// with generator [`board_rng`] of stream seed and board number. Boards are numbered from 1.
+/// ```
+/// use brydz_core::deal::{DealFilter, DealStream};
+/// let stream = DealStream::fair(2024);
+/// let boards: Vec<_> = stream.clone().take(3).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(boards[2].number(), 3);
+/// assert_eq!(boards[2], stream.board(3).unwrap());
+/// assert_eq!(DealStream::fair(2024).starting_at(3).next(), Some(Ok(boards[2].clone())));
+///
+/// let filter: DealFilter = "North 20+ hcp".parse().unwrap();
+/// let stream = DealStream::new(7, filter.generator());
+/// assert!(filter.matches(stream.board(1).unwrap().deal()));
+///
+/// let impossible: DealFilter = "North 7+ hearts, South 7+ hearts".parse().unwrap();
+/// assert!(DealStream::new(7, impossible.generator()).board(1).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DealStream<D: DealSource = FairDeal>{
+    seed: u64,
+    distribution: D,
+    next_number: u32,
+}
+
+impl DealStream<FairDeal>{
+    /// Stream of fairly dealt boards
+    pub fn fair(seed: u64) -> Self{
+        Self::new(seed, FairDeal)
+    }
+}
+
+impl<D: DealSource> DealStream<D>{
+    pub fn new(seed: u64, distribution: D) -> Self{
+        Self{seed, distribution, next_number: 1}
+    }
+    /// Stream continuing from board of given number
+    pub fn starting_at(mut self, number: u32) -> Self{
+        self.next_number = number;
+        self
+    }
+    pub fn seed(&self) -> u64{
+        self.seed
+    }
+    pub fn distribution(&self) -> &D{
+        &self.distribution
+    }
+    /// Board of given number, the same as yielded by iterator
+    pub fn board(&self, number: u32) -> Result<Board, DistributionError>{
+        self.distribution.generate_deal(&mut board_rng(self.seed, number))
+            .map(|deal| Board::new(number, deal))
+    }
+}
+
+impl<D: DealSource> Iterator for DealStream<D>{
+    type Item = Result<Board, DistributionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let number = self.next_number.checked_add(1)?;
+        let board = self.board(self.next_number);
+        self.next_number = number;
+        Some(board)
+    }
+}
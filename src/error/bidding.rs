@@ -20,7 +20,8 @@ pub enum BiddingErrorGen<S: SuitTrait>{
     ViolatedOrder(Mismatch<Side>),
     BidTooLow(Mismatch<Bid<S>>),
     DoubleOnSameAxis,
-    ReDoubleOnSameAxis
+    ReDoubleOnSameAxis,
+    AuctionFinished
 
 }
 
@@ -7,7 +7,7 @@ use crate::error::bidding::BiddingErrorGen;
 #[cfg(feature= "amfiteatr")]
 use amfiteatr_core::error::{AmfiError};
 #[cfg(feature= "amfiteatr")]
-use crate::amfi::spec::ContractDP;
+use crate::amfi::spec::{BiddingDP, BoardDP, ContractDP};
 
 
 use crate::error::contract::ContractErrorGen;
@@ -46,11 +46,20 @@ pub enum BridgeCoreErrorGen<Card: Card2SymTrait>{
     Distribution(DistributionError),
     Hand(CardSetErrorGen<Card>),
     Format(FormatError),
+    /// Error caused by token with `index` in parsed record
+    AtToken{index: usize, error: Box<BridgeCoreErrorGen<Card>>},
     Custom(String),
 
 
 }
 
+impl<Card: Card2SymTrait> BridgeCoreErrorGen<Card>{
+    /// Wraps error with index of token that caused it
+    pub fn at_token(self, index: usize) -> Self{
+        Self::AtToken{index, error: Box::new(self)}
+    }
+}
+
 impl<Card: Card2SymTrait> Display for BridgeCoreErrorGen<Card> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self{
@@ -78,3 +87,17 @@ impl From<BridgeCoreError> for AmfiError<ContractDP>{
         Self::Game(value)
     }
 }
+
+#[cfg(feature = "amfiteatr")]
+impl From<BridgeCoreError> for AmfiError<BiddingDP>{
+    fn from(value: BridgeCoreError) -> Self {
+        Self::Game(value)
+    }
+}
+
+#[cfg(feature = "amfiteatr")]
+impl From<BridgeCoreError> for AmfiError<BoardDP>{
+    fn from(value: BridgeCoreError) -> Self {
+        Self::Game(value)
+    }
+}
@@ -21,6 +21,10 @@ pub enum ContractErrorGen<Card: Card2SymTrait>{
     UsedExhaustedSuit(Side, Card::Suit),
     IgnoredCalledSuit(Side, Card::Suit),
     CardNotInHand(Side, Card),
+    AuctionNotFinished,
+    ContractClaimed,
+    ClaimRejected(Side, u8),
+    ClaimByDummy(Side),
 
 }
 impl<Card: Card2SymTrait>Display for ContractErrorGen<Card>{
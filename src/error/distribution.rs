@@ -1,6 +1,6 @@
-use karty::{ cards::Card2SymTrait};
+use karty::cards::{Card, Card2SymTrait};
 use crate::error::BridgeCoreErrorGen;
-use crate::player::side::SideMap;
+use crate::player::side::{Side, SideMap};
 #[cfg(feature="speedy")]
 use crate::speedy::{Readable, Writable};
 
@@ -9,7 +9,12 @@ use crate::speedy::{Readable, Writable};
 #[cfg_attr(feature = "speedy", derive(Writable, Readable))]
 pub enum DistributionError{
     TooFewCards(usize),
-    NotEqualCardNumbers(SideMap<usize>)
+    NotEqualCardNumbers(SideMap<usize>),
+    NoConsistentLayout,
+    CardNotAvailable(Card),
+    HandOverflow(Side),
+    MissingCard(Card),
+    IndexOutOfRange(u128)
 }
 
 impl<Card: Card2SymTrait> From<DistributionError> for BridgeCoreErrorGen<Card>{
@@ -8,7 +8,8 @@ use crate::speedy::{Readable, Writable};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "speedy", derive(Writable, Readable))]
 pub enum ScoreError{
-    NegativeTrickNumber
+    NegativeTrickNumber,
+    RubberFinished
 }
 
 impl Display for ScoreError{
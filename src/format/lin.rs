@@ -0,0 +1,331 @@
+//! Reading BBO hand records in LIN format.
+//!
+//! Record is a sequence of `command|value|` tokens. Used commands are `md` (deal and dealer),
+//! `sv` (vulnerability), `mb` (call), `pc` (played card) and `mc` (claimed tricks),
+//! other commands are skipped.
+//! Every error is wrapped in [`BridgeCoreErrorGen::AtToken`] with index of the token
+//! (index of command, starting from 0) that caused it.
+use std::str::FromStr;
+use karty::cards::{Card, Card2SymTrait};
+use karty::figures::parse_figure;
+use karty::hand::{CardSet, HandSuitedTrait, HandTrait};
+use karty::suits::Suit;
+use crate::bidding::Call;
+use crate::contract::{Contract, ContractMechanics};
+use crate::deal::Vulnerability;
+use crate::error::{BridgeCoreError, BridgeCoreErrorGen, ContractErrorGen, FormatError};
+use crate::format::{parse_call_symbol, parse_card_symbol, RecordAuction};
+use crate::player::side::{Side, SideMap, SIDES};
+
+/// Game read from LIN record.
+/// # Example:
+/// ```
+/// use brydz_core::contract::ContractMechanics;
+/// use brydz_core::format::lin::LinRecord;
+/// use karty::hand::HandTrait;
+/// use brydz_core::player::side::Side::*;
+/// let record: LinRecord = "md|3SAKQJT98765432,,,|sv|n|mb|7S|mb|d|mb|p|mb|p|mb|p|pc|H2|mc|13|".parse().unwrap();
+/// assert_eq!(record.dealer(), North);
+/// assert!(record.ns_vulnerable());
+/// assert_eq!(record.deal()[&South].len(), 13);
+/// assert_eq!(record.contract().unwrap().current_trick().count_cards(), 1);
+/// assert_eq!(record.claim(), Some(13));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinRecord{
+    deal: SideMap<CardSet>,
+    dealer: Side,
+    ns_vulnerable: bool,
+    ew_vulnerable: bool,
+    auction: RecordAuction,
+    contract: Option<Contract>,
+    claim: Option<u8>,
+}
+
+impl LinRecord{
+    /// Hands of players, hands not given in record are empty
+    pub fn deal(&self) -> &SideMap<CardSet>{
+        &self.deal
+    }
+    pub fn dealer(&self) -> Side{
+        self.dealer
+    }
+    pub fn ns_vulnerable(&self) -> bool{
+        self.ns_vulnerable
+    }
+    pub fn ew_vulnerable(&self) -> bool{
+        self.ew_vulnerable
+    }
+    pub fn vulnerability(&self) -> Vulnerability{
+        Vulnerability::from_axes(self.ns_vulnerable, self.ew_vulnerable)
+    }
+    pub fn auction(&self) -> &RecordAuction{
+        &self.auction
+    }
+    /// Contract with cards played so far, `None` when auction is not finished or deal was passed out
+    pub fn contract(&self) -> Option<&Contract>{
+        self.contract.as_ref()
+    }
+    /// Number of tricks claimed by declarer
+    pub fn claim(&self) -> Option<u8>{
+        self.claim
+    }
+}
+
+/// Side given by dealer digit (1 - South, 2 - West, 3 - North, 4 - East)
+fn parse_lin_side(c: char) -> Result<Side, FormatError>{
+    match c{
+        '1' => Ok(Side::South),
+        '2' => Ok(Side::West),
+        '3' => Ok(Side::North),
+        '4' => Ok(Side::East),
+        _ => Err(FormatError::DeserializeError)
+    }
+}
+
+/// Parses hand like `SAKQHJT9D32CA2`, every suit letter is followed by figures.
+fn parse_lin_hand(s: &str) -> Result<CardSet, FormatError>{
+    let mut hand = CardSet::empty();
+    let mut suit = None;
+    let mut rest = s.trim();
+    while let Some(c) = rest.chars().next(){
+        let next_suit = match c.to_ascii_uppercase(){
+            'S' => Some(Suit::Spades),
+            'H' => Some(Suit::Hearts),
+            'D' => Some(Suit::Diamonds),
+            'C' => Some(Suit::Clubs),
+            _ => None
+        };
+        match (next_suit, suit){
+            (Some(s), _) => {
+                suit = Some(s);
+                rest = &rest[1..];
+            },
+            (None, Some(s)) => {
+                let (remaining, figure) = parse_figure(rest).map_err(|_| FormatError::DeserializeError)?;
+                hand.insert_card(Card::from_figure_and_suit(figure, s))
+                    .map_err(|_| FormatError::DeserializeError)?;
+                rest = remaining;
+            },
+            (None, None) => return Err(FormatError::DeserializeError)
+        }
+    }
+    Ok(hand)
+}
+
+/// Parses `md` value: dealer digit and hands of South, West, North and East separated with commas.
+/// When only one hand is missing it is completed with remaining cards.
+fn parse_lin_deal(s: &str) -> Result<(Side, SideMap<CardSet>), FormatError>{
+    let mut chars = s.trim().chars();
+    let dealer = parse_lin_side(chars.next().ok_or(FormatError::DeserializeError)?)?;
+    let hands: Vec<&str> = chars.as_str().split(',').collect();
+    if hands.len() > SIDES.len(){
+        return Err(FormatError::DeserializeError)
+    }
+    let mut deal = SideMap::new_symmetric(CardSet::empty());
+    let mut union = CardSet::empty();
+    let mut missing = Vec::new();
+    for (i, side) in [Side::South, Side::West, Side::North, Side::East].iter().enumerate(){
+        let hand = match hands.get(i).map(|h| h.trim()){
+            None | Some("") => {
+                missing.push(*side);
+                continue;
+            },
+            Some(h) => parse_lin_hand(h)?
+        };
+        if !hand.intersection(&union).is_empty(){
+            return Err(FormatError::DeserializeError)
+        }
+        union = union.union(&hand);
+        deal[side] = hand;
+    }
+    if missing.len() == 1 && union.len() == 39{
+        deal[&missing[0]] = CardSet::from(!u64::from(union));
+    }
+    Ok((dealer, deal))
+}
+
+fn parse_lin_vulnerability(s: &str) -> Result<(bool, bool), FormatError>{
+    match s.trim().to_ascii_lowercase().as_str(){
+        "o" | "0" | "-" | "" => Ok((false, false)),
+        "n" => Ok((true, false)),
+        "e" => Ok((false, true)),
+        "b" => Ok((true, true)),
+        _ => Err(FormatError::DeserializeError)
+    }
+}
+
+/// Parses call, alerts (`!`) are ignored.
+fn parse_lin_call(s: &str) -> Result<Call<Suit>, FormatError>{
+    match s.trim().trim_end_matches('!').to_ascii_lowercase().as_str(){
+        "d" => Ok(Call::Double),
+        "r" => Ok(Call::Redouble),
+        call => parse_call_symbol(call)
+    }
+}
+
+/// Builder of record, consuming tokens one by one.
+struct LinReader{
+    deal: Option<SideMap<CardSet>>,
+    remaining: SideMap<CardSet>,
+    dealer: Option<Side>,
+    vulnerability: (bool, bool),
+    auction: RecordAuction,
+    contract: Option<Contract>,
+    claim: Option<u8>,
+}
+
+impl LinReader{
+    fn new() -> Self{
+        Self{deal: None, remaining: SideMap::new_symmetric(CardSet::empty()), dealer: None,
+            vulnerability: (false, false), auction: RecordAuction::new(), contract: None, claim: None}
+    }
+
+    fn read_call(&mut self, value: &str) -> Result<(), BridgeCoreError>{
+        let dealer = self.dealer.ok_or(FormatError::DeserializeError)?;
+        if self.auction.is_finished(){
+            return Err(FormatError::DeserializeError.into())
+        }
+        let side = dealer.next_i(self.auction.calls_entries().len() as u8);
+        self.auction.add_contract_bid(side, parse_lin_call(value)?)?;
+        if self.auction.is_finished(){
+            self.contract = self.auction.current_contract().map(|p| Contract::new(p.clone()));
+        }
+        Ok(())
+    }
+
+    fn read_card(&mut self, value: &str) -> Result<(), BridgeCoreError>{
+        let card = parse_card_symbol(value)?;
+        let deal = self.deal.as_ref().ok_or(FormatError::DeserializeError)?;
+        let contract = self.contract.as_mut().ok_or(FormatError::DeserializeError)?;
+        let side = contract.current_side();
+        //hands not given in record are not checked
+        if !deal[&side].is_empty(){
+            let hand = &mut self.remaining[&side];
+            if !hand.contains(&card){
+                return Err(ContractErrorGen::CardNotInHand(side, card).into())
+            }
+            if let Some(called) = contract.current_trick().called_suit(){
+                if card.suit() != called && hand.contains_in_suit(&called){
+                    return Err(ContractErrorGen::IgnoredCalledSuit(side, called).into())
+                }
+            }
+            hand.remove_card(&card)?;
+        }
+        contract.insert_card(side, card)?;
+        Ok(())
+    }
+
+    fn read_token(&mut self, command: &str, value: &str) -> Result<(), BridgeCoreError>{
+        match command.trim().to_ascii_lowercase().as_str(){
+            "md" => {
+                let (dealer, deal) = parse_lin_deal(value)?;
+                self.dealer = Some(dealer);
+                self.remaining = deal;
+                self.deal = Some(deal);
+            },
+            "sv" => self.vulnerability = parse_lin_vulnerability(value)?,
+            "mb" => self.read_call(value)?,
+            "pc" => self.read_card(value)?,
+            "mc" => {
+                let claim = value.trim().parse::<u8>().map_err(|_| FormatError::DeserializeError)?;
+                if claim > 13 || self.contract.is_none(){
+                    return Err(FormatError::DeserializeError.into())
+                }
+                self.claim = Some(claim);
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for LinRecord{
+    type Err = BridgeCoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pieces: Vec<&str> = s.split('|').collect();
+        let mut reader = LinReader::new();
+        for (index, token) in pieces.chunks(2).enumerate(){
+            match token{
+                [command, value] => reader.read_token(command, value).map_err(|e| e.at_token(index))?,
+                [rest] if rest.trim().is_empty() => {},
+                _ => return Err(BridgeCoreErrorGen::from(FormatError::DeserializeError).at_token(index))
+            }
+        }
+        let (deal, dealer) = match (reader.deal, reader.dealer){
+            (Some(deal), Some(dealer)) => (deal, dealer),
+            _ => return Err(FormatError::DeserializeError.into())
+        };
+        Ok(LinRecord{
+            deal,
+            dealer,
+            ns_vulnerable: reader.vulnerability.0,
+            ew_vulnerable: reader.vulnerability.1,
+            auction: reader.auction,
+            contract: reader.contract,
+            claim: reader.claim,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::cards::{FOUR_CLUBS, KING_CLUBS};
+    use karty::hand::HandTrait;
+    use karty::suits::Suit::Clubs;
+    use crate::bidding::consts::BID_D3;
+    use crate::contract::{ContractMechanics, ContractParametersGen};
+    use crate::error::{BridgeCoreErrorGen, ContractErrorGen, FormatError};
+    use crate::format::lin::LinRecord;
+    use crate::player::side::Side::*;
+
+    const RECORD: &str = "pn|a,b,c,d|st||md|1SAT62HA4DAKQ95C52,S983HJ75D76CAJ986,SQJ5HKQ3DT8432CT3,|sv|e|\
+        mb|1D|mb|p|mb|3D|mb|p|mb|p|mb|p|\
+        pc|CA|pc|C3|pc|C4|pc|C2|pc|CJ|pc|CT|pc|C7|pc|C5|pc|S9|pc|SJ|pc|SK|pc|SA|\
+        pc|DA|pc|D6|pc|D2|pc|DJ|pc|D5|pc|D7|pc|DT|pc|H2|pc|SQ|pc|S4|pc|S2|pc|S3|\
+        pc|S5|pc|S7|pc|ST|pc|S8|pc|HA|pc|H5|pc|H3|pc|H6|pc|H4|pc|H7|pc|HQ|pc|H8|\
+        pc|HK|pc|H9|pc|S6|pc|HJ|pc|D3|pc|HT|pc|DK|pc|C6|pc|DQ|pc|C8|pc|D4|pc|CQ|\
+        pc|D9|pc|C9|pc|D8|pc|CK|";
+
+    #[test]
+    fn parse_full_record(){
+        let record: LinRecord = RECORD.parse().unwrap();
+        assert_eq!(record.dealer(), South);
+        assert_eq!((record.ns_vulnerable(), record.ew_vulnerable()), (false, true));
+        assert_eq!(record.deal()[&East].len(), 13);
+        assert!(record.deal()[&East].contains(&KING_CLUBS));
+        assert_eq!(record.auction().calls_entries().len(), 6);
+        let contract = record.contract().unwrap();
+        assert_eq!(contract.contract_spec(), &ContractParametersGen::new(South, BID_D3));
+        assert!(contract.is_completed());
+        assert_eq!(contract.total_tricks_taken_axis(South.axis()), 11);
+    }
+
+    #[test]
+    fn claim_and_passed_out(){
+        let claimed: LinRecord = RECORD.replace("pc|DA|", "mc|11|pc|DA|").split("pc|DA|").next().unwrap().parse().unwrap();
+        assert_eq!(claimed.claim(), Some(11));
+        assert_eq!(claimed.contract().unwrap().count_completed_tricks(), 3);
+        let passed: LinRecord = "md|4,,,|mb|p|mb|p|mb|p|mb|p|".parse().unwrap();
+        assert_eq!(passed.dealer(), East);
+        assert!(passed.contract().is_none());
+        assert!(passed.deal()[&North].is_empty());
+    }
+
+    #[test]
+    fn errors_carry_token_index(){
+        //tokens: pn 0, st 1, md 2, sv 3, 1D 4, p 5, 3D 6
+        assert!(matches!(RECORD.replace("mb|3D|", "mb|1C|").parse::<LinRecord>(),
+            Err(BridgeCoreErrorGen::AtToken{index: 6, error}) if matches!(*error, BridgeCoreErrorGen::Bidding(_))));
+        //first card is token 10
+        assert_eq!(RECORD.replace("pc|CA|", "pc|CK|").parse::<LinRecord>().unwrap_err(),
+            BridgeCoreErrorGen::Contract(ContractErrorGen::CardNotInHand(West, KING_CLUBS)).at_token(10));
+        assert_eq!(RECORD.replace("pc|C4|", "pc|H2|").parse::<LinRecord>().unwrap_err(),
+            BridgeCoreErrorGen::Contract(ContractErrorGen::IgnoredCalledSuit(East, Clubs)).at_token(12));
+        assert_eq!(RECORD.replace("pc|C4|pc|C2|", "pc|C4|pc|C4|").parse::<LinRecord>().unwrap_err(),
+            BridgeCoreErrorGen::Contract(ContractErrorGen::CardNotInHand(South, FOUR_CLUBS)).at_token(13));
+        assert_eq!(RECORD.replace("mb|p|mb|p|mb|p|pc", "mb|p|mb|p|mb|p|mb|p|pc").parse::<LinRecord>().unwrap_err(),
+            BridgeCoreErrorGen::Format(FormatError::DeserializeError).at_token(10));
+    }
+}
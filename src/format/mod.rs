@@ -0,0 +1,113 @@
+//! Import and export of deals and games in bridge record formats.
+pub mod pbn;
+pub mod lin;
+
+use karty::cards::{Card, Card2SymTrait};
+use karty::cards::parse::parse_card_sf;
+use karty::figures::parse_figure;
+use karty::hand::{CardSet, HandTrait};
+use karty::suits::Suit;
+use karty::symbol::CardSymbol;
+use crate::bidding::{AuctionStack, Bid, Call, GeneralDeclarationStorage};
+use crate::bidding::parser::parse_call;
+use crate::cards::trump::TrumpGen;
+use crate::error::FormatError;
+use crate::player::side::Side;
+
+/// Auction read from or written to game record
+pub type RecordAuction = AuctionStack<Suit, GeneralDeclarationStorage<Suit>>;
+
+/// Symbols of figures in index order (from two to ace)
+const FIGURE_SYMBOLS: [char; 13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+/// Symbols of suits in index order (from clubs to spades)
+const SUIT_SYMBOLS: [char; 4] = ['C', 'D', 'H', 'S'];
+
+pub(crate) fn side_symbol(side: Side) -> char{
+    match side{
+        Side::North => 'N',
+        Side::East => 'E',
+        Side::South => 'S',
+        Side::West => 'W'
+    }
+}
+
+/// Parses side given by first letter or full name (case insensitive).
+pub(crate) fn parse_side_symbol(s: &str) -> Result<Side, FormatError>{
+    match s.trim().to_ascii_uppercase().as_str(){
+        "N" | "NORTH" => Ok(Side::North),
+        "E" | "EAST" => Ok(Side::East),
+        "S" | "SOUTH" => Ok(Side::South),
+        "W" | "WEST" => Ok(Side::West),
+        _ => Err(FormatError::DeserializeError)
+    }
+}
+
+/// Card written as suit letter followed by figure letter, e.g. `SA` or `HT`.
+pub(crate) fn card_symbol(card: &Card) -> String{
+    format!("{}{}", SUIT_SYMBOLS[card.suit().usize_index()], FIGURE_SYMBOLS[card.figure().usize_index()])
+}
+
+/// Parses whole token as card in suit-figure order.
+pub(crate) fn parse_card_symbol(s: &str) -> Result<Card, FormatError>{
+    match parse_card_sf(s.trim()){
+        Ok(("", card)) => Ok(card),
+        _ => Err(FormatError::DeserializeError)
+    }
+}
+
+pub(crate) fn trump_symbol(trump: &TrumpGen<Suit>) -> String{
+    match trump{
+        TrumpGen::Colored(s) => SUIT_SYMBOLS[s.usize_index()].to_string(),
+        TrumpGen::NoTrump => String::from("NT")
+    }
+}
+
+pub(crate) fn bid_symbol(bid: &Bid<Suit>) -> String{
+    format!("{}{}", bid.number(), trump_symbol(bid.trump()))
+}
+
+/// Call written as bid symbol, `X`, `XX` or `Pass`.
+pub(crate) fn call_symbol(call: &Call<Suit>) -> String{
+    match call{
+        Call::NewBid(bid) => bid_symbol(bid),
+        Call::Double => String::from("X"),
+        Call::Redouble => String::from("XX"),
+        Call::Pass => String::from("Pass")
+    }
+}
+
+/// Parses whole token as call.
+pub(crate) fn parse_call_symbol(s: &str) -> Result<Call<Suit>, FormatError>{
+    match parse_call(s.trim()){
+        Ok(("", call)) => Ok(call),
+        _ => Err(FormatError::DeserializeError)
+    }
+}
+
+/// Hand as four groups of figures (spades, hearts, diamonds, clubs) separated with dots.
+pub(crate) fn hand_symbol(hand: &CardSet) -> String{
+    [3usize, 2, 1, 0].iter().map(|suit|{
+        hand.into_iter().filter(|c| c.suit().usize_index() == *suit)
+            .map(|c| FIGURE_SYMBOLS[c.figure().usize_index()])
+            .rev().collect::<String>()
+    }).collect::<Vec<_>>().join(".")
+}
+
+/// Parses hand written as four groups of figures (spades, hearts, diamonds, clubs) separated with dots.
+pub(crate) fn parse_hand_symbol(s: &str) -> Result<CardSet, FormatError>{
+    let groups: Vec<&str> = s.trim().split('.').collect();
+    if groups.len() != 4{
+        return Err(FormatError::DeserializeError)
+    }
+    let mut hand = CardSet::empty();
+    for (group, suit) in groups.iter().zip([Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]){
+        let mut rest = *group;
+        while !rest.is_empty(){
+            let (remaining, figure) = parse_figure(rest).map_err(|_| FormatError::DeserializeError)?;
+            hand.insert_card(Card::from_figure_and_suit(figure, suit))
+                .map_err(|_| FormatError::DeserializeError)?;
+            rest = remaining;
+        }
+    }
+    Ok(hand)
+}
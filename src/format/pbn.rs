@@ -0,0 +1,660 @@
+//! Reading and writing games in PBN ([Portable Bridge Notation](https://www.tistis.nl/pbn/)).
+//!
+//! Supported tags are `Dealer`, `Vulnerable`, `Deal`, `Declarer`, `Contract`, `Result`,
+//! `Auction` and `Play`, other tags are skipped.
+//! Syntax errors are reported as [`FormatError::DeserializeError`], illegal calls and plays
+//! are reported with errors of [`AuctionStack`] and [`Contract`].
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use karty::cards::Card;
+use karty::hand::{CardSet, HandTrait};
+use nom::bytes::complete::{is_not, take_while1};
+use nom::character::complete::{char, space0, space1};
+use nom::sequence::{delimited, preceded, separated_pair, terminated};
+use nom::IResult;
+use crate::bidding::{Call, Doubling};
+use crate::bidding::parser::parse_bid;
+use crate::contract::{Contract, ContractMechanics, ContractParameters, ContractParametersGen};
+use crate::deal::Vulnerability;
+use crate::error::{BridgeCoreError, ContractErrorGen, FormatError};
+use crate::format::{RecordAuction, bid_symbol, call_symbol, card_symbol, hand_symbol, parse_call_symbol, parse_card_symbol, parse_hand_symbol, parse_side_symbol, side_symbol};
+use crate::player::side::{Side, SideMap, SIDES};
+
+/// Single game of PBN file: deal, auction, contract and play.
+/// # Example:
+/// ```
+/// use brydz_core::format::pbn::PbnGame;
+/// use brydz_core::contract::ContractMechanics;
+/// use brydz_core::player::side::Side::*;
+/// let game: PbnGame = "[Dealer \"N\"]
+/// [Vulnerable \"NS\"]
+/// [Deal \"N:AKQJT98765432... ...AKQJT98765432 .AKQJT98765432.. -\"]
+/// [Declarer \"N\"]
+/// [Contract \"7SX\"]
+/// [Result \"13\"]
+/// [Auction \"N\"]
+/// 7S X Pass Pass
+/// Pass
+/// [Play \"E\"]
+/// CA - - -
+/// *".parse().unwrap();
+/// assert_eq!(game.contract_parameters().unwrap().declarer(), North);
+/// assert!(game.ns_vulnerable());
+/// assert_eq!(game.play().unwrap().current_trick().count_cards(), 1);
+/// assert_eq!(game.result(), Some(13));
+/// assert_eq!(game.to_string().parse::<PbnGame>().unwrap(), game);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PbnGame{
+    deal: SideMap<CardSet>,
+    ns_vulnerable: bool,
+    ew_vulnerable: bool,
+    dealer: Option<Side>,
+    auction: Option<RecordAuction>,
+    contract_parameters: Option<ContractParameters>,
+    passed_out: bool,
+    play: Option<Contract>,
+    result: Option<u8>,
+}
+
+impl PbnGame{
+    pub fn new(deal: SideMap<CardSet>) -> Self{
+        Self{deal, ns_vulnerable: false, ew_vulnerable: false, dealer: None, auction: None,
+            contract_parameters: None, passed_out: false, play: None, result: None}
+    }
+
+    pub fn with_vulnerability(mut self, ns_vulnerable: bool, ew_vulnerable: bool) -> Self{
+        self.ns_vulnerable = ns_vulnerable;
+        self.ew_vulnerable = ew_vulnerable;
+        self
+    }
+
+    /// Sets auction (and contract resulting from it), `dealer` is the side that made first call.
+    pub fn with_auction(mut self, dealer: Side, auction: RecordAuction) -> Self{
+        self.dealer = Some(dealer);
+        self.contract_parameters = auction.current_contract().cloned();
+        self.passed_out = self.contract_parameters.is_none() && auction.is_finished();
+        self.auction = Some(auction);
+        self
+    }
+
+    pub fn with_contract_parameters(mut self, parameters: ContractParameters) -> Self{
+        self.contract_parameters = Some(parameters);
+        self.passed_out = false;
+        self
+    }
+
+    /// Sets played cards (and contract parameters). When contract is completed result is set too.
+    pub fn with_play(mut self, contract: Contract) -> Self{
+        self.contract_parameters = Some(contract.contract_spec().clone());
+        self.passed_out = false;
+        if contract.is_completed(){
+            self.result = Some(contract.total_tricks_taken_axis(contract.declarer().axis()) as u8);
+        }
+        self.play = Some(contract);
+        self
+    }
+
+    /// Sets number of tricks taken by declarer
+    pub fn with_result(mut self, result: u8) -> Self{
+        self.result = Some(result);
+        self
+    }
+
+    pub fn deal(&self) -> &SideMap<CardSet>{
+        &self.deal
+    }
+    pub fn ns_vulnerable(&self) -> bool{
+        self.ns_vulnerable
+    }
+    pub fn ew_vulnerable(&self) -> bool{
+        self.ew_vulnerable
+    }
+    pub fn vulnerability(&self) -> Vulnerability{
+        Vulnerability::from_axes(self.ns_vulnerable, self.ew_vulnerable)
+    }
+    pub fn dealer(&self) -> Option<Side>{
+        self.dealer
+    }
+    pub fn auction(&self) -> Option<&RecordAuction>{
+        self.auction.as_ref()
+    }
+    pub fn contract_parameters(&self) -> Option<&ContractParameters>{
+        self.contract_parameters.as_ref()
+    }
+    /// Returns `true` when deal was passed out (contract is `Pass`)
+    pub fn is_passed_out(&self) -> bool{
+        self.passed_out
+    }
+    /// Contract with cards played so far
+    pub fn play(&self) -> Option<&Contract>{
+        self.play.as_ref()
+    }
+    pub fn result(&self) -> Option<u8>{
+        self.result
+    }
+}
+
+impl PartialEq for PbnGame{
+    /// Games are equal when all tags are equal, plays are compared trick by trick.
+    fn eq(&self, other: &Self) -> bool {
+        let same_play = match (&self.play, &other.play){
+            (None, None) => true,
+            (Some(a), Some(b)) => a.contract_spec() == b.contract_spec()
+                && a.count_completed_tricks() == b.count_completed_tricks()
+                && (0..a.count_completed_tricks()).all(|i| a[i] == b[i])
+                && a.current_trick() == b.current_trick(),
+            _ => false
+        };
+        same_play && self.deal == other.deal
+            && self.ns_vulnerable == other.ns_vulnerable
+            && self.ew_vulnerable == other.ew_vulnerable
+            && self.dealer == other.dealer
+            && self.auction == other.auction
+            && self.contract_parameters == other.contract_parameters
+            && self.passed_out == other.passed_out
+            && self.result == other.result
+    }
+}
+
+impl Eq for PbnGame{}
+
+/// Parses tag line `[Name "Value"]`
+fn parse_tag(s: &str) -> IResult<&str, (&str, &str)>{
+    delimited(
+        terminated(char('['), space0),
+        separated_pair(
+            take_while1(|c: char| c.is_alphanumeric() || c == '_'),
+            space1,
+            delimited(char('"'), |i| is_not::<_, _, nom::error::Error<&str>>("\"")(i).or(Ok((i, ""))), char('"'))),
+        preceded(space0, char(']')))(s)
+}
+
+/// Removes comments: `{...}` blocks and lines starting with `;` or `%`.
+fn strip_comments(s: &str) -> String{
+    let mut depth = 0usize;
+    let mut stripped = String::with_capacity(s.len());
+    for line in s.lines(){
+        let trimmed = line.trim_start();
+        if depth == 0 && (trimmed.starts_with(';') || trimmed.starts_with('%')){
+            continue;
+        }
+        for c in line.chars(){
+            match c{
+                '{' => depth += 1,
+                '}' if depth > 0 => depth -= 1,
+                c if depth == 0 => stripped.push(c),
+                _ => {}
+            }
+        }
+        stripped.push('\n');
+    }
+    stripped
+}
+
+/// Splits game into tags with tokens of their sections (for `Auction` and `Play`).
+fn split_tags(s: &str) -> Result<Vec<(String, String, Vec<String>)>, FormatError>{
+    let mut tags: Vec<(String, String, Vec<String>)> = Vec::new();
+    for line in s.lines().map(|l| l.trim()).filter(|l| !l.is_empty()){
+        if line.starts_with('['){
+            let (rest, (name, value)) = parse_tag(line).map_err(|_| FormatError::DeserializeError)?;
+            if !rest.trim().is_empty(){
+                return Err(FormatError::DeserializeError)
+            }
+            tags.push((name.to_owned(), value.to_owned(), Vec::new()));
+        } else {
+            match tags.last_mut(){
+                Some((_, _, section)) => section.extend(line.split_whitespace().map(|t| t.to_owned())),
+                None => return Err(FormatError::DeserializeError)
+            }
+        }
+    }
+    Ok(tags)
+}
+
+/// Parses `Deal` tag value, e.g. `N:AKQ.JT9.876.5432 - - -` (`-` is unknown hand).
+fn parse_deal(s: &str) -> Result<SideMap<CardSet>, FormatError>{
+    let (first, hands) = s.trim().split_once(':').ok_or(FormatError::DeserializeError)?;
+    let first = parse_side_symbol(first)?;
+    let hands: Vec<&str> = hands.split_whitespace().collect();
+    if hands.len() != SIDES.len(){
+        return Err(FormatError::DeserializeError)
+    }
+    let mut deal = SideMap::new_symmetric(CardSet::empty());
+    let mut union = CardSet::empty();
+    for (i, hand) in hands.iter().enumerate(){
+        let cards = match *hand{
+            "-" => CardSet::empty(),
+            h => parse_hand_symbol(h)?
+        };
+        if !cards.intersection(&union).is_empty(){
+            return Err(FormatError::DeserializeError)
+        }
+        union = union.union(&cards);
+        deal[&first.next_i(i as u8)] = cards;
+    }
+    Ok(deal)
+}
+
+fn write_deal(deal: &SideMap<CardSet>) -> String{
+    let hands: Vec<String> = SIDES.iter().map(|s| match deal[s].is_empty(){
+        true => String::from("-"),
+        false => hand_symbol(&deal[s])
+    }).collect();
+    format!("N:{}", hands.join(" "))
+}
+
+fn parse_vulnerability(s: &str) -> Result<(bool, bool), FormatError>{
+    match s.trim().to_ascii_uppercase().as_str(){
+        "NONE" | "LOVE" | "-" => Ok((false, false)),
+        "NS" => Ok((true, false)),
+        "EW" => Ok((false, true)),
+        "ALL" | "BOTH" => Ok((true, true)),
+        _ => Err(FormatError::DeserializeError)
+    }
+}
+
+fn write_vulnerability(ns: bool, ew: bool) -> &'static str{
+    match (ns, ew){
+        (false, false) => "None",
+        (true, false) => "NS",
+        (false, true) => "EW",
+        (true, true) => "All"
+    }
+}
+
+/// Parses contract like `4HX`, returns `None` for `Pass`
+fn parse_contract(s: &str, declarer: Option<Side>) -> Result<Option<ContractParameters>, FormatError>{
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("pass"){
+        return Ok(None)
+    }
+    let (rest, bid) = parse_bid(s).map_err(|_| FormatError::DeserializeError)?;
+    let doubling = match rest.to_ascii_uppercase().as_str(){
+        "" => Doubling::None,
+        "X" => Doubling::Double,
+        "XX" => Doubling::Redouble,
+        _ => return Err(FormatError::DeserializeError)
+    };
+    let declarer = declarer.ok_or(FormatError::DeserializeError)?;
+    Ok(Some(ContractParametersGen::new_d(declarer, bid, doubling)))
+}
+
+fn write_contract(parameters: &ContractParameters) -> String{
+    let doubling = match parameters.doubling(){
+        Doubling::None => "",
+        Doubling::Double => "X",
+        Doubling::Redouble => "XX"
+    };
+    format!("{}{}", bid_symbol(parameters.bid()), doubling)
+}
+
+
+/// Replays auction section, notes (`=1=`) and suffixes (`!`, `?`) are skipped.
+fn parse_auction(dealer: Side, tokens: &[String]) -> Result<RecordAuction, BridgeCoreError>{
+    let mut auction = RecordAuction::new();
+    let mut side = dealer;
+    for token in tokens{
+        if token.starts_with('=') || token == "-"{
+            continue;
+        }
+        if token == "*"{
+            break;
+        }
+        if auction.is_finished(){
+            return Err(FormatError::DeserializeError.into())
+        }
+        if token.eq_ignore_ascii_case("AP"){
+            while !auction.is_finished(){
+                auction.add_contract_bid(side, Call::Pass)?;
+                side = side.next();
+            }
+            continue;
+        }
+        let call = parse_call_symbol(token.trim_end_matches(['!', '?']))?;
+        auction.add_contract_bid(side, call)?;
+        side = side.next();
+    }
+    Ok(auction)
+}
+
+/// Replays play section. Every line holds one trick, columns are sides starting from `first`.
+/// Play ends on `*` or on first missing card (`-`).
+fn parse_play(first: Side, tokens: &[String], parameters: &ContractParameters, deal: &SideMap<CardSet>) -> Result<Contract, BridgeCoreError>{
+    let mut contract = Contract::new(parameters.clone());
+    let tokens: Vec<&String> = tokens.iter().take_while(|t| *t != "*").collect();
+    let mut finished = false;
+    for trick in tokens.chunks(SIDES.len()){
+        let mut columns: [Option<Card>; 4] = [None; 4];
+        for (column, token) in columns.iter_mut().zip(trick.iter()){
+            if *token != "-"{
+                *column = Some(parse_card_symbol(token)?);
+            }
+        }
+        let leader = contract.current_side();
+        for i in 0..SIDES.len() as u8{
+            let side = leader.next_i(i);
+            match columns[(side - first) as usize]{
+                Some(card) => {
+                    if finished{
+                        return Err(FormatError::DeserializeError.into())
+                    }
+                    if !deal[&side].is_empty() && !deal[&side].contains(&card){
+                        return Err(ContractErrorGen::CardNotInHand(side, card).into())
+                    }
+                    contract.insert_card(side, card)?;
+                },
+                None => finished = true
+            }
+        }
+    }
+    Ok(contract)
+}
+
+fn write_play(contract: &Contract) -> Vec<String>{
+    let first = contract.contract_spec().whist();
+    let mut lines = Vec::new();
+    let current = contract.current_trick();
+    let tricks = (0..contract.count_completed_tricks()).map(|i| &contract[i])
+        .chain(std::iter::once(current).filter(|t| t.count_cards() > 0));
+    for trick in tricks{
+        lines.push((0..SIDES.len() as u8).map(|i| match trick[first.next_i(i)]{
+            Some(card) => card_symbol(&card),
+            None => String::from("-")
+        }).collect::<Vec<_>>().join(" "));
+    }
+    if !contract.is_completed(){
+        lines.push(String::from("*"));
+    }
+    lines
+}
+
+impl FromStr for PbnGame{
+    type Err = BridgeCoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        game_from_tags(&split_tags(&strip_comments(s))?)
+    }
+}
+
+/// Builds game from tags split by [`split_tags`]
+fn game_from_tags(tags: &[(String, String, Vec<String>)]) -> Result<PbnGame, BridgeCoreError>{
+    let find = |name: &str| tags.iter().find(|(n, _, _)| n == name);
+    let deal = match find("Deal"){
+        Some((_, value, _)) => parse_deal(value)?,
+        None => return Err(FormatError::DeserializeError.into())
+    };
+    let mut game = PbnGame::new(deal);
+    if let Some((_, value, _)) = find("Vulnerable"){
+        let (ns, ew) = parse_vulnerability(value)?;
+        game = game.with_vulnerability(ns, ew);
+    }
+    if let Some((_, value, _)) = find("Dealer"){
+        game.dealer = Some(parse_side_symbol(value)?);
+    }
+    if let Some((_, value, tokens)) = find("Auction"){
+        let dealer = parse_side_symbol(value)?;
+        if game.dealer.is_some_and(|d| d != dealer){
+            return Err(FormatError::DeserializeError.into())
+        }
+        game = game.with_auction(dealer, parse_auction(dealer, tokens)?);
+    }
+    let declarer = match find("Declarer").map(|(_, v, _)| v.trim().trim_start_matches('^')){
+        None | Some("") => None,
+        Some(side) => Some(parse_side_symbol(side)?)
+    };
+    if let Some((_, value, _)) = find("Contract"){
+        let parameters = parse_contract(value, declarer)?;
+        //contract given explicitly must agree with auction
+        if game.auction.is_some() && parameters != game.contract_parameters{
+            return Err(FormatError::DeserializeError.into())
+        }
+        game.passed_out = parameters.is_none();
+        game.contract_parameters = parameters;
+    }
+    if let Some((_, value, tokens)) = find("Play"){
+        let first = parse_side_symbol(value)?;
+        let parameters = game.contract_parameters.clone().ok_or(FormatError::DeserializeError)?;
+        if first != parameters.whist(){
+            return Err(FormatError::DeserializeError.into())
+        }
+        let play = parse_play(first, tokens, &parameters, &game.deal)?;
+        game = game.with_play(play);
+    }
+    if let Some((_, value, _)) = find("Result"){
+        if !value.trim().is_empty(){
+            let result = value.trim().parse::<u8>().map_err(|_| FormatError::DeserializeError)?;
+            if game.result.is_some_and(|r| r != result){
+                return Err(FormatError::DeserializeError.into())
+            }
+            game.result = Some(result);
+        }
+    }
+    Ok(game)
+}
+
+impl Display for PbnGame{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(dealer) = self.dealer{
+            writeln!(f, "[Dealer \"{}\"]", side_symbol(dealer))?;
+        }
+        writeln!(f, "[Vulnerable \"{}\"]", write_vulnerability(self.ns_vulnerable, self.ew_vulnerable))?;
+        write!(f, "[Deal \"{}\"]", write_deal(&self.deal))?;
+        if let Some(parameters) = &self.contract_parameters{
+            write!(f, "\n[Declarer \"{}\"]", side_symbol(parameters.declarer()))?;
+            write!(f, "\n[Contract \"{}\"]", write_contract(parameters))?;
+        } else if self.passed_out{
+            write!(f, "\n[Contract \"Pass\"]")?;
+        }
+        if let Some(result) = self.result{
+            write!(f, "\n[Result \"{result}\"]")?;
+        }
+        if let (Some(auction), Some(dealer)) = (&self.auction, self.dealer){
+            write!(f, "\n[Auction \"{}\"]", side_symbol(dealer))?;
+            for line in auction.calls_entries().chunks(SIDES.len()){
+                write!(f, "\n{}", line.iter().map(|e| call_symbol(e.call())).collect::<Vec<_>>().join(" "))?;
+            }
+        }
+        if let Some(contract) = &self.play{
+            write!(f, "\n[Play \"{}\"]", side_symbol(contract.contract_spec().whist()))?;
+            for line in write_play(contract){
+                write!(f, "\n{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tags describing board, game missing them takes them from the previous game in file.
+/// Contract, auction, play and result belong to single table and are never inherited.
+const INHERITED_TAGS: [&str; 7] = ["Event", "Site", "Date", "Board", "Dealer", "Vulnerable", "Deal"];
+
+/// Resolves tags of game against tags of the previous game: `#` value (and empty section) is copied from
+/// the previous tag of the same name and missing [`INHERITED_TAGS`] are taken from the previous game.
+fn inherit_tags(mut tags: Vec<(String, String, Vec<String>)>, previous: &[(String, String, Vec<String>)])
+    -> Result<Vec<(String, String, Vec<String>)>, FormatError>{
+    for (name, value, section) in tags.iter_mut(){
+        if value.trim() == "#"{
+            let (_, previous_value, previous_section) = previous.iter().find(|(n, _, _)| n == name)
+                .ok_or(FormatError::DeserializeError)?;
+            value.clone_from(previous_value);
+            if section.is_empty(){
+                section.clone_from(previous_section);
+            }
+        }
+    }
+    for tag in previous.iter().filter(|(n, _, _)| INHERITED_TAGS.contains(&n.as_str())){
+        if !tags.iter().any(|(n, _, _)| n == &tag.0){
+            tags.push(tag.clone());
+        }
+    }
+    Ok(tags)
+}
+
+/// Parses PBN file with many games separated by empty lines.
+/// Tags describing board (see [`INHERITED_TAGS`]) not repeated in game are inherited from the previous one,
+/// tag value `#` copies value of the same tag in the previous game.
+/// ```
+/// use brydz_core::format::pbn::parse_pbn_games;
+/// use brydz_core::player::side::Side::*;
+/// let games = parse_pbn_games("[Dealer \"N\"]
+/// [Vulnerable \"NS\"]
+/// [Deal \"N:AKQJT98765432... ...AKQJT98765432 .AKQJT98765432.. -\"]
+/// [Declarer \"N\"]
+/// [Contract \"7S\"]
+///
+/// [Vulnerable \"None\"]
+/// [Declarer \"#\"]
+/// [Contract \"6S\"]").unwrap();
+/// assert_eq!(games[1].deal(), games[0].deal());
+/// assert_eq!(games[1].dealer(), Some(North));
+/// assert!(!games[1].ns_vulnerable());
+/// assert_eq!(games[1].contract_parameters().unwrap().declarer(), North);
+/// ```
+pub fn parse_pbn_games(s: &str) -> Result<Vec<PbnGame>, BridgeCoreError>{
+    let stripped = strip_comments(s);
+    let mut games = Vec::new();
+    let mut previous = Vec::new();
+    let mut current = String::new();
+    for line in stripped.lines().chain(std::iter::once("")){
+        if line.trim().is_empty(){
+            if !current.trim().is_empty(){
+                let tags = inherit_tags(split_tags(&current)?, &previous)?;
+                games.push(game_from_tags(&tags)?);
+                previous = tags;
+            }
+            current.clear();
+        } else {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+    Ok(games)
+}
+
+/// Writes games separated with empty lines.
+pub fn write_pbn_games(games: &[PbnGame]) -> String{
+    games.iter().map(|g| g.to_string()).collect::<Vec<_>>().join("\n\n")
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::cards::{ACE_CLUBS, TWO_DIAMONDS};
+    use karty::hand::HandTrait;
+    use crate::bidding::Doubling;
+    use crate::bidding::consts::BID_D3;
+    use crate::contract::{ContractMechanics, ContractParametersGen};
+    use crate::error::{BridgeCoreErrorGen, ContractErrorGen, FormatError};
+    use crate::format::pbn::{parse_pbn_games, write_pbn_games, PbnGame};
+    use crate::player::side::Side::*;
+
+    const GAME: &str = "[Event \"Club\"]
+[Dealer \"S\"]
+[Vulnerable \"EW\"]
+[Deal \"N:QJ5.KQ3.T8432.T3 K74.T9862.J.KQ74 AT62.A4.AKQ95.52 983.J75.76.AJ986\"]
+[Declarer \"S\"]
+[Contract \"3D\"]
+[Result \"11\"]
+[Auction \"S\"]
+1D Pass 3D Pass
+Pass Pass
+[Play \"W\"]
+CA C3 C4 C2
+CJ CT C7 C5
+S9 SJ SK SA
+D6 D2 DJ DA
+D7 DT H2 D5
+S3 SQ S4 S2
+S8 S5 S7 ST
+H5 H3 H6 HA
+H7 HQ H8 H4
+HJ HK H9 S6
+C6 D3 HT DK
+C8 D4 CQ DQ
+C9 D8 CK D9";
+
+    #[test]
+    fn parse_full_game(){
+        let game: PbnGame = GAME.parse().unwrap();
+        assert_eq!(game.dealer(), Some(South));
+        assert!(!game.ns_vulnerable());
+        assert!(game.ew_vulnerable());
+        assert_eq!(game.deal()[&North].len(), 13);
+        assert!(game.deal()[&West].contains(&ACE_CLUBS));
+        assert_eq!(game.contract_parameters(), Some(&ContractParametersGen::new(South, BID_D3)));
+        let play = game.play().unwrap();
+        assert!(play.is_completed());
+        assert_eq!(play.total_tricks_taken_axis(South.axis()), 11);
+        assert_eq!(game.result(), Some(11));
+    }
+
+    #[test]
+    fn write_and_parse_again(){
+        let game: PbnGame = GAME.parse().unwrap();
+        let written = game.to_string();
+        let reparsed: PbnGame = written.parse().unwrap();
+        assert_eq!(reparsed, game);
+        assert_eq!(reparsed.to_string(), written);
+    }
+
+    #[test]
+    fn passed_out_and_many_games(){
+        let passed = "[Dealer \"E\"]\n[Vulnerable \"All\"]\n[Deal \"E:- - - -\"]\n[Contract \"Pass\"]\n[Auction \"E\"]\nAP";
+        let games = parse_pbn_games(&format!("% comment\n{GAME}\n\n{passed}\n")).unwrap();
+        assert_eq!(games.len(), 2);
+        assert!(games[1].is_passed_out());
+        assert_eq!(games[1].auction().unwrap().last_passes(), 4);
+        assert_eq!(parse_pbn_games(&write_pbn_games(&games)).unwrap(), games);
+    }
+
+    #[test]
+    fn tags_inherited_from_previous_game(){
+        let other_table = "[Declarer \"W\"]\n[Contract \"4HX\"]\n[Result \"8\"]";
+        let next_board = "[Vulnerable \"#\"]\n[Dealer \"E\"]\n[Deal \"E:- - - -\"]\n[Contract \"Pass\"]";
+        let games = parse_pbn_games(&format!("{GAME}\n\n{other_table}\n\n{next_board}")).unwrap();
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[1].deal(), games[0].deal());
+        assert_eq!(games[1].dealer(), Some(South));
+        assert!(games[1].ew_vulnerable());
+        assert_eq!(games[1].contract_parameters().unwrap().declarer(), West);
+        assert_eq!(games[1].contract_parameters().unwrap().doubling(), Doubling::Double);
+        assert!(games[1].auction().is_none() && games[1].play().is_none());
+        assert_eq!(games[1].result(), Some(8));
+        assert!(games[2].ew_vulnerable() && !games[2].ns_vulnerable());
+        assert_eq!(games[2].dealer(), Some(East));
+        assert!(games[2].is_passed_out());
+        assert!(games[2].result().is_none());
+        assert_eq!(parse_pbn_games("[Dealer \"#\"]\n[Deal \"E:- - - -\"]"),
+            Err(BridgeCoreErrorGen::Format(FormatError::DeserializeError)));
+    }
+
+    #[test]
+    fn incomplete_play(){
+        let s = GAME.replace("[Result \"11\"]\n", "").replace("C6 D3 HT DK\nC8 D4 CQ DQ\nC9 D8 CK D9", "- D3 HT -\n*");
+        let game: PbnGame = s.parse().unwrap();
+        let play = game.play().unwrap();
+        assert_eq!(play.count_completed_tricks(), 10);
+        assert_eq!(play.current_trick().count_cards(), 2);
+        assert_eq!(game.result(), None);
+        assert_eq!(game.to_string().parse::<PbnGame>().unwrap(), game);
+    }
+
+    #[test]
+    fn doubled_contract_tag(){
+        let s = GAME.replace("1D Pass 3D Pass\nPass Pass", "1D Pass 3D X\nPass Pass Pass").replace("[Contract \"3D\"]", "[Contract \"3DX\"]");
+        let game: PbnGame = s.parse().unwrap();
+        assert_eq!(game.contract_parameters(), Some(&ContractParametersGen::new_d(South, BID_D3, Doubling::Double)));
+    }
+
+    #[test]
+    fn errors(){
+        assert_eq!(GAME.replace("[Dealer \"S\"]", "[Dealer \"S\"").parse::<PbnGame>(),
+            Err(BridgeCoreErrorGen::Format(FormatError::DeserializeError)));
+        assert_eq!(GAME.replace("[Contract \"3D\"]", "[Contract \"4D\"]").parse::<PbnGame>(),
+            Err(BridgeCoreErrorGen::Format(FormatError::DeserializeError)));
+        assert!(matches!(GAME.replace("1D Pass 3D", "1D Pass 1C").parse::<PbnGame>(),
+            Err(BridgeCoreErrorGen::Bidding(_))));
+        assert_eq!(GAME.replace("C6 D3 HT DK", "C6 D3 HT D2").parse::<PbnGame>(),
+            Err(BridgeCoreErrorGen::Contract(ContractErrorGen::CardNotInHand(South, TWO_DIAMONDS))));
+    }
+}
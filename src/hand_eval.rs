@@ -0,0 +1,269 @@
+//! Evaluation of bridge hands: points, shape, losing-trick count, controls and quick tricks,
+//! and metrics of partnerships.
+//! ```
+//! use karty::card_set;
+//! use karty::cards::*;
+//! use brydz_core::hand_eval::{HandEvaluation, ShapeClass};
+//! let hand = card_set![ACE_SPADES, KING_SPADES, QUEEN_SPADES, SEVEN_SPADES, FIVE_SPADES, KING_HEARTS, JACK_HEARTS,
+//!     NINE_HEARTS, EIGHT_HEARTS, ACE_DIAMONDS, SIX_DIAMONDS, FOUR_DIAMONDS, TWO_CLUBS];
+//! let evaluation = HandEvaluation::new(&hand);
+//! assert_eq!(evaluation.hcp, 17);
+//! assert_eq!(evaluation.lengths, [5, 4, 3, 1]);
+//! assert_eq!(evaluation.shape_class, ShapeClass::TwoSuiter);
+//! assert_eq!(evaluation.losers, 5);
+//! assert_eq!(evaluation.quick_tricks, 3.5);
+//! ```
+use karty::cards::Card2SymTrait;
+use karty::figures::{Ace, Figure, Jack, King, Queen};
+use karty::hand::{CardSet, HandSuitedTrait};
+use karty::suits::Suit;
+use karty::suits::Suit::{Clubs, Diamonds, Hearts, Spades};
+use crate::player::axis::Axis;
+use crate::player::side::{SideMap, SIDES};
+
+/// Order of suits in arrays of lengths: spades, hearts, diamonds, clubs
+pub const SUIT_ORDER: [Suit; 4] = [Spades, Hearts, Diamonds, Clubs];
+/// Shapes counted as balanced, lengths sorted from the longest
+pub const BALANCED_SHAPES: [[u8; 4]; 3] = [[4, 3, 3, 3], [4, 4, 3, 2], [5, 3, 3, 2]];
+/// Shapes counted as semi-balanced, lengths sorted from the longest
+pub const SEMI_BALANCED_SHAPES: [[u8; 4]; 2] = [[5, 4, 2, 2], [6, 3, 2, 2]];
+
+/// Number of cards of suit in hand
+pub fn suit_length(hand: &CardSet, suit: Suit) -> u8{
+    hand.suit_iterator(&suit).count() as u8
+}
+
+/// Lengths of spades, hearts, diamonds and clubs
+pub fn suit_lengths(hand: &CardSet) -> [u8; 4]{
+    SUIT_ORDER.map(|suit| suit_length(hand, suit))
+}
+
+/// Lengths of suits sorted from the longest
+/// ```
+/// use karty::card_set;
+/// use karty::cards::*;
+/// use brydz_core::hand_eval::shape;
+/// let hand = card_set![ACE_SPADES, TWO_HEARTS, THREE_HEARTS, FOUR_CLUBS, FIVE_CLUBS, SIX_CLUBS];
+/// assert_eq!(shape(&hand), [3, 2, 1, 0]);
+/// ```
+pub fn shape(hand: &CardSet) -> [u8; 4]{
+    let mut shape = suit_lengths(hand);
+    shape.sort_unstable_by(|a, b| b.cmp(a));
+    shape
+}
+
+/// Milton Work points: ace 4, king 3, queen 2, jack 1
+pub fn high_card_points(hand: &CardSet) -> u8{
+    hand.into_iter().map(|c| match c.figure(){
+        Ace => 4,
+        King => 3,
+        Queen => 2,
+        Jack => 1,
+        _ => 0
+    }).sum()
+}
+
+/// Points for shortness: 3 for every void, 2 for every singleton and 1 for every doubleton
+pub fn distribution_points(hand: &CardSet) -> u8{
+    suit_lengths(hand).iter().map(|length| match length{
+        0 => 3,
+        1 => 2,
+        2 => 1,
+        _ => 0
+    }).sum()
+}
+
+/// High card points with distribution points
+pub fn total_points(hand: &CardSet) -> u8{
+    high_card_points(hand) + distribution_points(hand)
+}
+
+/// Controls: 2 for every ace and 1 for every king
+pub fn controls(hand: &CardSet) -> u8{
+    hand.into_iter().map(|c| match c.figure(){
+        Ace => 2,
+        King => 1,
+        _ => 0
+    }).sum()
+}
+
+/// Losing-trick count: in every suit up to three top places are checked for ace, king and queen
+/// (singleton is checked for ace only, doubleton for ace and king)
+pub fn losing_trick_count(hand: &CardSet) -> u8{
+    const TOP: [Figure; 3] = [Ace, King, Queen];
+    SUIT_ORDER.iter().map(|suit|{
+        let checked = (suit_length(hand, *suit) as usize).min(TOP.len());
+        let held = TOP[..checked].iter().filter(|f| hand.suit_iterator(suit).any(|c| c.figure() == **f)).count();
+        (checked - held) as u8
+    }).sum()
+}
+
+/// Quick (defensive) tricks of single suit: AK 2, AQ 1.5, A 1, KQ 1, Kx 0.5
+fn suit_quick_tricks(hand: &CardSet, suit: Suit) -> f32{
+    let has = |figure: Figure| hand.suit_iterator(&suit).any(|c| c.figure() == figure);
+    match (has(Ace), has(King), has(Queen)){
+        (true, true, _) => 2.0,
+        (true, false, true) => 1.5,
+        (true, false, false) => 1.0,
+        (false, true, true) => 1.0,
+        (false, true, false) if suit_length(hand, suit) >= 2 => 0.5,
+        _ => 0.0
+    }
+}
+
+/// Quick tricks summed over suits
+pub fn quick_tricks(hand: &CardSet) -> f32{
+    SUIT_ORDER.iter().map(|suit| suit_quick_tricks(hand, *suit)).sum()
+}
+
+/// Class of hand shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ShapeClass{
+    /// 4-3-3-3, 4-4-3-2 or 5-3-3-2
+    Balanced,
+    /// 5-4-2-2 or 6-3-2-2
+    SemiBalanced,
+    /// One long suit (6+) and no other suit of four cards
+    SingleSuiter,
+    /// Two suits, the longer of at least five cards and the other of at least four cards
+    TwoSuiter,
+    /// Three suits of at least four cards (4-4-4-1, 5-4-4-0)
+    ThreeSuiter
+}
+
+impl ShapeClass{
+    /// Class of shape given lengths sorted from the longest
+    /// ```
+    /// use brydz_core::hand_eval::ShapeClass;
+    /// assert_eq!(ShapeClass::of_shape([5, 3, 3, 2]), ShapeClass::Balanced);
+    /// assert_eq!(ShapeClass::of_shape([6, 3, 2, 2]), ShapeClass::SemiBalanced);
+    /// assert_eq!(ShapeClass::of_shape([7, 3, 2, 1]), ShapeClass::SingleSuiter);
+    /// assert_eq!(ShapeClass::of_shape([5, 4, 3, 1]), ShapeClass::TwoSuiter);
+    /// assert_eq!(ShapeClass::of_shape([4, 4, 4, 1]), ShapeClass::ThreeSuiter);
+    /// ```
+    pub fn of_shape(shape: [u8; 4]) -> Self{
+        if BALANCED_SHAPES.contains(&shape){
+            ShapeClass::Balanced
+        } else if SEMI_BALANCED_SHAPES.contains(&shape){
+            ShapeClass::SemiBalanced
+        } else if shape[2] >= 4{
+            ShapeClass::ThreeSuiter
+        } else if shape[1] >= 4{
+            ShapeClass::TwoSuiter
+        } else {
+            ShapeClass::SingleSuiter
+        }
+    }
+    pub fn of_hand(hand: &CardSet) -> Self{
+        Self::of_shape(shape(hand))
+    }
+}
+
+/// Summary of evaluation of single hand
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandEvaluation{
+    pub hcp: u8,
+    pub distribution_points: u8,
+    /// Lengths of spades, hearts, diamonds and clubs
+    pub lengths: [u8; 4],
+    pub shape_class: ShapeClass,
+    pub losers: u8,
+    pub controls: u8,
+    pub quick_tricks: f32
+}
+
+impl HandEvaluation{
+    pub fn new(hand: &CardSet) -> Self{
+        Self{
+            hcp: high_card_points(hand),
+            distribution_points: distribution_points(hand),
+            lengths: suit_lengths(hand),
+            shape_class: ShapeClass::of_hand(hand),
+            losers: losing_trick_count(hand),
+            controls: controls(hand),
+            quick_tricks: quick_tricks(hand)
+        }
+    }
+}
+
+fn axis_hands(hands: &SideMap<CardSet>, axis: Axis) -> impl Iterator<Item = &CardSet>{
+    SIDES.iter().filter(move |side| side.axis() == axis).map(|side| &hands[side])
+}
+
+/// High card points of both hands of partnership
+pub fn combined_hcp(hands: &SideMap<CardSet>, axis: Axis) -> u8{
+    axis_hands(hands, axis).map(high_card_points).sum()
+}
+
+/// Number of cards of suit held by partnership
+pub fn combined_length(hands: &SideMap<CardSet>, axis: Axis, suit: Suit) -> u8{
+    axis_hands(hands, axis).map(|hand| suit_length(hand, suit)).sum()
+}
+
+/// Longest combined suit of partnership, on equal lengths the higher suit is chosen
+/// ```
+/// use karty::card_set;
+/// use karty::cards::*;
+/// use karty::hand::{CardSet, HandTrait};
+/// use karty::suits::Suit::Hearts;
+/// use brydz_core::hand_eval::best_fit;
+/// use brydz_core::player::axis::Axis::{EastWest, NorthSouth};
+/// use brydz_core::player::side::SideMap;
+/// let mut hands = SideMap::new_symmetric(CardSet::empty());
+/// hands.north = card_set![ACE_HEARTS, KING_HEARTS, FIVE_HEARTS, TWO_HEARTS, FOUR_SPADES];
+/// hands.south = card_set![QUEEN_HEARTS, SEVEN_HEARTS, SIX_HEARTS, THREE_HEARTS, FIVE_SPADES];
+/// assert_eq!(best_fit(&hands, NorthSouth), (Hearts, 8));
+/// assert_eq!(best_fit(&hands, EastWest).1, 0);
+/// ```
+pub fn best_fit(hands: &SideMap<CardSet>, axis: Axis) -> (Suit, u8){
+    SUIT_ORDER.iter().map(|suit| (*suit, combined_length(hands, axis, *suit)))
+        .fold((Spades, 0), |best, candidate| if candidate.1 > best.1 { candidate } else { best })
+}
+
+/// Losing tricks of partnership, the better hand counted in full and the other capped at seven
+pub fn combined_losers(hands: &SideMap<CardSet>, axis: Axis) -> u8{
+    let mut losers: Vec<u8> = axis_hands(hands, axis).map(losing_trick_count).collect();
+    losers.sort_unstable();
+    losers.iter().enumerate().map(|(i, l)| if i == 0 { *l } else { (*l).min(7) }).sum()
+}
+
+/// Tricks expected by losing-trick count: 24 less combined losers of partnership
+pub fn expected_tricks(hands: &SideMap<CardSet>, axis: Axis) -> u8{
+    24u8.saturating_sub(combined_losers(hands, axis))
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::card_set;
+    use karty::cards::*;
+    use crate::hand_eval::{controls, distribution_points, high_card_points, losing_trick_count, quick_tricks,
+        ShapeClass, total_points};
+
+    #[test]
+    fn single_hand_metrics(){
+        let hand = card_set![ACE_SPADES, KING_SPADES, FIVE_SPADES, FOUR_SPADES, KING_HEARTS, QUEEN_HEARTS, TWO_HEARTS,
+            ACE_DIAMONDS, THREE_DIAMONDS, QUEEN_CLUBS, JACK_CLUBS, TEN_CLUBS, TWO_CLUBS];
+        assert_eq!(high_card_points(&hand), 19);
+        assert_eq!(distribution_points(&hand), 1);
+        assert_eq!(total_points(&hand), 20);
+        assert_eq!(controls(&hand), 6);
+        // spades 1, hearts 1, diamonds 1, clubs 2
+        assert_eq!(losing_trick_count(&hand), 5);
+        // AK 2, KQ 1, A 1
+        assert_eq!(quick_tricks(&hand), 4.0);
+        assert_eq!(ShapeClass::of_hand(&hand), ShapeClass::Balanced);
+    }
+
+    #[test]
+    fn shortness_and_singleton_king(){
+        let hand = card_set![KING_SPADES, ACE_HEARTS, QUEEN_HEARTS, JACK_HEARTS, TEN_HEARTS, NINE_HEARTS, EIGHT_HEARTS,
+            SEVEN_HEARTS, KING_CLUBS, FIVE_CLUBS, FOUR_CLUBS, THREE_CLUBS, TWO_CLUBS];
+        assert_eq!(distribution_points(&hand), 5);
+        // singleton king is a loser and no quick trick
+        assert_eq!(losing_trick_count(&hand), 1 + 1 + 2);
+        assert_eq!(quick_tricks(&hand), 1.5 + 0.5);
+        assert_eq!(ShapeClass::of_hand(&hand), ShapeClass::TwoSuiter);
+    }
+}
@@ -16,6 +16,8 @@ pub mod cards;
 pub mod meta;
 pub mod error;
 pub mod deal;
+pub mod hand_eval;
+pub mod format;
 
 #[cfg(feature = "amfiteatr")]
 pub mod amfi;
@@ -1,6 +1,27 @@
-#[derive(Debug, Eq, PartialEq,  Copy, Clone)]
+#[derive(Debug, Eq, PartialEq,  Copy, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Axis{
     NorthSouth,
     EastWest
-}
\ No newline at end of file
+}
+
+impl Axis{
+    /// Index of axis in per axis arrays: `0` for North-South, `1` for East-West
+    pub fn index(&self) -> usize{
+        match self{
+            Axis::NorthSouth => 0,
+            Axis::EastWest => 1
+        }
+    }
+    /// ```
+    /// use brydz_core::player::axis::Axis;
+    /// assert_eq!(Axis::NorthSouth.opposite(), Axis::EastWest);
+    /// assert_eq!(Axis::EastWest.opposite(), Axis::NorthSouth);
+    /// ```
+    pub fn opposite(&self) -> Axis{
+        match self{
+            Axis::NorthSouth => Axis::EastWest,
+            Axis::EastWest => Axis::NorthSouth
+        }
+    }
+}
@@ -99,12 +99,14 @@ impl Side{
             West => 3
         }
     }
+    #[cfg(feature = "amfiteatr")]
     pub(crate) fn first_unknown_side(self, declarer: Side) -> Side{
         match self - declarer{
             1 => self.partner(),
             _ => self.next()
         }
     }
+    #[cfg(feature = "amfiteatr")]
     pub(crate) fn second_unknown_side(self, declarer: Side) -> Side{
         match self - declarer{
             0 | 1 => self.prev(),
@@ -209,7 +211,7 @@ impl<T:Default> Default for SideMap<T>{
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "amfiteatr"))]
 mod tests{
     use crate::player::side::Side::*;
 
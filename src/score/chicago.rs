@@ -0,0 +1,263 @@
+use karty::cards::Card2SymTrait;
+use karty::suits::Suit;
+use crate::contract::{ContractMechanics, ContractParametersGen};
+use crate::deal::Vulnerability;
+use crate::error::{BridgeCoreErrorGen, ContractErrorGen, ScoreError};
+use crate::player::axis::Axis;
+use crate::player::side::Side;
+use crate::score::ScoreTracker;
+use crate::score::tables::{PENALTY_UNDER_TRICK, POINTS_CONTRACTED_TRICK, POINTS_OVER_TRICK, POINTS_PREMIUM_CONTRACT, POINTS_PREMIUM_SPORT, POINTS_SLAM};
+
+/// Number of deals in Chicago cycle
+pub const CHICAGO_CYCLE_DEALS: u8 = 4;
+/// Bonus for partscore made on the last deal of cycle that does not complete game
+pub const CHICAGO_LAST_DEAL_PARTSCORE: i32 = 100;
+
+/// Score of Chicago: cycles of four deals with dealer rotating clockwise and fixed vulnerability schedule
+/// (first deal nobody, second and third dealer's side, fourth both).
+/// Partscores carry over between deals of cycle, game bonus is awarded when they sum to game.
+/// Deal played after the fourth one starts next cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreTableChicago{
+    first_dealer: Side,
+    deals_played: u8,
+    total: [i32; 2],
+    partscore: [i32; 2],
+    finished_cycles: Vec<[i32; 2]>,
+}
+
+impl Default for ScoreTableChicago{
+    fn default() -> Self {
+        Self::new(Side::North)
+    }
+}
+
+impl ScoreTableChicago{
+    pub fn new(first_dealer: Side) -> Self{
+        Self{first_dealer, deals_played: 0, total: [0, 0], partscore: [0, 0], finished_cycles: Vec::new()}
+    }
+
+    /// Number of deals already played in current cycle
+    pub fn deals_played(&self) -> u8{
+        self.deals_played
+    }
+    pub fn is_cycle_finished(&self) -> bool{
+        self.deals_played == CHICAGO_CYCLE_DEALS
+    }
+    /// Position of next deal in cycle (from 0 to 3)
+    fn next_deal_index(&self) -> u8{
+        self.deals_played % CHICAGO_CYCLE_DEALS
+    }
+
+    /// Dealer of the next deal
+    /// ```
+    /// use brydz_core::player::side::Side::North;
+    /// use brydz_core::score::chicago::ScoreTableChicago;
+    /// let score = ScoreTableChicago::new(North);
+    /// assert_eq!(score.dealer(), North);
+    /// ```
+    pub fn dealer(&self) -> Side{
+        self.first_dealer.next_i(self.next_deal_index())
+    }
+
+    /// Vulnerability of axis in the next deal
+    /// ```
+    /// use brydz_core::player::axis::Axis::{EastWest, NorthSouth};
+    /// use brydz_core::player::side::Side::North;
+    /// use brydz_core::score::chicago::ScoreTableChicago;
+    /// let score = ScoreTableChicago::new(North);
+    /// assert!(!score.is_vulnerable(NorthSouth));
+    /// assert!(!score.is_vulnerable(EastWest));
+    /// ```
+    pub fn is_vulnerable(&self, axis: Axis) -> bool{
+        match self.next_deal_index(){
+            0 => false,
+            3 => true,
+            _ => self.dealer().axis() == axis
+        }
+    }
+
+    /// Vulnerability in the next deal
+    /// ```
+    /// use brydz_core::deal::Vulnerability;
+    /// use brydz_core::player::side::Side::East;
+    /// use brydz_core::score::chicago::ScoreTableChicago;
+    /// let mut score = ScoreTableChicago::new(East);
+    /// score.pass_out();
+    /// // South deals second deal
+    /// assert_eq!(score.vulnerability(), Vulnerability::NorthSouth);
+    /// ```
+    pub fn vulnerability(&self) -> Vulnerability{
+        Vulnerability::from_axes(self.is_vulnerable(Axis::NorthSouth), self.is_vulnerable(Axis::EastWest))
+    }
+
+    /// Total of axis in current cycle
+    pub fn cycle_total(&self, axis: Axis) -> i32{
+        self.total[axis.index()]
+    }
+    /// Partscore of axis carried to the next deal
+    pub fn partscore(&self, axis: Axis) -> i32{
+        self.partscore[axis.index()]
+    }
+    /// Totals (North-South, East-West) of finished cycles, current cycle is included once it is finished
+    pub fn finished_cycles(&self) -> &[[i32; 2]]{
+        &self.finished_cycles
+    }
+
+    fn start_deal(&mut self){
+        if self.is_cycle_finished(){
+            self.deals_played = 0;
+            self.total = [0, 0];
+            self.partscore = [0, 0];
+        }
+    }
+
+    fn finish_deal(&mut self){
+        self.deals_played += 1;
+        if self.is_cycle_finished(){
+            self.finished_cycles.push(self.total);
+        }
+    }
+
+    /// Writes result of contract with given number of tricks taken by declarer.
+    /// # Example:
+    /// ```
+    /// use brydz_core::bidding::Bid;
+    /// use brydz_core::cards::trump::TrumpGen;
+    /// use brydz_core::contract::ContractParametersGen;
+    /// use brydz_core::player::axis::Axis::NorthSouth;
+    /// use brydz_core::player::side::Side::{East, North};
+    /// use brydz_core::score::chicago::ScoreTableChicago;
+    /// use karty::suits::Suit::{Hearts, Spades};
+    /// let mut score = ScoreTableChicago::new(North);
+    /// let two_hearts = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Hearts), 2).unwrap());
+    /// score.update_contract(&two_hearts, 8).unwrap();
+    /// assert_eq!(score.cycle_total(NorthSouth), 60);
+    /// assert_eq!(score.dealer(), East);
+    /// let two_spades = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 2).unwrap());
+    /// score.update_contract(&two_spades, 8).unwrap();
+    /// // second partscore completes game, not vulnerable bonus
+    /// assert_eq!(score.cycle_total(NorthSouth), 420);
+    /// assert_eq!(score.partscore(NorthSouth), 0);
+    /// ```
+    pub fn update_contract(&mut self, contract: &ContractParametersGen<Suit>, taken: u8) -> Result<(), ScoreError>{
+        self.start_deal();
+        let declarer_axis = contract.declarer().axis();
+        let declarer = declarer_axis.index();
+        let vulnerability = self.is_vulnerable(declarer_axis);
+        let last_deal = self.next_deal_index() == CHICAGO_CYCLE_DEALS - 1;
+        if taken < contract.bid().number_normalised(){
+            let penalty = PENALTY_UNDER_TRICK.penalty_checked(contract, taken, vulnerability)?;
+            self.total[1 - declarer] += penalty;
+        } else {
+            let contracted = POINTS_CONTRACTED_TRICK.points(contract, taken);
+            self.partscore[declarer] += contracted;
+            let bonus = if self.partscore[declarer] >= POINTS_PREMIUM_SPORT.game_activation_level{
+                self.partscore = [0, 0];
+                match vulnerability{
+                    true => POINTS_PREMIUM_SPORT.game_vulnerable,
+                    false => POINTS_PREMIUM_SPORT.game_not_vulnerable
+                }
+            } else if last_deal{
+                CHICAGO_LAST_DEAL_PARTSCORE
+            } else {
+                0
+            };
+            self.total[declarer] += contracted + bonus
+                + POINTS_OVER_TRICK.points(contract, taken, vulnerability)
+                + POINTS_SLAM.points(contract, taken, vulnerability)
+                + POINTS_PREMIUM_CONTRACT.points(contract, taken);
+        }
+        self.finish_deal();
+        Ok(())
+    }
+
+    /// Counts passed out deal, it is not redealt.
+    pub fn pass_out(&mut self){
+        self.start_deal();
+        self.finish_deal();
+    }
+}
+
+impl<Co: ContractMechanics<Card = Crd>, Crd: Card2SymTrait<Suit = Suit>> ScoreTracker<Co, Crd> for ScoreTableChicago{
+    fn winner_axis(&self) -> Option<Axis> {
+        let ns = <Self as ScoreTracker<Co, Crd>>::points(self, &Axis::NorthSouth);
+        let ew = <Self as ScoreTracker<Co, Crd>>::points(self, &Axis::EastWest);
+        match ns.cmp(&ew){
+            std::cmp::Ordering::Greater => Some(Axis::NorthSouth),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Less => Some(Axis::EastWest)
+        }
+    }
+
+    fn update(&mut self, deal: &Co) -> Result<(), BridgeCoreErrorGen<Crd>> {
+        if !deal.is_completed(){
+            return Err(BridgeCoreErrorGen::Contract(ContractErrorGen::DealIncomplete))
+        }
+        let taken = deal.total_tricks_taken_axis(deal.contract_spec().declarer().axis()) as u8;
+        Ok(self.update_contract(deal.contract_spec(), taken)?)
+    }
+
+    /// Sum of all finished cycles and current one (when not finished)
+    fn points(&self, axis: &Axis) -> i32 {
+        let finished: i32 = self.finished_cycles.iter().map(|c| c[axis.index()]).sum();
+        match self.is_cycle_finished(){
+            true => finished,
+            false => finished + self.cycle_total(*axis)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::suits::Suit::{Clubs, Hearts};
+    use crate::bidding::Bid;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::{Contract, ContractParametersGen};
+    use crate::player::axis::Axis::{EastWest, NorthSouth};
+    use crate::player::side::Side::{East, North, South, West};
+    use crate::score::chicago::ScoreTableChicago;
+    use crate::score::ScoreTracker;
+
+    #[test]
+    fn chicago_rotation_and_vulnerability(){
+        let mut score = ScoreTableChicago::new(West);
+        let expected = [(West, false, false), (North, true, false), (East, false, true), (South, true, true)];
+        for (dealer, ns, ew) in expected{
+            assert_eq!(score.dealer(), dealer);
+            assert_eq!(score.is_vulnerable(NorthSouth), ns);
+            assert_eq!(score.is_vulnerable(EastWest), ew);
+            score.pass_out();
+        }
+        assert!(score.is_cycle_finished());
+        assert_eq!(score.dealer(), West);
+        assert_eq!(score.finished_cycles(), &[[0, 0]]);
+    }
+
+    #[test]
+    fn chicago_cycle(){
+        let mut score = ScoreTableChicago::new(North);
+        let three_clubs_ew = ContractParametersGen::new(East, Bid::init(TrumpGen::Colored(Clubs), 3).unwrap());
+        // deal 1: EW partscore 60
+        score.update_contract(&three_clubs_ew, 9).unwrap();
+        // deal 2 (EW vulnerable): EW down one, 100 to NS
+        score.update_contract(&three_clubs_ew, 8).unwrap();
+        assert_eq!((score.cycle_total(NorthSouth), score.cycle_total(EastWest)), (100, 60));
+        // deal 3 (NS vulnerable): 4H by NS, game erases EW partscore
+        let four_hearts = ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Hearts), 4).unwrap());
+        score.update_contract(&four_hearts, 10).unwrap();
+        assert_eq!(score.cycle_total(NorthSouth), 720);
+        assert_eq!(score.partscore(EastWest), 0);
+        // deal 4: EW partscore with bonus 100 on last deal
+        let two_clubs_ew = ContractParametersGen::new(West, Bid::init(TrumpGen::Colored(Clubs), 2).unwrap());
+        score.update_contract(&two_clubs_ew, 8).unwrap();
+        assert_eq!(score.cycle_total(EastWest), 200);
+        assert!(score.is_cycle_finished());
+        assert_eq!(score.finished_cycles(), &[[720, 200]]);
+        assert_eq!(<ScoreTableChicago as ScoreTracker<Contract, _>>::winner_axis(&score), Some(NorthSouth));
+        // next cycle starts with clean partscores
+        score.update_contract(&two_clubs_ew, 8).unwrap();
+        assert_eq!(score.deals_played(), 1);
+        assert_eq!(<ScoreTableChicago as ScoreTracker<Contract, _>>::points(&score, &EastWest), 240);
+    }
+}
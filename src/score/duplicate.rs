@@ -0,0 +1,137 @@
+//! Scoring of duplicate bridge: board score, IMPs and matchpoints.
+use karty::cards::Card2SymTrait;
+use karty::suits::Suit;
+use crate::contract::{ContractMechanics, ContractParameters};
+use crate::deal::Vulnerability;
+use crate::error::{BridgeCoreErrorGen, ScoreError};
+use crate::player::axis::Axis;
+use crate::score::sport::ScoreTableSport;
+
+/// Lower bounds of score difference for consecutive IMPs (WBF scale), from 1 to 24 IMPs.
+pub const IMP_SCALE: [i32; 24] = [
+    20, 50, 90, 130, 170, 220, 270, 320, 370, 430, 500, 600,
+    750, 900, 1100, 1300, 1500, 1750, 2000, 2250, 2500, 3000, 3500, 4000
+];
+
+/// Score of board from the perspective of North-South. Passed out board (`None`) scores 0.
+/// ```
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::ContractParametersGen;
+/// use brydz_core::deal::Vulnerability;
+/// use brydz_core::player::side::Side::{North, West};
+/// use brydz_core::score::duplicate::board_score;
+/// use karty::suits::Suit::Hearts;
+/// let four_hearts = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Hearts), 4).unwrap());
+/// assert_eq!(board_score(Some(&four_hearts), 10, Vulnerability::NorthSouth), Ok(620));
+/// let four_hearts = ContractParametersGen::new(West, Bid::init(TrumpGen::Colored(Hearts), 4).unwrap());
+/// assert_eq!(board_score(Some(&four_hearts), 9, Vulnerability::NorthSouth), Ok(50));
+/// assert_eq!(board_score(None, 0, Vulnerability::NorthSouth), Ok(0));
+/// ```
+pub fn board_score(contract: Option<&ContractParameters>, taken: u8, vulnerability: Vulnerability) -> Result<i32, ScoreError>{
+    match contract{
+        None => Ok(0),
+        Some(contract) => {
+            let declarer_axis = contract.declarer().axis();
+            let sign = match declarer_axis{
+                Axis::NorthSouth => 1,
+                Axis::EastWest => -1
+            };
+            ScoreTableSport::contract_points(contract, taken, vulnerability.is_vulnerable(declarer_axis)).map(|p| p * sign)
+        }
+    }
+}
+
+/// Score of completed contract from the perspective of North-South.
+pub fn completed_board_score<Co: ContractMechanics<Card = Crd>, Crd: Card2SymTrait<Suit = Suit>>(contract: &Co, vulnerability: Vulnerability) -> Result<i32, BridgeCoreErrorGen<Crd>>{
+    ScoreTableSport::with_vulnerability(vulnerability).completed_contract_points(contract, Axis::NorthSouth)
+}
+
+/// Converts score difference to IMPs, sign of difference is preserved.
+/// ```
+/// use brydz_core::score::duplicate::imps;
+/// assert_eq!(imps(0), 0);
+/// assert_eq!(imps(10), 0);
+/// assert_eq!(imps(20), 1);
+/// assert_eq!(imps(620 - 170), 10);
+/// assert_eq!(imps(-1430), -16);
+/// assert_eq!(imps(5000), 24);
+/// ```
+pub fn imps(difference: i32) -> i32{
+    let imps = IMP_SCALE.iter().take_while(|bound| difference.abs() >= **bound).count() as i32;
+    imps * difference.signum()
+}
+
+/// IMPs won by North-South comparing their score at one table with North-South score at the other.
+pub fn imps_between(ns_score: i32, other_ns_score: i32) -> i32{
+    imps(ns_score - other_ns_score)
+}
+
+/// Matchpoints of every result (North-South perspective) on the same board:
+/// 2 for every result beaten and 1 for every result tied.
+/// ```
+/// use brydz_core::score::duplicate::matchpoints;
+/// assert_eq!(matchpoints(&[420, 450, 420, -50]), vec![3, 6, 3, 0]);
+/// ```
+pub fn matchpoints(scores: &[i32]) -> Vec<u32>{
+    scores.iter().map(|score|{
+        scores.iter().map(|other| match score.cmp(other){
+            std::cmp::Ordering::Greater => 2,
+            std::cmp::Ordering::Equal => 1,
+            std::cmp::Ordering::Less => 0
+        }).sum::<u32>() - 1
+    }).collect()
+}
+
+/// Matchpoints as percentage of top (`2 * (n - 1)`), single result scores 50%.
+/// ```
+/// use brydz_core::score::duplicate::matchpoint_percentages;
+/// assert_eq!(matchpoint_percentages(&[420, 450, 420, -50]), vec![50.0, 100.0, 50.0, 0.0]);
+/// assert_eq!(matchpoint_percentages(&[100]), vec![50.0]);
+/// ```
+pub fn matchpoint_percentages(scores: &[i32]) -> Vec<f64>{
+    let top = 2 * scores.len().saturating_sub(1);
+    matchpoints(scores).into_iter().map(|mp| match top{
+        0 => 50.0,
+        top => 100.0 * mp as f64 / top as f64
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::suits::Suit::Spades;
+    use crate::bidding::Bid;
+    use crate::bidding::Doubling::Double;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::ContractParametersGen;
+    use crate::deal::Vulnerability;
+    use crate::player::side::Side::{East, South};
+    use crate::score::duplicate::{board_score, imps, imps_between, matchpoints, IMP_SCALE};
+
+    #[test]
+    fn imp_scale_bounds(){
+        for (i, bound) in IMP_SCALE.iter().enumerate(){
+            assert_eq!(imps(*bound), i as i32 + 1);
+            assert_eq!(imps(*bound - 10), i as i32);
+            assert_eq!(imps(-*bound), -(i as i32 + 1));
+        }
+    }
+
+    #[test]
+    fn team_match_swing(){
+        let four_spades = ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Spades), 4).unwrap());
+        let five_spades_doubled = ContractParametersGen::new_d(East, Bid::init(TrumpGen::Colored(Spades), 5).unwrap(), Double);
+        let open_room = board_score(Some(&four_spades), 10, Vulnerability::Both).unwrap();
+        let closed_room = board_score(Some(&five_spades_doubled), 8, Vulnerability::Both).unwrap();
+        assert_eq!(open_room, 620);
+        assert_eq!(closed_room, 800);
+        assert_eq!(imps_between(open_room, closed_room), -5);
+        assert_eq!(imps_between(closed_room, open_room), 5);
+    }
+
+    #[test]
+    fn matchpoints_all_equal(){
+        assert_eq!(matchpoints(&[110, 110, 110]), vec![2, 2, 2]);
+        assert!(matchpoints(&[]).is_empty());
+    }
+}
@@ -1,10 +1,13 @@
-//pub mod rubber_score;
+pub mod rubber_score;
 //pub mod rewards;
 //pub mod score_table;
 mod calculation;
 pub mod sport;
 pub mod points_table;
 pub mod tables;
+pub mod par;
+pub mod duplicate;
+pub mod chicago;
 
 pub use calculation::{ScoreTracker};
 
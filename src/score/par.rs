@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+use karty::suits::Suit;
+use crate::bidding::{Bid, Doubling};
+use crate::cards::trump::TRUMPS;
+use crate::contract::{ContractParameters, ContractParametersGen};
+use crate::contract::double_dummy::DoubleDummyTable;
+use crate::error::ScoreError;
+use crate::meta::{MAX_BID_NUMBER, MIN_BID_NUMBER};
+use crate::player::axis::Axis;
+use crate::player::side::{Side, SIDES};
+use crate::score::sport::ScoreTableSport;
+
+/// Result of par analysis: score of North-South (East-West score is the opposite)
+/// and contracts in which optimal bidding ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParScore{
+    score: i32,
+    contracts: Vec<ContractParameters>
+}
+
+impl ParScore{
+    /// Par score from the perspective of North-South.
+    pub fn score(&self) -> i32{
+        self.score
+    }
+    pub fn score_axis(&self, axis: Axis) -> i32{
+        match axis{
+            Axis::NorthSouth => self.score,
+            Axis::EastWest => -self.score
+        }
+    }
+    /// Par contracts sorted by bid, defeated ones (sacrifices) are doubled.
+    /// Empty when deal is passed out.
+    pub fn contracts(&self) -> &[ContractParameters]{
+        &self.contracts
+    }
+}
+
+/// State of simplified auction in which both axes bid knowing double dummy results.
+/// `last` is index of the highest bid (0 when nothing was bid) shifted by one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct AuctionState{
+    last: usize,
+    owner: Axis,
+    turn: Axis,
+    passes: u8,
+}
+
+struct ParSearch{
+    bids: Vec<Bid<Suit>>,
+    /// Final score (North-South perspective) for every bid and owner axis
+    finals: Vec<[i32; 2]>,
+    memory: Vec<Option<i32>>,
+}
+
+impl ParSearch{
+    fn slot(&self, state: &AuctionState) -> usize{
+        (((state.last * 2 + state.owner.index()) * 2 + state.turn.index()) * 4) + state.passes as usize
+    }
+
+    fn is_final(state: &AuctionState) -> bool{
+        match state.last{
+            0 => state.passes == 4,
+            _ => state.passes == 3
+        }
+    }
+
+    /// Possible continuations of the auction (pass first)
+    fn continuations(&self, state: &AuctionState) -> Vec<AuctionState>{
+        let next_turn = state.turn.opposite();
+        let mut continuations = vec![AuctionState{passes: state.passes + 1, turn: next_turn, ..*state}];
+        continuations.extend((state.last + 1..=self.bids.len()).map(|last|{
+            AuctionState{last, owner: state.turn, turn: next_turn, passes: 0}
+        }));
+        continuations
+    }
+
+    fn value(&mut self, state: &AuctionState) -> i32{
+        if Self::is_final(state){
+            return match state.last{
+                0 => 0,
+                n => self.finals[n - 1][state.owner.index()]
+            }
+        }
+        let slot = self.slot(state);
+        if let Some(v) = self.memory[slot]{
+            return v;
+        }
+        let values = self.continuations(state).iter().map(|s| self.value(s)).collect::<Vec<_>>();
+        let value = match state.turn{
+            Axis::NorthSouth => values.into_iter().max(),
+            Axis::EastWest => values.into_iter().min()
+        }.unwrap_or(0);
+        self.memory[slot] = Some(value);
+        value
+    }
+
+    /// Sacrifice is worth listing only when it prevents opponents from making
+    /// lower contract scoring more than par.
+    fn is_useful_sacrifice(&self, state: &AuctionState, par: i32) -> bool{
+        let opponents = state.owner.opposite().index();
+        let sign = match state.owner{
+            Axis::NorthSouth => -1,
+            Axis::EastWest => 1
+        };
+        self.finals[..state.last - 1].iter().any(|scores| sign * scores[opponents] > sign * par)
+    }
+
+    /// Collects final states reachable when both axes play optimally.
+    fn optimal_finals(&mut self, state: AuctionState, visited: &mut HashSet<AuctionState>, finals: &mut Vec<AuctionState>){
+        if !visited.insert(state){
+            return;
+        }
+        if Self::is_final(&state){
+            finals.push(state);
+            return;
+        }
+        let value = self.value(&state);
+        for next in self.continuations(&state){
+            if self.value(&next) == value{
+                self.optimal_finals(next, visited, finals);
+            }
+        }
+    }
+}
+
+/// Defeated contracts are always doubled, made ones never.
+fn par_doubling(bid: &Bid<Suit>, taken: u8) -> Doubling{
+    match taken < bid.number_normalised(){
+        true => Doubling::Double,
+        false => Doubling::None
+    }
+}
+
+/// Computes par score and par contracts for double dummy results of a deal.
+/// Par is the result of auction in which both axes know double dummy results,
+/// every defeated contract is doubled and no side can improve its score by bidding on.
+/// Scoring follows [`ScoreTableSport::contract_points`].
+/// `dealer` matters only when both axes can make the same contract.
+/// # Example:
+/// ```
+/// use brydz_core::bidding::{Bid, Doubling};
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::ContractParametersGen;
+/// use brydz_core::contract::double_dummy::DoubleDummyTable;
+/// use brydz_core::player::side::Side::*;
+/// use brydz_core::player::side::SideMap;
+/// use brydz_core::score::par::par_score;
+/// use karty::suits::Suit::{Hearts, Spades};
+/// //rows: clubs, diamonds, hearts, spades, no trump
+/// let table = DoubleDummyTable::new(SideMap::new(
+///     [4, 4, 3, 10, 7],
+///     [8, 8, 9, 3, 5],
+///     [4, 4, 3, 10, 7],
+///     [8, 8, 9, 3, 5]));
+/// //East-West save in 5 hearts over 4 spades
+/// let par = par_score(&table, false, false, North).unwrap();
+/// assert_eq!(par.score(), 300);
+/// assert_eq!(par.contracts()[0], ContractParametersGen::new_d(East, Bid::init(TrumpGen::Colored(Hearts), 5).unwrap(), Doubling::Double));
+/// let par = par_score(&table, false, true, North).unwrap();
+/// assert_eq!(par.score(), 420);
+/// assert_eq!(par.contracts(), &[
+///     ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 4).unwrap()),
+///     ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Spades), 4).unwrap())]);
+/// ```
+pub fn par_score(table: &DoubleDummyTable, ns_vulnerability: bool, ew_vulnerability: bool, dealer: Side) -> Result<ParScore, ScoreError>{
+    let mut bids = Vec::with_capacity(TRUMPS.len() * MAX_BID_NUMBER as usize);
+    for number in MIN_BID_NUMBER..=MAX_BID_NUMBER{
+        for trump in TRUMPS{
+            //every combination is valid bid
+            if let Ok(bid) = Bid::init(trump, number){
+                bids.push(bid);
+            }
+        }
+    }
+    bids.sort();
+    let mut finals = Vec::with_capacity(bids.len());
+    for bid in bids.iter(){
+        let mut scores = [0i32; 2];
+        for axis in [Axis::NorthSouth, Axis::EastWest]{
+            let taken = table.tricks_axis(axis, bid.trump());
+            let vulnerability = match axis{
+                Axis::NorthSouth => ns_vulnerability,
+                Axis::EastWest => ew_vulnerability
+            };
+            let contract = ContractParametersGen::new_d(SIDES[axis.index()], *bid, par_doubling(bid, taken));
+            let points = ScoreTableSport::contract_points(&contract, taken, vulnerability)?;
+            scores[axis.index()] = match axis{
+                Axis::NorthSouth => points,
+                Axis::EastWest => -points
+            };
+        }
+        finals.push(scores);
+    }
+    let memory = vec![None; (bids.len() + 1) * 16];
+    let mut search = ParSearch{bids, finals, memory};
+    let start = AuctionState{last: 0, owner: dealer.axis(), turn: dealer.axis(), passes: 0};
+    let score = search.value(&start);
+    let mut final_states = Vec::new();
+    search.optimal_finals(start, &mut HashSet::new(), &mut final_states);
+    let mut contracts = Vec::new();
+    for state in final_states.iter().filter(|s| s.last > 0){
+        let bid = &search.bids[state.last - 1];
+        let taken = table.tricks_axis(state.owner, bid.trump());
+        let doubling = par_doubling(bid, taken);
+        if doubling == Doubling::Double && !search.is_useful_sacrifice(state, score){
+            continue;
+        }
+        for declarer in SIDES.iter().filter(|s| s.axis() == state.owner && table.tricks(**s, bid.trump()) == taken){
+            let contract = ContractParametersGen::new_d(*declarer, *bid, doubling);
+            if !contracts.contains(&contract){
+                contracts.push(contract);
+            }
+        }
+    }
+    contracts.sort_by(|a, b| a.bid().cmp(b.bid()).then_with(|| a.declarer().index().cmp(&b.declarer().index())));
+    Ok(ParScore{score, contracts})
+}
+
+#[cfg(test)]
+mod tests{
+    use karty::suits::Suit::{Clubs, Hearts, Spades};
+    use crate::bidding::{Bid, Doubling};
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::ContractParametersGen;
+    use crate::contract::double_dummy::DoubleDummyTable;
+    use crate::player::side::Side::*;
+    use crate::player::side::SideMap;
+    use crate::score::par::par_score;
+
+    #[test]
+    fn passed_out(){
+        let table = DoubleDummyTable::new(SideMap::new_symmetric([6, 6, 6, 6, 6]));
+        let par = par_score(&table, false, false, North).unwrap();
+        assert_eq!(par.score(), 0);
+        assert!(par.contracts().is_empty());
+    }
+
+    #[test]
+    fn dealer_decides_when_both_make_one_no_trump(){
+        let table = DoubleDummyTable::new(SideMap::new_symmetric([6, 6, 6, 6, 7]));
+        assert_eq!(par_score(&table, false, false, North).unwrap().score(), 90);
+        assert_eq!(par_score(&table, false, false, East).unwrap().score(), -90);
+    }
+
+    #[test]
+    fn slam_and_overtricks(){
+        let table = DoubleDummyTable::new(SideMap::new(
+            [9, 8, 11, 13, 12],
+            [3, 4, 1, 0, 1],
+            [9, 8, 11, 13, 12],
+            [3, 4, 1, 0, 1]));
+        let par = par_score(&table, true, false, West).unwrap();
+        assert_eq!(par.score(), 2210);
+        assert_eq!(par.contracts(), &[
+            ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 7).unwrap()),
+            ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Spades), 7).unwrap())]);
+    }
+
+    #[test]
+    fn sacrifice_against_game(){
+        //East-West save in 5 clubs going two down doubled against vulnerable 4 hearts,
+        //when they are vulnerable too it costs 500, still less than 620 for the game,
+        //but it is too expensive when only they are vulnerable (500 against 420)
+        let table = DoubleDummyTable::new(SideMap::new(
+            [2, 3, 10, 6, 6],
+            [9, 7, 3, 7, 5],
+            [2, 3, 10, 6, 6],
+            [9, 7, 3, 7, 5]));
+        let par = par_score(&table, true, false, North).unwrap();
+        assert_eq!(par.score(), 300);
+        let both_vulnerable = par_score(&table, true, true, North).unwrap();
+        assert_eq!(both_vulnerable.score(), 500);
+        assert_eq!(both_vulnerable.contracts(), par.contracts());
+        assert_eq!(par.contracts(), &[
+            ContractParametersGen::new_d(East, Bid::init(TrumpGen::Colored(Clubs), 5).unwrap(), Doubling::Double),
+            ContractParametersGen::new_d(West, Bid::init(TrumpGen::Colored(Clubs), 5).unwrap(), Doubling::Double)]);
+        let par = par_score(&table, false, true, North).unwrap();
+        assert_eq!(par.score(), 420);
+        assert_eq!(par.contracts()[0].bid(), &Bid::init(TrumpGen::Colored(Hearts), 4).unwrap());
+    }
+}
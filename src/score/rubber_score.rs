@@ -1,61 +1,268 @@
-use crate::contract::deal::Deal;
-
+use karty::cards::Card2SymTrait;
+use karty::hand::CardSet;
+use karty::suits::Suit;
+use karty::symbol::CardSymbol;
+use crate::cards::trump::TrumpGen;
+use crate::contract::{ContractMechanics, ContractParametersGen};
+use crate::deal::Vulnerability;
+use crate::error::{BridgeCoreErrorGen, ContractErrorGen, ScoreError};
+use crate::player::axis::Axis;
+use crate::player::side::{SideMap, SIDES};
+use crate::score::ScoreTracker;
+use crate::score::tables::{PENALTY_UNDER_TRICK, POINTS_CONTRACTED_TRICK, POINTS_OVER_TRICK, POINTS_PREMIUM_CONTRACT, POINTS_SLAM};
 
 
 pub const GAME_WINNING_POINTS: i32 = 100;
-pub const RUBBER_WINNING_GAMES: i32 = 2;
-
+pub const RUBBER_WINNING_GAMES: u8 = 2;
+/// Rubber bonus when opponents did not win a game
+pub const RUBBER_BONUS_FAST: i32 = 700;
+/// Rubber bonus when opponents won a game
+pub const RUBBER_BONUS_SLOW: i32 = 500;
+/// Four of five trump honours in one hand
+pub const HONOURS_FOUR: i32 = 100;
+/// All five trump honours or all four aces in no trump in one hand
+pub const HONOURS_FIVE: i32 = 150;
 
-/*
-#[derive(Debug, Eq, PartialEq,  Clone)]
-pub struct Game<F: Figure, S: Suit, Um: Register<Card<F,S>>, Se: SuitExhaustRegister<S>>{
-    winning_points: i64,
-    contracts: Vec<Deal<F, S, Um, Se>>,
+/// Lowest figure index counted as honour (ten)
+const HONOUR_MIN_FIGURE_INDEX: usize = 8;
 
+/// Honours held in single hand of original deal, credited to axis of holder (above the line).
+/// In suit contract four of five top trumps score 100 and all five 150, in no trump all four aces score 150.
+/// # Example:
+/// ```
+/// use brydz_core::bidding::Bid;
+/// use brydz_core::cards::trump::TrumpGen;
+/// use brydz_core::contract::ContractParametersGen;
+/// use brydz_core::player::axis::Axis::EastWest;
+/// use brydz_core::player::side::{Side, SideMap};
+/// use brydz_core::score::rubber_score::honours_points;
+/// use karty::cards::*;
+/// use karty::hand::{CardSet, HandTrait};
+/// use karty::suits::Suit::Hearts;
+/// let mut west = CardSet::empty();
+/// for card in [ACE_HEARTS, KING_HEARTS, QUEEN_HEARTS, TEN_HEARTS]{
+///     west.insert_card(card).unwrap();
+/// }
+/// let hands = SideMap::new(CardSet::empty(), CardSet::empty(), CardSet::empty(), west);
+/// let contract = ContractParametersGen::new(Side::North, Bid::init(TrumpGen::Colored(Hearts), 4).unwrap());
+/// assert_eq!(honours_points(&contract, &hands), Some((EastWest, 100)));
+/// let contract = ContractParametersGen::new(Side::North, Bid::init(TrumpGen::NoTrump, 3).unwrap());
+/// assert_eq!(honours_points(&contract, &hands), None);
+/// ```
+pub fn honours_points(contract: &ContractParametersGen<Suit>, hands: &SideMap<CardSet>) -> Option<(Axis, i32)>{
+    SIDES.iter().find_map(|side|{
+        let hand = &hands[side];
+        let points = match contract.bid().trump(){
+            TrumpGen::Colored(trump) => match hand.into_iter()
+                .filter(|c| c.suit() == *trump && c.figure().usize_index() >= HONOUR_MIN_FIGURE_INDEX)
+                .count(){
+                5 => HONOURS_FIVE,
+                4 => HONOURS_FOUR,
+                _ => 0
+            },
+            TrumpGen::NoTrump => match hand.into_iter()
+                .filter(|c| c.figure().usize_index() == HONOUR_MIN_FIGURE_INDEX + 4)
+                .count(){
+                4 => HONOURS_FIVE,
+                _ => 0
+            }
+        };
+        match points{
+            0 => None,
+            p => Some((side.axis(), p))
+        }
+    })
 }
-impl<F: Figure, S: Suit, Um: Register<Card<F,S>>, Se: SuitExhaustRegister<S>>Game<F, S, Um, Se>{
-    pub fn new(winning_points: i64) -> Self{
-        Self{winning_points, contracts: Vec::new()}
-    }
 
-}*/
+/// Score of rubber bridge. Points for contracted tricks are written below the line and count toward game,
+/// everything else (overtricks, bonuses, penalties, honours) is written above the line.
+/// Axis that won a game becomes vulnerable, rubber ends when one axis wins two games.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ScoreTableRubber{
+    above: [i32; 2],
+    below: [i32; 2],
+    partscore: [i32; 2],
+    games: [u8; 2],
+}
 
-/*
-impl Game{
+impl ScoreTableRubber{
     pub fn new() -> Self{
-        Self{north_south_scores: Vec::new(), east_west_scores: Vec::new()}
+        Self::default()
+    }
+    /// Points written above the line
+    pub fn above(&self, axis: Axis) -> i32{
+        self.above[axis.index()]
+    }
+    /// Points written below the line in all games
+    pub fn below(&self, axis: Axis) -> i32{
+        self.below[axis.index()]
+    }
+    /// Points below the line in current (unfinished) game
+    pub fn partscore(&self, axis: Axis) -> i32{
+        self.partscore[axis.index()]
+    }
+    pub fn games(&self, axis: Axis) -> u8{
+        self.games[axis.index()]
+    }
+    pub fn is_vulnerable(&self, axis: Axis) -> bool{
+        self.games(axis) > 0
+    }
+    pub fn vulnerability(&self) -> Vulnerability{
+        Vulnerability::from_axes(self.is_vulnerable(Axis::NorthSouth), self.is_vulnerable(Axis::EastWest))
+    }
+    pub fn is_finished(&self) -> bool{
+        self.games.iter().any(|g| *g >= RUBBER_WINNING_GAMES)
     }
-    pub fn sum(&self, axis: Axis) -> i32{
-        self[axis].iter().sum()
 
+    /// Writes result of contract with given number of tricks taken by declarer.
+    /// # Example:
+    /// ```
+    /// use brydz_core::bidding::Bid;
+    /// use brydz_core::bidding::Doubling::Double;
+    /// use brydz_core::cards::trump::TrumpGen;
+    /// use brydz_core::contract::ContractParametersGen;
+    /// use brydz_core::player::axis::Axis::{EastWest, NorthSouth};
+    /// use brydz_core::player::side::Side::{North, West};
+    /// use brydz_core::score::rubber_score::ScoreTableRubber;
+    /// use karty::suits::Suit::{Clubs, Hearts};
+    /// let mut score = ScoreTableRubber::new();
+    /// let contract = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Hearts), 2).unwrap());
+    /// score.update_contract(&contract, 9).unwrap();
+    /// assert_eq!((score.below(NorthSouth), score.above(NorthSouth)), (60, 30));
+    /// let contract = ContractParametersGen::new_d(West, Bid::init(TrumpGen::Colored(Clubs), 3).unwrap(), Double);
+    /// score.update_contract(&contract, 8).unwrap();
+    /// assert_eq!(score.above(NorthSouth), 130);
+    /// let contract = ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 1).unwrap());
+    /// score.update_contract(&contract, 7).unwrap();
+    /// assert_eq!(score.games(NorthSouth), 1);
+    /// assert!(score.is_vulnerable(NorthSouth));
+    /// assert!(!score.is_vulnerable(EastWest));
+    /// assert_eq!(score.partscore(NorthSouth), 0);
+    /// ```
+    pub fn update_contract(&mut self, contract: &ContractParametersGen<Suit>, taken: u8) -> Result<(), ScoreError>{
+        if self.is_finished(){
+            return Err(ScoreError::RubberFinished)
+        }
+        let declarer_axis = contract.declarer().axis();
+        let declarer = declarer_axis.index();
+        let vulnerability = self.is_vulnerable(declarer_axis);
+        if taken < contract.bid().number_normalised(){
+            let penalty = PENALTY_UNDER_TRICK.penalty_checked(contract, taken, vulnerability)?;
+            self.above[declarer_axis.opposite().index()] += penalty;
+            return Ok(())
+        }
+        let contracted = POINTS_CONTRACTED_TRICK.points(contract, taken);
+        self.below[declarer] += contracted;
+        self.partscore[declarer] += contracted;
+        self.above[declarer] += POINTS_OVER_TRICK.points(contract, taken, vulnerability)
+            + POINTS_SLAM.points(contract, taken, vulnerability)
+            + POINTS_PREMIUM_CONTRACT.points(contract, taken);
+        if self.partscore[declarer] >= GAME_WINNING_POINTS{
+            self.games[declarer] += 1;
+            self.partscore = [0, 0];
+            if self.games[declarer] >= RUBBER_WINNING_GAMES{
+                self.above[declarer] += match self.games[1 - declarer]{
+                    0 => RUBBER_BONUS_FAST,
+                    _ => RUBBER_BONUS_SLOW
+                };
+            }
+        }
+        Ok(())
     }
-    pub fn winner(&self) -> Option<Axis>{
-        match self.sum(NorthSouth).cmp(&self.sum(EastWest)){
-            Ordering::Equal => None,
-            Ordering::Greater => Some(NorthSouth),
-            Ordering::Less => Some(EastWest)
 
+    /// Writes result of contract and honours held in original hands.
+    pub fn update_contract_with_honours(&mut self, contract: &ContractParametersGen<Suit>, taken: u8, hands: &SideMap<CardSet>) -> Result<(), ScoreError>{
+        self.update_contract(contract, taken)?;
+        if let Some((axis, points)) = honours_points(contract, hands){
+            self.above[axis.index()] += points;
         }
+        Ok(())
     }
-    pub fn is_finished(&self) -> bool {
-        (self.sum(NorthSouth) >= 100) && (self.sum(EastWest) >= 100)
+
+    /// Writes result of completed contract and honours held in original hands.
+    pub fn update_with_honours<Co: ContractMechanics<Card = Crd>, Crd: Card2SymTrait<Suit = Suit>>(&mut self, deal: &Co, hands: &SideMap<CardSet>) -> Result<(), BridgeCoreErrorGen<Crd>>{
+        if !deal.is_completed(){
+            return Err(BridgeCoreErrorGen::Contract(ContractErrorGen::DealIncomplete))
+        }
+        let taken = deal.total_tricks_taken_axis(deal.contract_spec().declarer().axis()) as u8;
+        Ok(self.update_contract_with_honours(deal.contract_spec(), taken, hands)?)
     }
 }
 
-impl Default for Game {
-     fn default() -> Self {
-        Self::new()
-     }
+impl<Co: ContractMechanics<Card = Crd>, Crd: Card2SymTrait<Suit = Suit>> ScoreTracker<Co, Crd> for ScoreTableRubber{
+    fn winner_axis(&self) -> Option<Axis> {
+        let ns = <Self as ScoreTracker<Co, Crd>>::points(self, &Axis::NorthSouth);
+        let ew = <Self as ScoreTracker<Co, Crd>>::points(self, &Axis::EastWest);
+        match ns.cmp(&ew){
+            std::cmp::Ordering::Greater => Some(Axis::NorthSouth),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Less => Some(Axis::EastWest)
+        }
+    }
+
+    /// Writes result of completed contract, honours are not scored (use [`ScoreTableRubber::update_with_honours`]).
+    fn update(&mut self, deal: &Co) -> Result<(), BridgeCoreErrorGen<Crd>> {
+        if !deal.is_completed(){
+            return Err(BridgeCoreErrorGen::Contract(ContractErrorGen::DealIncomplete))
+        }
+        let taken = deal.total_tricks_taken_axis(deal.contract_spec().declarer().axis()) as u8;
+        Ok(self.update_contract(deal.contract_spec(), taken)?)
+    }
+
+    fn points(&self, axis: &Axis) -> i32 {
+        self.above(*axis) + self.below(*axis)
+    }
 }
 
-impl Index<Axis> for Game{
-    type Output = Vec<i32>;
+#[cfg(test)]
+mod tests{
+    use karty::suits::Suit::{Diamonds, Spades};
+    use crate::bidding::Bid;
+    use crate::bidding::Doubling::Redouble;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::{Contract, ContractParametersGen};
+    use crate::error::ScoreError;
+    use crate::player::axis::Axis::{EastWest, NorthSouth};
+    use crate::player::side::Side::{East, North, South};
+    use crate::score::rubber_score::ScoreTableRubber;
+    use crate::score::ScoreTracker;
 
-    fn index(&self, index: Axis) -> &Self::Output {
-        match index{
-            Axis::NorthSouth => &self.north_south_scores,
-            Axis::EastWest => &self.east_west_scores
-        }
+    #[test]
+    fn rubber_two_straight_games(){
+        let mut score = ScoreTableRubber::new();
+        let four_spades = ContractParametersGen::new(South, Bid::init(TrumpGen::Colored(Spades), 4).unwrap());
+        score.update_contract(&four_spades, 10).unwrap();
+        score.update_contract(&four_spades, 11).unwrap();
+        assert!(score.is_finished());
+        assert_eq!(score.below(NorthSouth), 240);
+        // overtrick + 700
+        assert_eq!(score.above(NorthSouth), 730);
+        assert_eq!(<ScoreTableRubber as ScoreTracker<Contract, _>>::winner_axis(&score), Some(NorthSouth));
+        assert_eq!(score.update_contract(&four_spades, 10), Err(ScoreError::RubberFinished));
+    }
+
+    #[test]
+    fn rubber_three_games_and_partscores(){
+        let mut score = ScoreTableRubber::new();
+        let two_diamonds = ContractParametersGen::new(East, Bid::init(TrumpGen::Colored(Diamonds), 2).unwrap());
+        score.update_contract(&two_diamonds, 8).unwrap();
+        assert_eq!(score.partscore(EastWest), 40);
+        let three_no_trump = ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 3).unwrap());
+        score.update_contract(&three_no_trump, 9).unwrap();
+        // partscore of opponents is erased by game
+        assert_eq!(score.partscore(EastWest), 0);
+        assert_eq!(score.below(EastWest), 40);
+        let three_diamonds = ContractParametersGen::new(East, Bid::init(TrumpGen::Colored(Diamonds), 3).unwrap());
+        score.update_contract(&three_diamonds, 9).unwrap();
+        score.update_contract(&three_diamonds, 9).unwrap();
+        assert_eq!(score.games(EastWest), 1);
+        assert_eq!(score.below(EastWest), 160);
+        // vulnerable, redoubled, down two: 1000 above the line for defenders
+        let redoubled = ContractParametersGen::new_d(East, Bid::init(TrumpGen::Colored(Spades), 4).unwrap(), Redouble);
+        score.update_contract(&redoubled, 8).unwrap();
+        assert_eq!(score.above(NorthSouth), 1000);
+        score.update_contract(&three_no_trump, 9).unwrap();
+        assert!(score.is_finished());
+        assert_eq!(score.above(NorthSouth), 1500);
     }
 }
-*/
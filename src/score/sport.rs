@@ -1,8 +1,9 @@
 use std::cmp::Ordering;
 use karty::cards::Card2SymTrait;
 use karty::suits::{Suit};
-use crate::contract::{ContractMechanics};
-use crate::error::{BridgeCoreErrorGen, ContractErrorGen};
+use crate::contract::{ContractMechanics, ContractParametersGen};
+use crate::deal::Vulnerability;
+use crate::error::{BridgeCoreErrorGen, ContractErrorGen, ScoreError};
 use crate::player::axis::Axis;
 use crate::score::calculation::ScoreIngredient;
 use crate::score::ScoreTracker;
@@ -23,6 +24,70 @@ impl ScoreTableSport{
         Self{ns_score: 0, ew_score: 0, ns_vulnerability, ew_vulnerability}
     }
 
+    /// ```
+    /// use brydz_core::deal::Vulnerability;
+    /// use brydz_core::score::sport::ScoreTableSport;
+    /// let table = ScoreTableSport::with_vulnerability(Vulnerability::EastWest);
+    /// assert_eq!(table.vulnerability(), Vulnerability::EastWest);
+    /// ```
+    pub fn with_vulnerability(vulnerability: Vulnerability) -> Self{
+        Self::new(vulnerability.ns_vulnerable(), vulnerability.ew_vulnerable())
+    }
+
+    pub fn vulnerability(&self) -> Vulnerability{
+        Vulnerability::from_axes(self.ns_vulnerability, self.ew_vulnerability)
+    }
+
+    /// Calculates score of declarer's axis for played contract, negative when contract is defeated
+    /// (then it is penalty written for defenders).
+    /// # Example:
+    /// ```
+    /// use brydz_core::bidding::Bid;
+    /// use brydz_core::bidding::Doubling::Double;
+    /// use brydz_core::cards::trump::TrumpGen;
+    /// use brydz_core::contract::ContractParametersGen;
+    /// use brydz_core::player::side::Side::North;
+    /// use brydz_core::score::sport::ScoreTableSport;
+    /// use karty::suits::Suit::Spades;
+    /// let contract = ContractParametersGen::new(North, Bid::init(TrumpGen::Colored(Spades), 4).unwrap());
+    /// assert_eq!(ScoreTableSport::contract_points(&contract, 10, false), Ok(420));
+    /// assert_eq!(ScoreTableSport::contract_points(&contract, 11, true), Ok(650));
+    /// assert_eq!(ScoreTableSport::contract_points(&contract, 9, true), Ok(-100));
+    /// let contract = ContractParametersGen::new_d(North, Bid::init(TrumpGen::NoTrump, 3).unwrap(), Double);
+    /// assert_eq!(ScoreTableSport::contract_points(&contract, 9, false), Ok(550));
+    /// assert_eq!(ScoreTableSport::contract_points(&contract, 5, false), Ok(-800));
+    /// ```
+    pub fn contract_points(contract: &ContractParametersGen<Suit>, taken: u8, vulnerability: bool) -> Result<i32, ScoreError>{
+        if taken < contract.bid().number_normalised(){
+            return PENALTY_UNDER_TRICK.penalty_checked(contract, taken, vulnerability).map(|p| -p)
+        }
+        let contracted_points = POINTS_CONTRACTED_TRICK.calculate(contract, taken, false);
+        let overtrick_bonus = POINTS_OVER_TRICK.points(contract, taken, vulnerability);
+        let slam_bonus = POINTS_SLAM.points(contract, taken, vulnerability);
+        let premium_game_points = POINTS_PREMIUM_SPORT.points(contracted_points, vulnerability);
+        let premium_contract_points = POINTS_PREMIUM_CONTRACT.points(contract, taken);
+        Ok(contracted_points + overtrick_bonus + slam_bonus + premium_game_points + premium_contract_points)
+    }
+
+    /// Score of completed contract from the perspective of `axis` (negative for losing side),
+    /// vulnerability is taken from table.
+    pub fn completed_contract_points<Co: ContractMechanics<Card = Crd>, Crd: Card2SymTrait<Suit = Suit>>(&self, contract: &Co, axis: Axis) -> Result<i32, BridgeCoreErrorGen<Crd>>{
+        if !contract.is_completed(){
+            return Err(BridgeCoreErrorGen::Contract(ContractErrorGen::DealIncomplete))
+        }
+        let declarer_axis = contract.contract_spec().declarer().axis();
+        let vulnerability = match declarer_axis{
+            Axis::EastWest => self.ew_vulnerability,
+            Axis::NorthSouth => self.ns_vulnerability
+        };
+        let taken = contract.total_tricks_taken_axis(declarer_axis) as u8;
+        let points = Self::contract_points(contract.contract_spec(), taken, vulnerability)?;
+        match axis == declarer_axis{
+            true => Ok(points),
+            false => Ok(-points)
+        }
+    }
+
 }
 
 
@@ -135,25 +200,10 @@ for ScoreTableSport{
                 Axis::EastWest => self.ew_vulnerability,
                 Axis::NorthSouth => self.ns_vulnerability
             };
-            let defender_vulnerability = match axis{
-                Axis::EastWest => self.ns_vulnerability,
-                Axis::NorthSouth => self.ew_vulnerability
-            };
             let taken = deal.total_tricks_taken_axis(axis) as u8;
-            let contracted_points = POINTS_CONTRACTED_TRICK.calculate(deal.contract_spec(), taken, false);
-            let overtrick_bonus = POINTS_OVER_TRICK.points(deal.contract_spec(), taken, vulnerability);
-            let slam_bonus = POINTS_SLAM.points(deal.contract_spec(), taken, vulnerability);
-            let premium_game_points = POINTS_PREMIUM_SPORT.points(contracted_points, vulnerability);
-            let premium_contract_points = POINTS_PREMIUM_CONTRACT.points(deal.contract_spec(), taken);
-            let penalty_undertricks = match PENALTY_UNDER_TRICK.penalty_checked(deal.contract_spec(), taken, defender_vulnerability){
-                Ok(points) => points,
-                Err(e) => return Err(BridgeCoreErrorGen::Score(e))
-            };
-            println!("contracted: {}, overtrick: {}", &contracted_points, &overtrick_bonus);
-
-            let declarer_axis_score = contracted_points+ overtrick_bonus + slam_bonus
-                + premium_game_points + premium_contract_points;
-            let defender_axis_score = penalty_undertricks;
+            let points = Self::contract_points(deal.contract_spec(), taken, vulnerability)?;
+            let declarer_axis_score = points.max(0);
+            let defender_axis_score = (-points).max(0);
 
             match axis{
                 Axis::NorthSouth => {
@@ -182,3 +232,69 @@ for ScoreTableSport{
     }
 }
 
+#[cfg(test)]
+mod tests{
+    use karty::cards::{Card, Card2SymTrait, STANDARD_DECK};
+    use karty::suits::SUITS;
+    use crate::bidding::Bid;
+    use crate::cards::trump::TrumpGen;
+    use crate::contract::{Contract, ContractMechanics, ContractParametersGen};
+    use crate::player::axis::Axis::{EastWest, NorthSouth};
+    use crate::player::side::Side;
+    use crate::player::side::Side::{East, North};
+    use crate::score::ScoreTracker;
+    use crate::score::sport::ScoreTableSport;
+
+    /// Plays no trump contract so that trick `i` is won by `winners[i]`,
+    /// last trick must be won by winner of the previous one.
+    fn play_no_trump(contract: &mut Contract, winners: [Side; 13]){
+        let mut suits: Vec<Vec<Card>> = SUITS.iter().map(|suit| {
+            let mut cards: Vec<Card> = STANDARD_DECK.iter().filter(|c| c.suit() == *suit).copied().collect();
+            cards.sort_by_key(|c| c.figure());
+            cards
+        }).collect();
+        for (trick, winner) in winners.into_iter().enumerate(){
+            let leader = contract.current_side();
+            for i in 0..4u8{
+                let side = leader.next_i(i);
+                let card = match trick{
+                    12 => suits[i as usize].remove(0),
+                    n if side == winner => suits[n / 3].pop().unwrap(),
+                    n => suits[n / 3].remove(0)
+                };
+                contract.insert_card(side, card).unwrap();
+            }
+        }
+    }
+
+    fn points(score: &ScoreTableSport) -> (i32, i32){
+        (<ScoreTableSport as ScoreTracker<Contract, Card>>::points(score, &NorthSouth),
+         <ScoreTableSport as ScoreTracker<Contract, Card>>::points(score, &EastWest))
+    }
+
+    #[test]
+    fn defeated_contract_penalty_uses_declarer_vulnerability(){
+        let mut contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+        play_no_trump(&mut contract, [North, North, North, North, North, North, North, North, East, East, East, East, East]);
+        assert_eq!(contract.total_tricks_taken_axis(NorthSouth), 8);
+
+        // declarer vulnerable, defenders not: 100 for vulnerable undertrick, nothing for declarer's 8 tricks
+        let mut score = ScoreTableSport::new(true, false);
+        score.update(&contract).unwrap();
+        assert_eq!(points(&score), (0, 100));
+
+        let mut score = ScoreTableSport::new(false, true);
+        score.update(&contract).unwrap();
+        assert_eq!(points(&score), (0, 50));
+    }
+
+    #[test]
+    fn penalty_for_more_than_seven_undertricks(){
+        let mut contract = Contract::new(ContractParametersGen::new(North, Bid::init(TrumpGen::NoTrump, 3).unwrap()));
+        play_no_trump(&mut contract, [East; 13]);
+        assert_eq!(contract.total_tricks_taken_axis(NorthSouth), 0);
+        let mut score = ScoreTableSport::new(false, true);
+        score.update(&contract).unwrap();
+        assert_eq!(points(&score), (0, 450));
+    }
+}
@@ -3,7 +3,6 @@ use karty::suits::Suit;
 use crate::bidding::Doubling;
 use crate::contract::ContractParametersGen;
 use crate::cards::trump::TrumpGen;
-use crate::meta::HALF_TRICKS;
 use crate::score::calculation::ScoreIngredient;
 
 pub struct PointsContractedTrick{
@@ -39,9 +38,10 @@ impl PointsContractedTrick{
     /// assert_eq!(points_table.points(&contract, 6), 0 );
     /// assert_eq!(points_table.points(&contract, 7), 160 );
     /// assert_eq!(points_table.points(&contract, 8), 160 );
+    /// let contract = ContractParametersGen::new(North, Bid::init(NoTrump, 3).unwrap());
+    /// assert_eq!(points_table.points(&contract, 8), 0 );
     ///
     /// ```
-
     pub fn points(&self, contract: &ContractParametersGen<Suit>, taken: u8) -> i32{
         let multiplier = match contract.doubling(){
             Doubling::None => 1,
@@ -64,17 +64,11 @@ impl PointsContractedTrick{
                 }
             }
             TrumpGen::NoTrump => {
-                if taken <= HALF_TRICKS{
-                    0
-                } else{
-                    let number = if contract.bid().number_normalised() <= taken{
-                        contract.bid().number() - 1
-                    }
-                    else{
-                        0
-                    };
+                if contract.bid().number_normalised() <= taken{
+                    let number = contract.bid().number() - 1;
                     (self.nt_first + (self.nt_next * i32::from(number))) * multiplier
-
+                } else{
+                    0
                 }
 
             }
@@ -3,7 +3,7 @@ use karty::suits::{SuitTrait, Suit};
 use crate::bidding::Doubling;
 use crate::contract::ContractParametersGen;
 use crate::error::{ScoreError};
-use crate::meta::{SIZE_GREATER_HALF_TRICKS};
+use crate::meta::{QUARTER_SIZE};
 
 
 #[derive(Default)]
@@ -86,7 +86,8 @@ impl<S: SuitTrait, const L: usize> PenaltyUnderTrick<S, L>{
     }
 }
 
-pub const PENALTY_UNDER_TRICK: PenaltyUnderTrick<Suit, SIZE_GREATER_HALF_TRICKS> = PenaltyUnderTrick{
+/// Seven level contract can be defeated by up to 13 tricks
+pub const PENALTY_UNDER_TRICK: PenaltyUnderTrick<Suit, QUARTER_SIZE> = PenaltyUnderTrick{
     penalty_tables: [
         FIRST_TRICK_PENALTY,
         LEVEL_2_TRICK_PENALTY,
@@ -94,6 +95,12 @@ pub const PENALTY_UNDER_TRICK: PenaltyUnderTrick<Suit, SIZE_GREATER_HALF_TRICKS>
         LEVEL_3_TRICK_PENALTY,
         LEVEL_3_TRICK_PENALTY,
         LEVEL_3_TRICK_PENALTY,
+        LEVEL_3_TRICK_PENALTY,
+        LEVEL_3_TRICK_PENALTY,
+        LEVEL_3_TRICK_PENALTY,
+        LEVEL_3_TRICK_PENALTY,
+        LEVEL_3_TRICK_PENALTY,
+        LEVEL_3_TRICK_PENALTY,
         LEVEL_3_TRICK_PENALTY
     ],
     _phantom: PhantomData
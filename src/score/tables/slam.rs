@@ -47,28 +47,14 @@ impl PointsSlam{
     pub fn points(&self, contract: &ContractParametersGen<Suit>, taken: u8, vulnerable: bool) -> i32{
         let declared = contract.bid().number_normalised() as usize;
         match declared{
-            n if n == QUARTER_SIZE => {
-                if taken >= declared as u8{
-                    match vulnerable{
-                        true => self.vulnerable_great_slam,
-                        false => self.not_vulnerable_great_slam
-                    }
-                }
-                else{
-                    0
-                }
+            n if n == QUARTER_SIZE && taken >= declared as u8 => match vulnerable{
+                true => self.vulnerable_great_slam,
+                false => self.not_vulnerable_great_slam
+            },
+            n1 if n1 == (QUARTER_SIZE - 1) && taken >= declared as u8 => match vulnerable{
+                true => self.vulnerable_small_slam,
+                false => self.not_vulnerable_small_slam
             },
-            n1 if n1 == (QUARTER_SIZE - 1) => {
-                if taken >= declared as u8{
-                    match vulnerable{
-                        true => self.vulnerable_small_slam,
-                        false => self.not_vulnerable_small_slam
-                    }
-                }
-                else{
-                    0
-                }
-            }
             _ => {0}
         }
     }